@@ -0,0 +1,47 @@
+#[macro_use]
+extern crate criterion;
+extern crate ansi_term;
+
+use std::fmt::Write;
+
+use criterion::{Criterion, black_box};
+use ansi_term::{ANSIStrings, Style};
+use ansi_term::Colour::*;
+
+// These measure the code that runs every time a painted string gets
+// displayed, which is what syntax highlighters and other heavy emitters
+// spend their time in.
+
+fn prefix(c: &mut Criterion) {
+    let styles = [
+        ("plain",  Style::default()),
+        ("named",  Red.bold()),
+        ("fixed",  Fixed(208).on(Fixed(17)).underline()),
+        ("rgb",    RGB(255, 128, 0).on(RGB(10, 20, 30)).italic()),
+    ];
+
+    for &(name, style) in &styles {
+        let mut buf = String::with_capacity(64);
+        c.bench_function(&format!("prefix/{}", name), |b| b.iter(|| {
+            buf.clear();
+            write!(buf, "{}", black_box(style).prefix()).unwrap();
+        }));
+    }
+}
+
+fn strings(c: &mut Criterion) {
+    let line = [
+        Blue.bold().paint("fn"), Style::default().paint(" "),
+        Yellow.paint("main"), Style::default().paint("() {"),
+        Fixed(244).italic().paint("// comment"), Style::default().paint("}"),
+    ];
+
+    let mut buf = String::with_capacity(256);
+    c.bench_function("strings/line", |b| b.iter(|| {
+        buf.clear();
+        write!(buf, "{}", ANSIStrings(black_box(&line))).unwrap();
+    }));
+}
+
+criterion_group!(benches, prefix, strings);
+criterion_main!(benches);
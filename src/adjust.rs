@@ -0,0 +1,211 @@
+use style::Colour;
+use web::{hsl, to_hsl};
+
+
+impl Colour {
+
+    /// Returns a lighter version of this colour, as an `RGB` colour, with
+    /// the given amount from zero to one added to its lightness. Named and
+    /// `Fixed` colours get converted using xterm’s palette first.
+    ///
+    /// This is for deriving shades, such as for highlighted or secondary
+    /// text, from one colour in a configuration. The hue and saturation stay
+    /// the same, and a lightness of one is white.
+    ///
+    /// This method is only available with the `rgb` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::Colour;
+    ///
+    /// assert_eq!(Colour::RGB(0, 0, 128).lighten(0.25), Colour::RGB(0, 0, 255));
+    /// assert_eq!(Colour::Red.lighten(1.0), Colour::RGB(255, 255, 255));
+    /// ```
+    pub fn lighten(self, amount: f32) -> Colour {
+        let (hue, saturation, lightness) = to_hsl(self);
+        hsl(hue, saturation, (lightness + amount).clamp(0.0, 1.0))
+    }
+
+    /// Returns a darker version of this colour, as an `RGB` colour, with the
+    /// given amount from zero to one taken away from its lightness. Named
+    /// and `Fixed` colours get converted using xterm’s palette first.
+    ///
+    /// This is for deriving shades, such as for disabled or secondary text,
+    /// from one colour in a configuration. The hue and saturation stay the
+    /// same, and a lightness of zero is black.
+    ///
+    /// This method is only available with the `rgb` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::Colour;
+    ///
+    /// assert_eq!(Colour::RGB(0, 0, 255).darken(0.25), Colour::RGB(0, 0, 128));
+    /// assert_eq!(Colour::White.darken(1.0), Colour::RGB(0, 0, 0));
+    /// ```
+    pub fn darken(self, amount: f32) -> Colour {
+        self.lighten(-amount)
+    }
+
+    /// Returns a more vivid version of this colour, as an `RGB` colour, with
+    /// the given amount from zero to one added to its saturation. Named and
+    /// `Fixed` colours get converted using xterm’s palette first.
+    ///
+    /// The hue and lightness stay the same, so colours from a theme keep
+    /// the same relationships to each other.
+    ///
+    /// This method is only available with the `rgb` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::Colour;
+    ///
+    /// assert_eq!(Colour::RGB(150, 100, 100).saturate(0.5), Colour::RGB(213, 38, 38));
+    /// ```
+    pub fn saturate(self, amount: f32) -> Colour {
+        let (hue, saturation, lightness) = to_hsl(self);
+        hsl(hue, (saturation + amount).clamp(0.0, 1.0), lightness)
+    }
+
+    /// Returns a duller version of this colour, as an `RGB` colour, with the
+    /// given amount from zero to one taken away from its saturation. Named
+    /// and `Fixed` colours get converted using xterm’s palette first.
+    ///
+    /// This is for de-emphasising text, such as for inactive parts of an
+    /// interface, while keeping the hue it has in the rest of the theme.
+    ///
+    /// This method is only available with the `rgb` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::Colour;
+    ///
+    /// assert_eq!(Colour::RGB(200, 50, 50).desaturate(0.5), Colour::RGB(138, 113, 113));
+    /// ```
+    pub fn desaturate(self, amount: f32) -> Colour {
+        self.saturate(-amount)
+    }
+
+    /// Returns the grey with the same lightness as this colour, as an `RGB`
+    /// colour. This is the same as desaturating it completely.
+    ///
+    /// This method is only available with the `rgb` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::Colour;
+    ///
+    /// assert_eq!(Colour::RGB(200, 50, 50).grayscale(), Colour::RGB(125, 125, 125));
+    /// ```
+    pub fn grayscale(self) -> Colour {
+        self.desaturate(1.0)
+    }
+
+    /// Returns the colour opposite this one on the colour wheel, as an `RGB`
+    /// colour, with the same saturation and lightness. Named and `Fixed`
+    /// colours get converted using xterm’s palette first.
+    ///
+    /// This is for picking an accent that stands out against a colour chosen
+    /// by the user. Greys have no hue, so their complement is themselves.
+    ///
+    /// This method is only available with the `rgb` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::Colour;
+    ///
+    /// assert_eq!(Colour::RGB(255, 0, 0).complement(), Colour::RGB(0, 255, 255));
+    /// ```
+    pub fn complement(self) -> Colour {
+        self.rotate_hue(180.0)
+    }
+
+    /// Returns the two colours either side of this one on the colour wheel,
+    /// 30 degrees away, as `RGB` colours with the same saturation and
+    /// lightness. Together with this colour they make a set that sits well
+    /// together.
+    ///
+    /// This method is only available with the `rgb` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::Colour;
+    ///
+    /// assert_eq!(Colour::RGB(255, 0, 0).analogous(),
+    ///            [ Colour::RGB(255, 0, 128), Colour::RGB(255, 128, 0) ]);
+    /// ```
+    pub fn analogous(self) -> [Colour; 2] {
+        [ self.rotate_hue(-30.0), self.rotate_hue(30.0) ]
+    }
+
+    /// Returns the two colours that are a third of the way around the colour
+    /// wheel from this one, as `RGB` colours with the same saturation and
+    /// lightness. Together with this colour they make an evenly spaced set
+    /// of three.
+    ///
+    /// This method is only available with the `rgb` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::Colour;
+    ///
+    /// assert_eq!(Colour::RGB(255, 0, 0).triadic(),
+    ///            [ Colour::RGB(0, 255, 0), Colour::RGB(0, 0, 255) ]);
+    /// ```
+    pub fn triadic(self) -> [Colour; 2] {
+        [ self.rotate_hue(120.0), self.rotate_hue(240.0) ]
+    }
+
+    /// Returns this colour with its hue turned by the given number of
+    /// degrees, as an `RGB` colour.
+    fn rotate_hue(self, degrees: f32) -> Colour {
+        let (hue, saturation, lightness) = to_hsl(self);
+        hsl((hue + degrees).rem_euclid(360.0), saturation, lightness)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use style::Colour::*;
+
+    #[test]
+    fn keeps_hue() {
+        assert_eq!(RGB(200, 100, 50).lighten(0.1), RGB(213, 130, 88));
+        assert_eq!(RGB(200, 100, 50).darken(0.1), RGB(159, 80, 40));
+    }
+
+    #[test]
+    fn greys_stay_grey() {
+        assert_eq!(RGB(128, 128, 128).lighten(0.2), RGB(179, 179, 179));
+        assert_eq!(RGB(10, 10, 10).darken(0.5), RGB(0, 0, 0));
+    }
+
+    #[test]
+    fn saturation_limits() {
+        assert_eq!(RGB(200, 100, 50).saturate(1.0), RGB(250, 83, 0));
+        assert_eq!(RGB(128, 128, 128).grayscale(), RGB(128, 128, 128));
+        assert_eq!(Red.grayscale(), RGB(103, 103, 103));
+    }
+
+    #[test]
+    fn wheel_wraps_around() {
+        assert_eq!(RGB(200, 100, 50).complement(), RGB(50, 150, 200));
+        assert_eq!(RGB(200, 100, 50).analogous(), [ RGB(200, 50, 75), RGB(200, 175, 50) ]);
+        assert_eq!(RGB(128, 128, 128).complement(), RGB(128, 128, 128));
+    }
+
+    #[test]
+    fn no_change() {
+        assert_eq!(RGB(95, 135, 175).lighten(0.0), RGB(95, 135, 175));
+        assert_eq!(White.darken(0.0), White.to_rgb());
+    }
+}
@@ -1,8 +1,13 @@
 use style::{Colour, Style};
 
 use std::fmt;
+use std::str;
 
-use write::AnyWrite;
+use compiled::CodeBuffer;
+use filter::filter_style;
+use hook::write_code;
+#[cfg(any(feature = "fixed", feature = "rgb"))]
+use syntax::{colour_syntax, ColourSyntax};
 
 
 // ---- generating ANSI codes ----
@@ -10,7 +15,11 @@ use write::AnyWrite;
 impl Style {
 
     /// Write any bytes that go *before* a piece of text to the given writer.
-    fn write_prefix<W: AnyWrite + ?Sized>(&self, f: &mut W) -> Result<(), W::Error> {
+    ///
+    /// This gets called for every string that gets painted, so it writes
+    /// each piece of the code with `write_str` rather than going through the
+    /// formatting machinery.
+    fn write_prefix(&self, f: &mut dyn fmt::Write) -> fmt::Result {
 
         // If there are actually no styles here, then don’t write *any* codes
         // as the prefix. An empty ANSI code may not affect the terminal
@@ -21,54 +30,46 @@ impl Style {
 
         // Write the codes’ prefix, then write numbers, separated by
         // semicolons, for each text style we want to apply.
-        write!(f, "\x1B[")?;
-        let mut written_anything = false;
-
-        {
-            let mut write_char = |c| {
-                if written_anything { write!(f, ";")?; }
-                written_anything = true;
-                write!(f, "{}", c)?;
-                Ok(())
-            };
-
-            if self.is_bold           { write_char('1')? }
-            if self.is_dimmed         { write_char('2')? }
-            if self.is_italic         { write_char('3')? }
-            if self.is_underline      { write_char('4')? }
-            if self.is_blink          { write_char('5')? }
-            if self.is_reverse        { write_char('7')? }
-            if self.is_hidden         { write_char('8')? }
-            if self.is_strikethrough  { write_char('9')? }
-        }
+        let mut separator = "\x1B[";
+        let mut write_code = |f: &mut dyn fmt::Write, code: &str| {
+            f.write_str(separator)?;
+            separator = ";";
+            f.write_str(code)
+        };
+
+        if self.is_bold           { write_code(f, "1")? }
+        if self.is_dimmed         { write_code(f, "2")? }
+        if self.is_italic         { write_code(f, "3")? }
+        if self.is_underline      { write_code(f, "4")? }
+        if self.is_blink          { write_code(f, "5")? }
+        if self.is_reverse        { write_code(f, "7")? }
+        if self.is_hidden         { write_code(f, "8")? }
+        if self.is_strikethrough  { write_code(f, "9")? }
 
         // The foreground and background colours, if specified, need to be
         // handled specially because the number codes are more complicated.
         // (see `write_background_code` and `write_foreground_code`)
         if let Some(bg) = self.background {
-            if written_anything { write!(f, ";")?; }
-            written_anything = true;
+            write_code(f, "")?;
             bg.write_background_code(f)?;
         }
 
         if let Some(fg) = self.foreground {
-            if written_anything { write!(f, ";")?; }
+            write_code(f, "")?;
             fg.write_foreground_code(f)?;
         }
 
         // All the codes end with an `m`, because reasons.
-        write!(f, "m")?;
-
-        Ok(())
+        f.write_str("m")
     }
 
     /// Write any bytes that go *after* a piece of text to the given writer.
-    fn write_suffix<W: AnyWrite + ?Sized>(&self, f: &mut W) -> Result<(), W::Error> {
+    fn write_suffix(&self, f: &mut dyn fmt::Write) -> fmt::Result {
         if self.is_plain() {
             Ok(())
         }
         else {
-            write!(f, "{}", RESET)
+            f.write_str(RESET)
         }
     }
 }
@@ -80,37 +81,106 @@ pub static RESET: &str = "\x1B[0m";
 
 
 impl Colour {
-    fn write_foreground_code<W: AnyWrite + ?Sized>(&self, f: &mut W) -> Result<(), W::Error> {
+    fn write_foreground_code(&self, f: &mut dyn fmt::Write) -> fmt::Result {
         match *self {
-            Colour::Black      => write!(f, "30"),
-            Colour::Red        => write!(f, "31"),
-            Colour::Green      => write!(f, "32"),
-            Colour::Yellow     => write!(f, "33"),
-            Colour::Blue       => write!(f, "34"),
-            Colour::Purple     => write!(f, "35"),
-            Colour::Cyan       => write!(f, "36"),
-            Colour::White      => write!(f, "37"),
-            Colour::Fixed(num) => write!(f, "38;5;{}", &num),
-            Colour::RGB(r,g,b) => write!(f, "38;2;{};{};{}", &r, &g, &b),
+            Colour::Black      => f.write_str("30"),
+            Colour::Red        => f.write_str("31"),
+            Colour::Green      => f.write_str("32"),
+            Colour::Yellow     => f.write_str("33"),
+            Colour::Blue       => f.write_str("34"),
+            Colour::Purple     => f.write_str("35"),
+            Colour::Cyan       => f.write_str("36"),
+            Colour::White      => f.write_str("37"),
+            #[cfg(feature = "fixed")]
+            Colour::Fixed(num) => { write_extended(f, "38", "5")?; write_number(f, num) },
+            #[cfg(feature = "rgb")]
+            Colour::RGB(r,g,b) => { write_extended(f, "38", "2")?; write_rgb(f, r, g, b) },
+            #[cfg(feature = "cmyk")]
+            Colour::CMY(c,m,y) => { write_extended(f, "38", "3")?; write_rgb(f, c, m, y) },
+            #[cfg(feature = "cmyk")]
+            Colour::CMYK(c,m,y,k) => { write_extended(f, "38", "4")?; write_cmyk(f, c, m, y, k) },
         }
     }
 
-    fn write_background_code<W: AnyWrite + ?Sized>(&self, f: &mut W) -> Result<(), W::Error> {
+    fn write_background_code(&self, f: &mut dyn fmt::Write) -> fmt::Result {
         match *self {
-            Colour::Black      => write!(f, "40"),
-            Colour::Red        => write!(f, "41"),
-            Colour::Green      => write!(f, "42"),
-            Colour::Yellow     => write!(f, "43"),
-            Colour::Blue       => write!(f, "44"),
-            Colour::Purple     => write!(f, "45"),
-            Colour::Cyan       => write!(f, "46"),
-            Colour::White      => write!(f, "47"),
-            Colour::Fixed(num) => write!(f, "48;5;{}", &num),
-            Colour::RGB(r,g,b) => write!(f, "48;2;{};{};{}", &r, &g, &b),
+            Colour::Black      => f.write_str("40"),
+            Colour::Red        => f.write_str("41"),
+            Colour::Green      => f.write_str("42"),
+            Colour::Yellow     => f.write_str("43"),
+            Colour::Blue       => f.write_str("44"),
+            Colour::Purple     => f.write_str("45"),
+            Colour::Cyan       => f.write_str("46"),
+            Colour::White      => f.write_str("47"),
+            #[cfg(feature = "fixed")]
+            Colour::Fixed(num) => { write_extended(f, "48", "5")?; write_number(f, num) },
+            #[cfg(feature = "rgb")]
+            Colour::RGB(r,g,b) => { write_extended(f, "48", "2")?; write_rgb(f, r, g, b) },
+            #[cfg(feature = "cmyk")]
+            Colour::CMY(c,m,y) => { write_extended(f, "48", "3")?; write_rgb(f, c, m, y) },
+            #[cfg(feature = "cmyk")]
+            Colour::CMYK(c,m,y,k) => { write_extended(f, "48", "4")?; write_cmyk(f, c, m, y, k) },
         }
     }
 }
 
+/// Writes the start of the code for a palette or RGB colour, such as the
+/// `38;2;` in `38;2;r;g;b`, with the separators from the colour syntax. In
+/// the colon form, the colours other than palette ones have an empty colour
+/// space before them.
+#[cfg(any(feature = "fixed", feature = "rgb"))]
+fn write_extended(f: &mut dyn fmt::Write, code: &str, kind: &str) -> fmt::Result {
+    let separator = separator();
+    f.write_str(code)?;
+    f.write_str(separator)?;
+    f.write_str(kind)?;
+    f.write_str(separator)?;
+    if kind != "5" && separator == ":" { f.write_str(":")?; }
+    Ok(())
+}
+
+/// The separator between the numbers of a palette or RGB colour.
+#[cfg(any(feature = "fixed", feature = "rgb"))]
+fn separator() -> &'static str {
+    match colour_syntax() {
+        ColourSyntax::Semicolon  => ";",
+        ColourSyntax::Colon      => ":",
+    }
+}
+
+#[cfg(feature = "cmyk")]
+fn write_cmyk(f: &mut dyn fmt::Write, c: u8, m: u8, y: u8, k: u8) -> fmt::Result {
+    write_rgb(f, c, m, y)?;
+    f.write_str(separator())?;
+    write_number(f, k)
+}
+
+#[cfg(feature = "rgb")]
+fn write_rgb(f: &mut dyn fmt::Write, r: u8, g: u8, b: u8) -> fmt::Result {
+    let separator = separator();
+    write_number(f, r)?;
+    f.write_str(separator)?;
+    write_number(f, g)?;
+    f.write_str(separator)?;
+    write_number(f, b)
+}
+
+/// Writes a number in decimal, without the overhead of `Display`.
+#[cfg(any(feature = "fixed", feature = "rgb"))]
+fn write_number(f: &mut dyn fmt::Write, mut num: u8) -> fmt::Result {
+    let mut digits = [0_u8; 3];
+    let mut start = digits.len();
+
+    loop {
+        start -= 1;
+        digits[start] = b'0' + num % 10;
+        num /= 10;
+        if num == 0 { break }
+    }
+
+    f.write_str(str::from_utf8(&digits[start ..]).expect("digits are ASCII"))
+}
+
 
 /// Like `ANSIString`, but only displays the style prefix.
 ///
@@ -164,7 +234,7 @@ impl Style {
     ///            style.prefix().to_string());
     /// ```
     pub fn prefix(self) -> Prefix {
-        Prefix(self)
+        Prefix(filter_style(self))
     }
 
     /// The infix bytes between this style and `next` style. These are the bytes
@@ -189,7 +259,7 @@ impl Style {
     ///            style.infix(style).to_string());
     /// ```
     pub fn infix(self, next: Style) -> Infix {
-        Infix(self, next)
+        Infix(filter_style(self), filter_style(next))
     }
 
     /// The suffix for this style. These are the bytes that tell the terminal
@@ -213,7 +283,7 @@ impl Style {
     ///            style.suffix().to_string());
     /// ```
     pub fn suffix(self) -> Suffix {
-        Suffix(self)
+        Suffix(filter_style(self))
     }
 }
 
@@ -234,7 +304,7 @@ impl Colour {
     ///            Green.suffix().to_string());
     /// ```
     pub fn prefix(self) -> Prefix {
-        Prefix(self.normal())
+        self.normal().prefix()
     }
 
     /// The infix bytes between this colour and `next` colour. These are the bytes
@@ -252,7 +322,7 @@ impl Colour {
     ///            Red.infix(Yellow).to_string());
     /// ```
     pub fn infix(self, next: Colour) -> Infix {
-        Infix(self.normal(), next.normal())
+        self.normal().infix(next.normal())
     }
 
     /// The suffix for this colour as a `Style`. These are the bytes that
@@ -269,15 +339,14 @@ impl Colour {
     ///            Purple.suffix().to_string());
     /// ```
     pub fn suffix(self) -> Suffix {
-        Suffix(self.normal())
+        self.normal().suffix()
     }
 }
 
 
 impl fmt::Display for Prefix {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let f: &mut fmt::Write = f;
-        self.0.write_prefix(f)
+        write_code(f, self.0, |f| self.0.write_prefix(f))
     }
 }
 
@@ -288,12 +357,13 @@ impl fmt::Display for Infix {
 
         match Difference::between(&self.0, &self.1) {
             Difference::ExtraStyles(style) => {
-                let f: &mut fmt::Write = f;
-                style.write_prefix(f)
+                write_code(f, self.1, |f| style.write_prefix(f))
             },
             Difference::Reset => {
-                let f: &mut fmt::Write = f;
-                write!(f, "{}{}", RESET, self.1.prefix())
+                write_code(f, self.1, |f| {
+                    f.write_str(RESET)?;
+                    self.1.write_prefix(f)
+                })
             },
             Difference::NoDifference => {
                 Ok(())   // nothing to write
@@ -305,11 +375,48 @@ impl fmt::Display for Infix {
 
 impl fmt::Display for Suffix {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let f: &mut fmt::Write = f;
-        self.0.write_suffix(f)
+        write_code(f, self.0, |f| self.0.write_suffix(f))
+    }
+}
+
+
+impl Prefix {
+
+    /// Renders the prefix into a `CodeBuffer`, which doesn’t allocate, so
+    /// its bytes can be copied into another buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::Colour::Red;
+    ///
+    /// let mut line = Vec::new();
+    /// line.extend_from_slice(Red.bold().prefix().render().as_bytes());
+    /// line.extend_from_slice(b"error");
+    /// line.extend_from_slice(Red.bold().suffix().render().as_bytes());
+    ///
+    /// assert_eq!(line, b"\x1B[1;31merror\x1B[0m");
+    /// ```
+    pub fn render(self) -> CodeBuffer {
+        CodeBuffer::render(self)
     }
 }
 
+impl Infix {
+
+    /// Renders the infix into a `CodeBuffer`, which doesn’t allocate.
+    pub fn render(self) -> CodeBuffer {
+        CodeBuffer::render(self)
+    }
+}
+
+impl Suffix {
+
+    /// Renders the suffix into a `CodeBuffer`, which doesn’t allocate.
+    pub fn render(self) -> CodeBuffer {
+        CodeBuffer::render(self)
+    }
+}
 
 
 #[cfg(test)]
@@ -346,13 +453,24 @@ mod test {
     test!(cyan_ul_on_white:      Cyan.underline().on(White);        "hi" => "\x1B[4;47;36mhi\x1B[0m");
     test!(cyan_bold_ul_on_white: Cyan.bold().underline().on(White); "hi" => "\x1B[1;4;47;36mhi\x1B[0m");
     test!(cyan_ul_bold_on_white: Cyan.underline().bold().on(White); "hi" => "\x1B[1;4;47;36mhi\x1B[0m");
+    #[cfg(feature = "fixed")]
     test!(fixed:                 Fixed(100);                        "hi" => "\x1B[38;5;100mhi\x1B[0m");
+    #[cfg(feature = "fixed")]
     test!(fixed_on_purple:       Fixed(100).on(Purple);             "hi" => "\x1B[45;38;5;100mhi\x1B[0m");
+    #[cfg(feature = "fixed")]
     test!(fixed_on_fixed:        Fixed(100).on(Fixed(200));         "hi" => "\x1B[48;5;200;38;5;100mhi\x1B[0m");
+    #[cfg(feature = "rgb")]
     test!(rgb:                   RGB(70,130,180);                   "hi" => "\x1B[38;2;70;130;180mhi\x1B[0m");
+    #[cfg(feature = "rgb")]
     test!(rgb_on_blue:           RGB(70,130,180).on(Blue);          "hi" => "\x1B[44;38;2;70;130;180mhi\x1B[0m");
+    #[cfg(feature = "rgb")]
     test!(blue_on_rgb:           Blue.on(RGB(70,130,180));          "hi" => "\x1B[48;2;70;130;180;34mhi\x1B[0m");
+    #[cfg(feature = "rgb")]
     test!(rgb_on_rgb:            RGB(70,130,180).on(RGB(5,10,15));  "hi" => "\x1B[48;2;5;10;15;38;2;70;130;180mhi\x1B[0m");
+    #[cfg(feature = "cmyk")]
+    test!(cmy:                   CMY(1,2,3);                        "hi" => "\x1B[38;3;1;2;3mhi\x1B[0m");
+    #[cfg(feature = "cmyk")]
+    test!(cmyk_on_blue:          CMYK(1,2,3,4).on(Blue);            "hi" => "\x1B[44;38;4;1;2;3;4mhi\x1B[0m");
     test!(bold:                  Style::new().bold();               "hi" => "\x1B[1mhi\x1B[0m");
     test!(underline:             Style::new().underline();          "hi" => "\x1B[4mhi\x1B[0m");
     test!(bunderline:            Style::new().bold().underline();   "hi" => "\x1B[1;4mhi\x1B[0m");
@@ -371,4 +489,13 @@ mod test {
         assert_eq!(White.normal().infix(Blue.normal()).to_string(), "\x1B[34m");
         assert_eq!(Blue.bold().infix(Blue.bold()).to_string(), "");
     }
+
+    #[test]
+    #[cfg(feature = "rgb")]
+    fn longest_code_renders() {
+        let everything = Style::new().bold().dimmed().italic().underline().blink().reverse().hidden().strikethrough();
+        let style = everything.fg(RGB(255, 255, 255)).on(RGB(255, 255, 255));
+        assert_eq!(&*style.infix(Style::new().fg(RGB(200, 200, 200))).render(), "\x1B[0m\x1B[38;2;200;200;200m");
+        assert_eq!(style.prefix().render().as_str(), style.prefix().to_string());
+    }
 }
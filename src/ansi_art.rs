@@ -0,0 +1,243 @@
+use sgr;
+use style::Style;
+use styled_text::StyledText;
+
+
+/// The characters for the bytes of code page 437 from `0x80` to `0xFF`.
+static HIGH: &str = "ÇüéâäàåçêëèïîìÄÅÉæÆôöòûùÿÖÜ¢£¥₧ƒáíóúñÑªº¿⌐¬½¼¡«»\
+                     ░▒▓│┤╡╢╖╕╣║╗╝╜╛┐└┴┬├─┼╞╟╚╔╩╦╠═╬╧╨╤╥╙╘╒╓╫╪┘┌█▄▌▐▀\
+                     αßΓπΣσµτΦΘΩδ∞φε∩≡±≥≤⌠⌡÷≈°∙·√ⁿ²■\u{A0}";
+
+/// The glyphs that code page 437 shows for the control characters from
+/// `0x00` to `0x1F`, which ANSI art uses as pictures.
+static LOW: &str = " ☺☻♥♦♣♠•◘○◙♂♀♪♫☼►◄↕‼¶§▬↨↑↓→←∟↔▲▼";
+
+/// The byte that ends the art, after which there may be a SAUCE record
+/// with its title and author.
+const END_OF_FILE: u8 = 0x1A;
+
+/// The furthest down the cursor movement codes can move the cursor, so a
+/// corrupt or hostile file can’t make the canvas use up all the memory.
+const MAX_ROW: usize = 9_999;
+
+
+/// Loads a piece of classic ANSI art, such as a `.ans` file or a BBS’s
+/// message of the day, returning the picture it draws as lines of
+/// `StyledText`.
+///
+/// The bytes are decoded as code page 437, the character set of the PCs the
+/// art was drawn on, with its block and line-drawing characters. The art’s
+/// escape codes get interpreted the way `ANSI.SYS` did: the colour codes
+/// set the style of the characters that follow, and the cursor movement
+/// codes move around a canvas of the given width, which is 80 columns for
+/// almost every piece of art. Text that reaches the edge of the canvas wraps
+/// onto the next line.
+///
+/// Bold text in ANSI art is usually drawn in the bright version of its
+/// colour, so it gets kept as bold, which terminals show the same way.
+///
+/// This function is only available with the `ansi-art` feature.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::load_ansi_art;
+/// use ansi_term::Colour::{Blue, Red};
+///
+/// let art = load_ansi_art(b"\x1B[1;34m\xDB\xDB\x1B[0m\r\n\x1B[31m\xB0\xB1\xB2", 80);
+///
+/// assert_eq!(art.len(), 2);
+/// assert_eq!(art[0].to_string(), Blue.bold().paint("██").to_string());
+/// assert_eq!(art[1].to_string(), Red.paint("░▒▓").to_string());
+/// ```
+pub fn load_ansi_art(bytes: &[u8], width: usize) -> Vec<StyledText> {
+    let mut canvas = Canvas::new(width);
+    let mut index = 0;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            END_OF_FILE  => break,
+            b'\r'        => canvas.col = 0,
+            b'\n'        => { canvas.row += 1; canvas.col = 0; },
+            0x1B if bytes.get(index + 1) == Some(&b'[') => {
+                let start = index + 2;
+                let end = bytes[start ..].iter()
+                                         .position(|b| (0x40 ..= 0x7E).contains(b))
+                                         .map_or(bytes.len(), |p| start + p);
+
+                if end < bytes.len() {
+                    canvas.control(&bytes[start .. end], bytes[end]);
+                }
+
+                index = end + 1;
+                continue;
+            },
+            byte  => canvas.put(cp437(byte)),
+        }
+
+        index += 1;
+    }
+
+    canvas.into_lines()
+}
+
+/// Returns the character for a byte in code page 437.
+fn cp437(byte: u8) -> char {
+    match byte {
+        0x00 ..= 0x1F  => LOW.chars().nth(byte as usize).unwrap(),
+        0x7F           => '⌂',
+        0x80 ..= 0xFF  => HIGH.chars().nth(byte as usize - 0x80).unwrap(),
+        _              => byte as char,
+    }
+}
+
+
+/// The grid of characters that the art draws on.
+struct Canvas {
+    cells: Vec<Vec<(Style, char)>>,
+    width: usize,
+    row: usize,
+    col: usize,
+    saved: (usize, usize),
+    style: Style,
+}
+
+impl Canvas {
+    fn new(width: usize) -> Canvas {
+        Canvas { cells: Vec::new(), width, row: 0, col: 0, saved: (0, 0), style: Style::default() }
+    }
+
+    fn put(&mut self, c: char) {
+        if self.col >= self.width {
+            self.row += 1;
+            self.col = 0;
+        }
+
+        if self.cells.len() <= self.row {
+            self.cells.resize(self.row + 1, Vec::new());
+        }
+
+        let line = &mut self.cells[self.row];
+        if line.len() <= self.col {
+            line.resize(self.col + 1, (Style::default(), ' '));
+        }
+
+        line[self.col] = (self.style, c);
+        self.col += 1;
+    }
+
+    /// Runs a control sequence with the given parameters and final byte.
+    fn control(&mut self, params: &[u8], code: u8) {
+        let params: Vec<Option<usize>> = params.split(|&b| b == b';')
+            .map(|p| std::str::from_utf8(p).ok().and_then(|p| p.parse().ok()))
+            .collect();
+
+        // Movements treat a missing or zero count as one.
+        let count = |n: usize| params.get(n).copied().flatten().unwrap_or(1).max(1);
+
+        match code {
+            b'A'         => self.row = self.row.saturating_sub(count(0)),
+            b'B'         => self.row = self.row.saturating_add(count(0)).min(MAX_ROW),
+            b'C'         => self.col = self.col.saturating_add(count(0)).min(self.width),
+            b'D'         => self.col = self.col.saturating_sub(count(0)),
+            b'H' | b'f'  => { self.row = (count(0) - 1).min(MAX_ROW); self.col = (count(1) - 1).min(self.width) },
+            b's'         => self.saved = (self.row, self.col),
+            b'u'         => { self.row = self.saved.0; self.col = self.saved.1 },
+            b'J' if params.first() == Some(&Some(2)) => {
+                self.cells.clear();
+                self.row = 0;
+                self.col = 0;
+            },
+            b'K'         => if let Some(line) = self.cells.get_mut(self.row) {
+                line.truncate(self.col);
+            },
+            b'm'         => self.select_graphic_rendition(&params),
+            _            => {},
+        }
+    }
+
+    fn select_graphic_rendition(&mut self, params: &[Option<usize>]) {
+        let mut params = params.iter().map(|p| p.unwrap_or(0));
+
+        while let Some(param) = params.next() {
+            sgr::apply(&mut self.style, param, &mut params);
+        }
+    }
+
+    /// Returns the lines of the canvas, without the unstyled spaces at the
+    /// ends of them.
+    fn into_lines(self) -> Vec<StyledText> {
+        self.cells.into_iter().map(|mut line| {
+            while line.last() == Some(&(Style::default(), ' ')) {
+                line.pop();
+            }
+
+            let mut text = StyledText::new();
+            let mut run = String::new();
+            let mut run_style = Style::default();
+
+            for (style, c) in line {
+                if style != run_style && !run.is_empty() {
+                    text.push(run_style, &run);
+                    run.clear();
+                }
+                run_style = style;
+                run.push(c);
+            }
+
+            if !run.is_empty() {
+                text.push(run_style, &run);
+            }
+
+            text
+        }).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use style::Colour::*;
+
+    #[test]
+    fn code_page_tables() {
+        assert_eq!(HIGH.chars().count(), 128);
+        assert_eq!(LOW.chars().count(), 32);
+        assert_eq!(cp437(b'A'), 'A');
+        assert_eq!(cp437(0xC9), '╔');
+    }
+
+    #[test]
+    fn wraps_at_the_width() {
+        let art = load_ansi_art(b"abcde", 3);
+        assert_eq!(art.iter().map(|l| l.to_plain_string()).collect::<Vec<_>>(), vec![ "abc", "de" ]);
+    }
+
+    #[test]
+    fn cursor_movement() {
+        let art = load_ansi_art(b"\x1B[2;3Hx\x1B[1;1Hy\x1B[2Cz", 80);
+        assert_eq!(art[0].to_plain_string(), "y  z");
+        assert_eq!(art[1].to_plain_string(), "  x");
+    }
+
+    #[test]
+    fn huge_movements() {
+        let art = load_ansi_art(b"\x1B[18446744073709551615B\x1B[18446744073709551615Cx", 80);
+        assert_eq!(art.len(), MAX_ROW + 2);
+        assert_eq!(art[MAX_ROW + 1].to_plain_string(), "x");
+    }
+
+    #[test]
+    fn huge_position() {
+        let art = load_ansi_art(b"\x1B[400000000;2Hx", 80);
+        assert_eq!(art.len(), MAX_ROW + 1);
+        assert_eq!(art[MAX_ROW].to_plain_string(), " x");
+    }
+
+    #[test]
+    fn stops_at_sauce() {
+        let art = load_ansi_art(b"\x1B[44m \x1B[0m\x1ASAUCE00", 80);
+        assert_eq!(art.len(), 1);
+        assert_eq!(art[0].to_string(), Style::default().on(Blue).paint(" ").to_string());
+    }
+}
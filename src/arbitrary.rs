@@ -0,0 +1,134 @@
+//! `Arbitrary` implementations for property testing with `quickcheck`,
+//! available with the `quickcheck` feature.
+
+use quickcheck::{Arbitrary, Gen};
+
+use display::ANSIString;
+use style::{Colour, Style};
+use styled_text::StyledText;
+
+
+impl Arbitrary for Colour {
+
+    /// Picks the named colours about as often as the other two kinds put
+    /// together, as they’re the ones most code uses.
+    fn arbitrary(g: &mut Gen) -> Colour {
+        use style::Colour::*;
+
+        match *g.choose(&[0, 1, 2, 3]).unwrap() {
+            #[cfg(feature = "fixed")]
+            0  => Fixed(u8::arbitrary(g)),
+            #[cfg(feature = "rgb")]
+            1  => RGB(u8::arbitrary(g), u8::arbitrary(g), u8::arbitrary(g)),
+            _  => *g.choose(&[ Black, Red, Green, Yellow, Blue, Purple, Cyan, White ]).unwrap(),
+        }
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item=Colour>> {
+        match *self {
+            Colour::Black  => quickcheck::empty_shrinker(),
+            _              => quickcheck::single_shrinker(Colour::Black),
+        }
+    }
+}
+
+impl Arbitrary for Style {
+    fn arbitrary(g: &mut Gen) -> Style {
+        Style {
+            foreground:        Option::arbitrary(g),
+            background:        Option::arbitrary(g),
+            is_bold:           bool::arbitrary(g),
+            is_dimmed:         bool::arbitrary(g),
+            is_italic:         bool::arbitrary(g),
+            is_underline:      bool::arbitrary(g),
+            is_blink:          bool::arbitrary(g),
+            is_reverse:        bool::arbitrary(g),
+            is_hidden:         bool::arbitrary(g),
+            is_strikethrough:  bool::arbitrary(g),
+        }
+    }
+
+    /// Shrinks towards the plain style, by taking away one colour or
+    /// property at a time.
+    fn shrink(&self) -> Box<dyn Iterator<Item=Style>> {
+        let s = *self;
+        let mut smaller = Vec::new();
+
+        if s.foreground.is_some()  { smaller.push(Style { foreground: None, .. s }) }
+        if s.background.is_some()  { smaller.push(Style { background: None, .. s }) }
+        if s.is_bold               { smaller.push(Style { is_bold: false, .. s }) }
+        if s.is_dimmed             { smaller.push(Style { is_dimmed: false, .. s }) }
+        if s.is_italic             { smaller.push(Style { is_italic: false, .. s }) }
+        if s.is_underline          { smaller.push(Style { is_underline: false, .. s }) }
+        if s.is_blink              { smaller.push(Style { is_blink: false, .. s }) }
+        if s.is_reverse            { smaller.push(Style { is_reverse: false, .. s }) }
+        if s.is_hidden             { smaller.push(Style { is_hidden: false, .. s }) }
+        if s.is_strikethrough      { smaller.push(Style { is_strikethrough: false, .. s }) }
+
+        Box::new(smaller.into_iter())
+    }
+}
+
+impl Arbitrary for ANSIString<'static> {
+    fn arbitrary(g: &mut Gen) -> ANSIString<'static> {
+        Style::arbitrary(g).paint(String::arbitrary(g))
+    }
+
+    /// Shrinks the text first, then the style.
+    fn shrink(&self) -> Box<dyn Iterator<Item=ANSIString<'static>>> {
+        let style = *self.style_ref();
+        let text = String::from(&**self);
+
+        let texts = text.shrink().map(move |t| style.paint(t));
+        let styles = style.shrink().map(move |s| s.paint(text.clone()));
+        Box::new(texts.chain(styles))
+    }
+}
+
+impl Arbitrary for StyledText {
+    fn arbitrary(g: &mut Gen) -> StyledText {
+        let spans: Vec<(Style, String)> = Arbitrary::arbitrary(g);
+        collect_spans(spans)
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item=StyledText>> {
+        let spans: Vec<(Style, String)> = self.fragments().iter()
+            .map(|f| (*f.style_ref(), String::from(&**f)))
+            .collect();
+
+        Box::new(spans.shrink().map(collect_spans))
+    }
+}
+
+fn collect_spans(spans: Vec<(Style, String)>) -> StyledText {
+    let mut text = StyledText::new();
+    for (style, string) in spans {
+        text.push(style, &string);
+    }
+    text
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use display::ANSIStrings;
+    use util::{sub_string, unstyle};
+
+    quickcheck::quickcheck! {
+        fn no_codes_between_equal_styles(style: Style) -> bool {
+            style.infix(style).to_string().is_empty()
+        }
+
+        fn unstyle_keeps_all_the_text(strings: Vec<ANSIString<'static>>) -> bool {
+            let text: String = strings.iter().map(|s| &**s).collect();
+            unstyle(&ANSIStrings(&strings)) == text
+        }
+
+        fn sub_string_of_everything(strings: Vec<ANSIString<'static>>) -> bool {
+            let strs = ANSIStrings(&strings);
+            let all = sub_string(0, usize::MAX, &strs);
+            unstyle(&ANSIStrings(&all)) == unstyle(&strs)
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use std::io::{self, Write};
+
+use display::{ANSIString, ANSIStrings};
+use style::Style;
+
+
+/// A way of turning styled text into output, so the same strings can be
+/// rendered for different targets.
+///
+/// The crate comes with backends that write ANSI escape codes ([`Sgr`], the
+/// one used when strings get displayed), HTML ([`Html`]), and, with the
+/// `terminfo` feature, the codes from a terminal’s terminfo entry. On
+/// Windows, there’s also one that sets the colours through the console API.
+/// Other crates can implement this trait to add their own.
+///
+/// A backend gets told whenever the style changes, including from and to
+/// the plain style at the start and end, and given each piece of text in
+/// between.
+///
+/// [`Sgr`]: struct.Sgr.html
+/// [`Html`]: struct.Html.html
+///
+/// # Examples
+///
+/// ```
+/// use std::io::{self, Write};
+/// use ansi_term::{ANSIStrings, Backend, Style};
+/// use ansi_term::Colour::Red;
+///
+/// /// Writes bold text in asterisks, and everything else as plain text.
+/// struct Markdown;
+///
+/// impl Backend for Markdown {
+///     fn transition(&mut self, w: &mut dyn Write, from: Style, to: Style) -> io::Result<()> {
+///         if from.is_bold != to.is_bold { w.write_all(b"**")?; }
+///         Ok(())
+///     }
+/// }
+///
+/// let strings = [ Red.bold().paint("Warning"), Style::new().paint(": low disk") ];
+/// assert_eq!(ANSIStrings(&strings).render_with(&mut Markdown), b"**Warning**: low disk");
+/// ```
+pub trait Backend {
+
+    /// Writes whatever is needed to change the style of the text that
+    /// follows from one style to another.
+    fn transition(&mut self, w: &mut dyn Write, from: Style, to: Style) -> io::Result<()>;
+
+    /// Writes a piece of text. By default, the text gets written as it is.
+    fn text(&mut self, w: &mut dyn Write, text: &str) -> io::Result<()> {
+        w.write_all(text.as_bytes())
+    }
+}
+
+/// The backend that writes ANSI SGR escape codes, with the minimum of codes
+/// between strings, as displaying `ANSIStrings` does.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Debug)]
+pub struct Sgr;
+
+impl Backend for Sgr {
+    fn transition(&mut self, w: &mut dyn Write, from: Style, to: Style) -> io::Result<()> {
+        write!(w, "{}", from.infix(to))
+    }
+}
+
+/// The backend that writes HTML, with each run of styled text in a `span`
+/// element with an inline style, and the text escaped.
+///
+/// The colours are the ones from [`Style::to_inline_css`].
+///
+/// [`Style::to_inline_css`]: struct.Style.html#method.to_inline_css
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{ANSIStrings, Html};
+/// use ansi_term::Colour::Red;
+///
+/// let strings = [ Red.bold().paint("<error>"), "!".into() ];
+/// assert_eq!(ANSIStrings(&strings).render_with(&mut Html),
+///            "<span style=\"color:#cd0000;font-weight:bold;\">&lt;error&gt;</span>!".as_bytes());
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Default, Debug)]
+pub struct Html;
+
+impl Backend for Html {
+    fn transition(&mut self, w: &mut dyn Write, from: Style, to: Style) -> io::Result<()> {
+        if from == to {
+            return Ok(());
+        }
+
+        if !from.is_plain() {
+            w.write_all(b"</span>")?;
+        }
+
+        if !to.is_plain() {
+            write!(w, "<span style=\"{}\">", to.to_inline_css())?;
+        }
+
+        Ok(())
+    }
+
+    fn text(&mut self, w: &mut dyn Write, text: &str) -> io::Result<()> {
+        let mut start = 0;
+
+        for (index, c) in text.char_indices() {
+            let entity = match c {
+                '&'   => "&amp;",
+                '<'   => "&lt;",
+                '>'   => "&gt;",
+                '"'   => "&quot;",
+                '\''  => "&#39;",
+                _     => continue,
+            };
+
+            w.write_all(&text.as_bytes()[start .. index])?;
+            w.write_all(entity.as_bytes())?;
+            start = index + 1;
+        }
+
+        w.write_all(&text.as_bytes()[start ..])
+    }
+}
+
+impl<'a> ANSIStrings<'a> {
+
+    /// Writes these strings to a writer with the given backend, starting and
+    /// ending with the plain style.
+    pub fn write_with<B: Backend + ?Sized, W: Write>(&self, backend: &mut B, mut w: W) -> io::Result<()> {
+        let mut previous = Style::default();
+
+        for string in self.0.iter() {
+            let style = *string.style_ref();
+            backend.transition(&mut w, previous, style)?;
+            backend.text(&mut w, string)?;
+            previous = style;
+        }
+
+        backend.transition(&mut w, previous, Style::default())
+    }
+
+    /// Renders these strings with the given backend, returning the bytes it
+    /// writes.
+    pub fn render_with<B: Backend + ?Sized>(&self, backend: &mut B) -> Vec<u8> {
+        let mut output = Vec::new();
+        self.write_with(backend, &mut output).expect("writing to a Vec");
+        output
+    }
+}
+
+impl<'a> ANSIString<'a> {
+
+    /// Writes this string to a writer with the given backend.
+    pub fn write_with<B: Backend + ?Sized, W: Write>(&self, backend: &mut B, w: W) -> io::Result<()> {
+        ANSIStrings(std::slice::from_ref(self)).write_with(backend, w)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use style::Colour::*;
+
+    #[test]
+    fn sgr_matches_display() {
+        let strings = [ Red.paint("a"), Red.bold().paint("b"), Blue.paint("c"), "d".into() ];
+        let strings = ANSIStrings(&strings);
+        assert_eq!(strings.render_with(&mut Sgr), strings.to_string().into_bytes());
+    }
+
+    #[test]
+    fn html_escapes_text() {
+        let strings = [ "a & 'b' \"c\"".into() ];
+        assert_eq!(ANSIStrings(&strings).render_with(&mut Html), b"a &amp; &#39;b&#39; &quot;c&quot;");
+    }
+
+    #[test]
+    fn html_closes_spans() {
+        let strings = [ Red.paint("a"), Red.paint("b"), Blue.underline().paint("c") ];
+        assert_eq!(String::from_utf8(ANSIStrings(&strings).render_with(&mut Html)).unwrap(),
+                   "<span style=\"color:#cd0000;\">ab</span><span style=\"color:#0000ee;text-decoration:underline;\">c</span>");
+    }
+}
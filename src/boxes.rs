@@ -0,0 +1,108 @@
+use std::fmt;
+
+use display::{ANSIString, ANSIStrings};
+use style::Style;
+use table::Table;
+
+
+/// Returns a horizontal rule of the given width, drawn with the `─`
+/// line-drawing character in the given style.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::horizontal_rule;
+/// use ansi_term::Colour::Black;
+///
+/// assert_eq!(horizontal_rule(4, Black.bold()).to_string(),
+///            "\x1B[1;30m────\x1B[0m");
+/// ```
+pub fn horizontal_rule(width: usize, style: Style) -> ANSIString<'static> {
+    style.paint("─".repeat(width))
+}
+
+
+/// A block of styled text drawn inside a box, as returned by [`boxed`].
+///
+/// [`boxed`]: fn.boxed.html
+#[derive(PartialEq, Clone, Debug)]
+pub struct Boxed {
+    lines: Vec<Vec<ANSIString<'static>>>,
+    border: Style,
+}
+
+/// Draws a box around a block of styled text, for framed warnings and
+/// panels.
+///
+/// The block is split into lines at each newline, keeping the styles of the
+/// text, and the box is made as wide as the widest line’s *visible* width.
+/// The value returned implements `Display`, writing the box one line at a
+/// time, each of which ends with a newline.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{boxed, ANSIStrings, Style};
+/// use ansi_term::Colour::Yellow;
+///
+/// let warning = [ Yellow.paint("warning"), Style::default().paint(":\ndisk full") ];
+/// let panel = boxed(&ANSIStrings(&warning), Style::default());
+///
+/// assert_eq!(panel.to_string(), "┌───────────┐\n\
+///                                │ \x1B[33mwarning\x1B[0m:  │\n\
+///                                │ disk full │\n\
+///                                └───────────┘\n");
+/// ```
+pub fn boxed(block: &ANSIStrings, border: Style) -> Boxed {
+    let mut lines = vec![ Vec::new() ];
+
+    for fragment in block.0.iter() {
+        let style = *fragment.style_ref();
+
+        for (index, piece) in fragment.split('\n').enumerate() {
+            if index > 0 {
+                lines.push(Vec::new());
+            }
+
+            if !piece.is_empty() {
+                lines.last_mut().unwrap().push(style.paint(String::from(piece)));
+            }
+        }
+    }
+
+    Boxed { lines, border }
+}
+
+impl fmt::Display for Boxed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut table = Table::new().border(self.border);
+        for line in &self.lines {
+            table.add_row(vec![ ANSIStrings(line) ]);
+        }
+
+        write!(f, "{}", table)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use style::Colour::*;
+
+    #[test]
+    fn styled_border() {
+        let text = [ Style::default().paint("hi") ];
+        assert_eq!(boxed(&ANSIStrings(&text), Red.normal()).to_string(),
+                   "\x1B[31m┌────┐\x1B[0m\n\
+                    \x1B[31m│\x1B[0m hi \x1B[31m│\x1B[0m\n\
+                    \x1B[31m└────┘\x1B[0m\n");
+    }
+
+    #[test]
+    fn style_carries_across_lines() {
+        let text = [ Blue.paint("a\nbc") ];
+        assert_eq!(boxed(&ANSIStrings(&text), Style::default()).lines,
+                   vec![ vec![ Blue.paint("a") ], vec![ Blue.paint("bc") ] ]);
+    }
+}
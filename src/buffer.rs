@@ -0,0 +1,148 @@
+use std::fmt;
+use std::io;
+
+use stream::Stream;
+
+
+/// A buffer for assembling styled output in memory, so it can be written out
+/// all at once by a [`BufferWriter`].
+///
+/// A `Buffer` implements both `io::Write` and `fmt::Write`, so styled strings
+/// can be written to it with `write!`, and byte strings with their
+/// `write_to` methods. Buffers can be filled on any thread, then sent back to
+/// the thread that prints them.
+///
+/// [`BufferWriter`]: struct.BufferWriter.html
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+/// use ansi_term::{Buffer, Colour::Green};
+///
+/// let mut buffer = Buffer::new();
+/// write!(buffer, "{} tests passed", Green.paint("12")).unwrap();
+/// assert_eq!(buffer.as_slice(), b"\x1B[32m12\x1B[0m tests passed");
+/// ```
+#[derive(PartialEq, Eq, Clone, Default, Debug)]
+pub struct Buffer(Vec<u8>);
+
+impl Buffer {
+
+    /// Creates a new, empty buffer.
+    pub fn new() -> Buffer {
+        Buffer(Vec::new())
+    }
+
+    /// Returns the bytes written to this buffer so far.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns the number of bytes written to this buffer so far.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether nothing has been written to this buffer yet.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Empties the buffer, so it can be used again.
+    pub fn clear(&mut self) {
+        self.0.clear()
+    }
+
+    /// Returns the bytes written to this buffer.
+    pub fn into_inner(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl io::Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl fmt::Write for Buffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+
+/// Writes [`Buffer`]s to standard output or standard error in one go.
+///
+/// The stream stays locked while a buffer is being written, so when several
+/// threads print at the same time, each buffer comes out whole instead of
+/// having its colour codes interleaved with the others.
+///
+/// [`Buffer`]: struct.Buffer.html
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+/// use ansi_term::{BufferWriter, Colour::Red};
+///
+/// let writer = BufferWriter::stderr();
+/// let mut buffer = writer.buffer();
+/// writeln!(buffer, "{}: disk full", Red.bold().paint("error")).unwrap();
+/// writer.print(&buffer).unwrap();
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct BufferWriter {
+    stream: Stream,
+}
+
+impl BufferWriter {
+
+    /// Creates a writer that prints buffers to standard output.
+    pub fn stdout() -> BufferWriter {
+        BufferWriter { stream: Stream::Stdout }
+    }
+
+    /// Creates a writer that prints buffers to standard error.
+    pub fn stderr() -> BufferWriter {
+        BufferWriter { stream: Stream::Stderr }
+    }
+
+    /// Creates a new, empty buffer to be printed by this writer.
+    pub fn buffer(&self) -> Buffer {
+        Buffer::new()
+    }
+
+    /// Writes the contents of the buffer to the stream and flushes it,
+    /// holding the stream’s lock the whole time.
+    pub fn print(&self, buffer: &Buffer) -> io::Result<()> {
+        self.stream.locked(|w| {
+            w.write_all(buffer.as_slice())?;
+            w.flush()
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use display::ANSIByteStrings;
+    use style::Colour::*;
+
+    #[test]
+    fn bytes_and_strings() {
+        let mut buffer = Buffer::new();
+        ANSIByteStrings(&[ Red.paint(&b"a"[..]), Red.bold().paint(&b"b"[..]) ]).write_to(&mut buffer).unwrap();
+        fmt::Write::write_fmt(&mut buffer, format_args!("{}", Blue.paint("c"))).unwrap();
+
+        assert_eq!(buffer.into_inner(), b"\x1B[31ma\x1B[1mb\x1B[0m\x1B[34mc\x1B[0m");
+    }
+}
@@ -0,0 +1,105 @@
+use display::ANSIString;
+use style::{Colour, Style};
+use web::palette;
+
+
+/// Returns a chart of the 256-colour palette, for programs that want to
+/// show the user which colours their terminal has, such as with a
+/// `--show-palette` option.
+///
+/// Each colour is shown as its number on a background of that colour, with
+/// black or white text, whichever is easier to read. The chart has three
+/// parts, separated by blank lines: the sixteen basic colours, in two lines
+/// of eight; the 216 colours of the colour cube, as six squares of six by
+/// six; and the 24 shades of grey, in two lines of twelve. Every line ends
+/// with a newline, so the chart can be printed as it is.
+///
+/// This function is only available with the `fixed` feature.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{colour_chart, ANSIStrings};
+///
+/// let chart = colour_chart();
+/// print!("{}", ANSIStrings(&chart));
+///
+/// let plain = ANSIStrings(&chart).to_plain_string();
+/// assert!(plain.starts_with("   0     1 "));
+/// assert_eq!(plain.lines().count(), 19);
+/// ```
+pub fn colour_chart() -> Vec<ANSIString<'static>> {
+    let mut chart = Vec::new();
+
+    for row in 0 .. 2 {
+        line(&mut chart, (0 .. 8).map(|c| row * 8 + c), 8);
+    }
+    chart.push(Style::default().paint("\n"));
+
+    // The squares are two rows of three, each drawn one line at a time.
+    for squares in 0 .. 2 {
+        for row in 0 .. 6 {
+            let colours = (0 .. 3).flat_map(|square| {
+                let start = 16 + (squares * 3 + square) * 36 + row * 6;
+                start .. start + 6
+            });
+            line(&mut chart, colours, 6);
+        }
+        chart.push(Style::default().paint("\n"));
+    }
+
+    for row in 0 .. 2 {
+        line(&mut chart, (0 .. 12).map(|c| 232 + row * 12 + c), 12);
+    }
+
+    chart
+}
+
+/// Adds a line of colours to the chart, with an extra space after every
+/// group of the given size.
+fn line<I: Iterator<Item = u8>>(chart: &mut Vec<ANSIString<'static>>, colours: I, group: usize) {
+    for (index, colour) in colours.enumerate() {
+        if index > 0 {
+            let gap = if index % group == 0 { "   " } else { " " };
+            chart.push(Style::default().paint(gap));
+        }
+
+        chart.push(swatch(colour));
+    }
+
+    chart.push(Style::default().paint("\n"));
+}
+
+/// Returns a colour’s number on a background of that colour.
+fn swatch(colour: u8) -> ANSIString<'static> {
+    let (r, g, b) = palette(colour);
+    let brightness = (299 * u32::from(r) + 587 * u32::from(g) + 114 * u32::from(b)) / 1000;
+    let text = if brightness > 128 { Colour::Black } else { Colour::White };
+
+    text.on(Colour::Fixed(colour)).paint(format!(" {:3} ", colour))
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn every_colour_once() {
+        let mut seen = [false; 256];
+        for fragment in colour_chart() {
+            if let Some(Colour::Fixed(n)) = fragment.style_ref().background {
+                assert!(!seen[n as usize], "colour {} twice", n);
+                seen[n as usize] = true;
+            }
+        }
+
+        assert!(seen.iter().all(|&s| s));
+    }
+
+    #[test]
+    fn readable_text() {
+        assert_eq!(swatch(15).style_ref().foreground, Some(Colour::Black));
+        assert_eq!(swatch(16).style_ref().foreground, Some(Colour::White));
+    }
+}
@@ -0,0 +1,211 @@
+use std::fmt;
+use std::ops::Deref;
+use std::str;
+
+use ansi::RESET;
+use style::Style;
+
+
+/// The longest code this crate ever writes: a reset, followed by a prefix
+/// with every property set and two 24-bit colours.
+const CODE_CAPACITY: usize = 64;
+
+/// A small string, stored inline without allocating, that holds a rendered
+/// escape code, as returned by the `render` methods of [`Prefix`],
+/// [`Infix`], and [`Suffix`].
+///
+/// It dereferences to a `str`, and is big enough for any code this crate
+/// writes.
+///
+/// [`Prefix`]: struct.Prefix.html
+/// [`Infix`]: struct.Infix.html
+/// [`Suffix`]: struct.Suffix.html
+#[derive(Clone, Copy)]
+pub struct CodeBuffer {
+    bytes: [u8; CODE_CAPACITY],
+    len: usize,
+}
+
+impl CodeBuffer {
+    pub(crate) fn new() -> CodeBuffer {
+        CodeBuffer { bytes: [0; CODE_CAPACITY], len: 0 }
+    }
+
+    /// Renders the given value into a new buffer.
+    pub(crate) fn render<D: fmt::Display>(code: D) -> CodeBuffer {
+        let mut buf = CodeBuffer::new();
+        fmt::Write::write_fmt(&mut buf, format_args!("{}", code)).expect("escape code too long");
+        buf
+    }
+
+    /// Returns the code as a string.
+    pub fn as_str(&self) -> &str {
+        // Only ever written to through `write_str`, so this is valid UTF-8.
+        str::from_utf8(self.as_bytes()).expect("escape code is valid UTF-8")
+    }
+
+    /// Returns the code as bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[.. self.len]
+    }
+}
+
+impl Deref for CodeBuffer {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for CodeBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Write for CodeBuffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > CODE_CAPACITY {
+            return Err(fmt::Error);
+        }
+
+        self.bytes[self.len .. end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+impl PartialEq for CodeBuffer {
+    fn eq(&self, other: &CodeBuffer) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl fmt::Debug for CodeBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+
+/// A `Style` with its prefix rendered ahead of time.
+///
+/// Painting with a `Style` works out which codes to write every time the
+/// painted string gets displayed. That’s usually fast enough, but programs
+/// that paint the same style millions of times — such as syntax highlighters
+/// — can compile the style once, and have every string painted with it copy
+/// the finished codes instead.
+///
+/// A `CompiledStyle` holds the codes inline, so it doesn’t allocate, and can
+/// be copied around as freely as a `Style`.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::Colour::Red;
+///
+/// let keyword = Red.bold().compile();
+/// assert_eq!("\x1B[1;31m", keyword.prefix());
+/// assert_eq!("\x1B[1;31mfn\x1B[0m", keyword.paint("fn").to_string());
+/// ```
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct CompiledStyle {
+    style: Style,
+    prefix: CodeBuffer,
+}
+
+impl Style {
+
+    /// Renders this style’s prefix ahead of time, returning a
+    /// `CompiledStyle` that can paint strings without rendering it again.
+    pub fn compile(self) -> CompiledStyle {
+        CompiledStyle::new(self)
+    }
+}
+
+impl CompiledStyle {
+
+    /// Renders the prefix of the given style.
+    pub fn new(style: Style) -> CompiledStyle {
+        CompiledStyle { style, prefix: CodeBuffer::render(style.prefix()) }
+    }
+
+    /// The style this was compiled from.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// The prefix bytes for this style, as rendered when it was compiled.
+    pub fn prefix(&self) -> &str {
+        self.prefix.as_str()
+    }
+
+    /// The suffix bytes for this style: a reset code, unless the prefix is
+    /// empty.
+    pub fn suffix(&self) -> &str {
+        if self.prefix.as_str().is_empty() { "" } else { RESET }
+    }
+
+    /// Paints the given text with this style.
+    ///
+    /// The value returned implements `Display`, writing the text surrounded
+    /// by the pre-rendered codes.
+    pub fn paint<'a>(&'a self, text: &'a str) -> CompiledString<'a> {
+        CompiledString { style: self, text }
+    }
+}
+
+impl From<Style> for CompiledStyle {
+    fn from(style: Style) -> CompiledStyle {
+        CompiledStyle::new(style)
+    }
+}
+
+
+/// Some text painted with a `CompiledStyle`, as returned by
+/// [`CompiledStyle::paint`].
+///
+/// [`CompiledStyle::paint`]: struct.CompiledStyle.html#method.paint
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct CompiledString<'a> {
+    style: &'a CompiledStyle,
+    text: &'a str,
+}
+
+impl<'a> fmt::Display for CompiledString<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.style.prefix())?;
+        f.write_str(self.text)?;
+        f.write_str(self.style.suffix())
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use style::Colour::*;
+    use style::Style;
+
+    #[test]
+    #[cfg(feature = "rgb")]
+    fn longest_style_fits() {
+        let style = RGB(255, 255, 255).on(RGB(255, 255, 255))
+            .bold().dimmed().italic().underline().blink().reverse().hidden().strikethrough();
+
+        assert_eq!(style.compile().prefix(), style.prefix().to_string());
+    }
+
+    #[test]
+    fn plain() {
+        let style = Style::default().compile();
+        assert_eq!(style.paint("text").to_string(), "text");
+    }
+
+    #[test]
+    fn same_as_uncompiled() {
+        let style = Cyan.on(Blue).italic();
+        assert_eq!(style.compile().paint("hi").to_string(), style.paint("hi").to_string());
+    }
+}
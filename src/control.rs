@@ -0,0 +1,451 @@
+use std::fmt;
+use std::io;
+
+
+/// The two ways of ending an Operating System Command (OSC) sequence.
+///
+/// Most terminals accept either, but some only understand one of them:
+/// older xterm-alikes expect the bell character, whereas terminals that
+/// follow ECMA-48 strictly expect the String Terminator.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum OscTerminator {
+
+    /// End the sequence with the bell character, `BEL` (`\x07`).
+    Bell,
+
+    /// End the sequence with the String Terminator, `ESC \` (`\x1B\\`).
+    StringTerminator,
+}
+
+impl Default for OscTerminator {
+
+    /// The bell character is the most widely understood terminator, so it
+    /// gets used unless you ask for something else.
+    fn default() -> OscTerminator {
+        OscTerminator::Bell
+    }
+}
+
+impl fmt::Display for OscTerminator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            OscTerminator::Bell              => f.write_str("\x07"),
+            OscTerminator::StringTerminator  => f.write_str("\x1B\\"),
+        }
+    }
+}
+
+
+/// A value that changes the title of the terminal window (or tab) when it
+/// gets displayed.
+///
+/// This type implements the `Display` trait, so it can be written without
+/// doing any extra allocation. Create one with the [`set_title`] or
+/// [`set_icon_and_title`] functions.
+///
+/// Any control characters in the title are left out of the output, because
+/// they could end the sequence early and have the rest of the title printed
+/// to the screen.
+///
+/// [`set_title`]: fn.set_title.html
+/// [`set_icon_and_title`]: fn.set_icon_and_title.html
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Title<'a> {
+    title: &'a str,
+    code: u8,
+    terminator: OscTerminator,
+}
+
+/// Sets the title of the terminal window, using the `OSC 2` sequence.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::set_title;
+///
+/// assert_eq!("\x1B]2;Compiling…\x07",
+///            set_title("Compiling…").to_string());
+/// ```
+pub fn set_title<'a>(title: &'a str) -> Title<'a> {
+    Title { title, code: 2, terminator: OscTerminator::default() }
+}
+
+/// Sets both the icon name and the title of the terminal window, using the
+/// `OSC 0` sequence.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::set_icon_and_title;
+///
+/// assert_eq!("\x1B]0;vim\x07",
+///            set_icon_and_title("vim").to_string());
+/// ```
+pub fn set_icon_and_title<'a>(title: &'a str) -> Title<'a> {
+    Title { title, code: 0, terminator: OscTerminator::default() }
+}
+
+impl<'a> Title<'a> {
+
+    /// Returns a `Title` that ends with the given terminator instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::{set_title, OscTerminator};
+    ///
+    /// let title = set_title("50%").terminated_by(OscTerminator::StringTerminator);
+    /// assert_eq!("\x1B]2;50%\x1B\\", title.to_string());
+    /// ```
+    pub fn terminated_by(self, terminator: OscTerminator) -> Title<'a> {
+        Title { terminator, .. self }
+    }
+}
+
+impl<'a> fmt::Display for Title<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\x1B]{};", self.code)?;
+
+        for piece in self.title.split(char::is_control) {
+            f.write_str(piece)?;
+        }
+
+        write!(f, "{}", self.terminator)
+    }
+}
+
+
+/// The code that tells the terminal to start buffering its output, until
+/// [`END_SYNCHRONIZED_UPDATE`] is sent (private mode 2026).
+///
+/// [`END_SYNCHRONIZED_UPDATE`]: static.END_SYNCHRONIZED_UPDATE.html
+pub static BEGIN_SYNCHRONIZED_UPDATE: &str = "\x1B[?2026h";
+
+/// The code that tells the terminal to draw everything it has buffered since
+/// [`BEGIN_SYNCHRONIZED_UPDATE`] was sent.
+///
+/// [`BEGIN_SYNCHRONIZED_UPDATE`]: static.BEGIN_SYNCHRONIZED_UPDATE.html
+pub static END_SYNCHRONIZED_UPDATE: &str = "\x1B[?2026l";
+
+
+/// A writer that brackets everything written through it in a synchronized
+/// update, so the terminal renders it all at once instead of flickering
+/// through each fragment.
+///
+/// The update begins when the guard gets created, and ends when it gets
+/// dropped or [`finish`] is called. Terminals that don’t support
+/// synchronized updates ignore both codes.
+///
+/// [`finish`]: #method.finish
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+/// use ansi_term::{SynchronizedUpdate, Colour::Red};
+///
+/// let mut out = Vec::new();
+/// {
+///     let mut update = SynchronizedUpdate::begin(&mut out).unwrap();
+///     write!(update, "{}", Red.paint("repainted")).unwrap();
+/// }
+///
+/// assert_eq!(out, b"\x1B[?2026h\x1B[31mrepainted\x1B[0m\x1B[?2026l");
+/// ```
+#[derive(Debug)]
+pub struct SynchronizedUpdate<'a, W: io::Write + 'a> {
+    writer: &'a mut W,
+    finished: bool,
+}
+
+impl<'a, W: io::Write> SynchronizedUpdate<'a, W> {
+
+    /// Writes the code to begin a synchronized update to the given writer,
+    /// returning a guard that ends it again.
+    pub fn begin(writer: &'a mut W) -> io::Result<SynchronizedUpdate<'a, W>> {
+        writer.write_all(BEGIN_SYNCHRONIZED_UPDATE.as_bytes())?;
+        Ok(SynchronizedUpdate { writer, finished: false })
+    }
+
+    /// Ends the synchronized update and flushes the writer, returning any
+    /// error that happened. Dropping the guard does the same thing, but has
+    /// to ignore errors.
+    pub fn finish(mut self) -> io::Result<()> {
+        self.end()
+    }
+
+    fn end(&mut self) -> io::Result<()> {
+        if self.finished {
+            return Ok(());
+        }
+
+        self.finished = true;
+        self.writer.write_all(END_SYNCHRONIZED_UPDATE.as_bytes())?;
+        self.writer.flush()
+    }
+}
+
+impl<'a, W: io::Write> io::Write for SynchronizedUpdate<'a, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl<'a, W: io::Write> Drop for SynchronizedUpdate<'a, W> {
+    fn drop(&mut self) {
+        let _ = self.end();
+    }
+}
+
+/// Runs the given function inside a synchronized update on the writer,
+/// ending the update afterwards even if the function returns an error.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+/// use ansi_term::{synchronized, Colour::Green};
+///
+/// let mut out = Vec::new();
+/// synchronized(&mut out, |w| write!(w, "{}", Green.paint("done"))).unwrap();
+///
+/// assert_eq!(out, b"\x1B[?2026h\x1B[32mdone\x1B[0m\x1B[?2026l");
+/// ```
+pub fn synchronized<W, F, T>(writer: &mut W, f: F) -> io::Result<T>
+where W: io::Write,
+      F: FnOnce(&mut SynchronizedUpdate<W>) -> io::Result<T> {
+    let mut update = SynchronizedUpdate::begin(writer)?;
+    let result = f(&mut update);
+    let end = update.finish();
+    let value = result?;
+    end.map(|_| value)
+}
+
+
+/// The code that saves the cursor position, along with the current
+/// character attributes (`DECSC`).
+pub static SAVE_CURSOR: &str = "\x1B7";
+
+/// The code that moves the cursor back to the position saved by
+/// [`SAVE_CURSOR`], restoring the character attributes (`DECRC`).
+///
+/// [`SAVE_CURSOR`]: static.SAVE_CURSOR.html
+pub static RESTORE_CURSOR: &str = "\x1B8";
+
+/// The SCO/ANSI.SYS code that saves the cursor position (`CSI s`). Some
+/// terminals only understand this form, and others only the `DECSC` one.
+pub static SAVE_CURSOR_CSI: &str = "\x1B[s";
+
+/// The code that moves the cursor back to the position saved by
+/// [`SAVE_CURSOR_CSI`] (`CSI u`).
+///
+/// [`SAVE_CURSOR_CSI`]: static.SAVE_CURSOR_CSI.html
+pub static RESTORE_CURSOR_CSI: &str = "\x1B[u";
+
+
+/// A writer that saves the cursor position when it gets created, and moves
+/// the cursor back there when it gets dropped or [`restore`] is called.
+///
+/// This is useful for drawing something somewhere else on the screen, such
+/// as a progress indicator, and then carrying on from where you were.
+///
+/// [`restore`]: #method.restore
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+/// use ansi_term::{SavedCursor, Colour::Yellow};
+///
+/// let mut out = Vec::new();
+/// {
+///     let mut saved = SavedCursor::save(&mut out).unwrap();
+///     write!(saved, "\n{}", Yellow.paint("[####    ]")).unwrap();
+/// }
+///
+/// assert_eq!(out, b"\x1B7\n\x1B[33m[####    ]\x1B[0m\x1B8");
+/// ```
+#[derive(Debug)]
+pub struct SavedCursor<'a, W: io::Write + 'a> {
+    writer: &'a mut W,
+    restore_code: &'static str,
+    restored: bool,
+}
+
+impl<'a, W: io::Write> SavedCursor<'a, W> {
+
+    /// Saves the cursor position using the `DECSC` code, returning a guard
+    /// that restores it with `DECRC`.
+    pub fn save(writer: &'a mut W) -> io::Result<SavedCursor<'a, W>> {
+        writer.write_all(SAVE_CURSOR.as_bytes())?;
+        Ok(SavedCursor { writer, restore_code: RESTORE_CURSOR, restored: false })
+    }
+
+    /// Saves the cursor position using the `CSI s` code, returning a guard
+    /// that restores it with `CSI u`.
+    pub fn save_csi(writer: &'a mut W) -> io::Result<SavedCursor<'a, W>> {
+        writer.write_all(SAVE_CURSOR_CSI.as_bytes())?;
+        Ok(SavedCursor { writer, restore_code: RESTORE_CURSOR_CSI, restored: false })
+    }
+
+    /// Moves the cursor back to the saved position and flushes the writer,
+    /// returning any error that happened. Dropping the guard does the same
+    /// thing, but has to ignore errors.
+    pub fn restore(mut self) -> io::Result<()> {
+        self.end()
+    }
+
+    fn end(&mut self) -> io::Result<()> {
+        if self.restored {
+            return Ok(());
+        }
+
+        self.restored = true;
+        self.writer.write_all(self.restore_code.as_bytes())?;
+        self.writer.flush()
+    }
+}
+
+impl<'a, W: io::Write> io::Write for SavedCursor<'a, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl<'a, W: io::Write> Drop for SavedCursor<'a, W> {
+    fn drop(&mut self) {
+        let _ = self.end();
+    }
+}
+
+
+/// The code that makes the line the cursor is on show the top halves of
+/// characters drawn at twice the width and height (`DECDHL`).
+pub static DOUBLE_HEIGHT_TOP: &str = "\x1B#3";
+
+/// The code that makes the line the cursor is on show the bottom halves of
+/// characters drawn at twice the width and height (`DECDHL`).
+pub static DOUBLE_HEIGHT_BOTTOM: &str = "\x1B#4";
+
+/// The code that makes the line the cursor is on show characters at their
+/// normal size again (`DECSWL`).
+pub static SINGLE_WIDTH: &str = "\x1B#5";
+
+/// The code that makes the line the cursor is on show characters at twice
+/// their normal width (`DECDWL`).
+pub static DOUBLE_WIDTH: &str = "\x1B#6";
+
+
+/// A value that displays its contents on lines of double-width or
+/// double-height characters, for banners on terminals that support these
+/// VT100 modes. Create one with the [`double_width`] or [`double_height`]
+/// functions.
+///
+/// The modes apply to whole lines, so the contents should fit on one line,
+/// at half the usual number of columns. The styles of the contents get
+/// written as normal, so the banner can be in colour too. Terminals that
+/// don’t support the modes show the contents at their normal size.
+///
+/// [`double_width`]: fn.double_width.html
+/// [`double_height`]: fn.double_height.html
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct DoubleSize<T> {
+    contents: T,
+    double_height: bool,
+}
+
+/// Displays the contents on a line of double-width characters.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::double_width;
+/// use ansi_term::Colour::Green;
+///
+/// assert_eq!(double_width(Green.paint("OK")).to_string(),
+///            "\x1B#6\x1B[32mOK\x1B[0m");
+/// ```
+pub fn double_width<T: fmt::Display>(contents: T) -> DoubleSize<T> {
+    DoubleSize { contents, double_height: false }
+}
+
+/// Displays the contents on two lines of double-height characters: the
+/// contents get written twice, once for the top halves and once for the
+/// bottom halves of the characters, with a newline between them.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::double_height;
+/// use ansi_term::Colour::Red;
+///
+/// assert_eq!(double_height(Red.bold().paint("ALERT")).to_string(),
+///            "\x1B#3\x1B[1;31mALERT\x1B[0m\n\x1B#4\x1B[1;31mALERT\x1B[0m");
+/// ```
+pub fn double_height<T: fmt::Display>(contents: T) -> DoubleSize<T> {
+    DoubleSize { contents, double_height: true }
+}
+
+impl<T: fmt::Display> fmt::Display for DoubleSize<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.double_height {
+            write!(f, "{}{}\n{}{}", DOUBLE_HEIGHT_TOP, self.contents, DOUBLE_HEIGHT_BOTTOM, self.contents)
+        }
+        else {
+            write!(f, "{}{}", DOUBLE_WIDTH, self.contents)
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use display::ANSIStrings;
+    use style::Colour::{Red, Blue};
+
+    #[test]
+    fn title() {
+        assert_eq!(set_title("hello").to_string(), "\x1B]2;hello\x07");
+    }
+
+    #[test]
+    fn icon_and_title() {
+        let title = set_icon_and_title("hello").terminated_by(OscTerminator::StringTerminator);
+        assert_eq!(title.to_string(), "\x1B]0;hello\x1B\\");
+    }
+
+    #[test]
+    fn control_characters_are_removed() {
+        assert_eq!(set_title("a\x07b\x1B\\c\n").to_string(), "\x1B]2;ab\\c\x07");
+    }
+
+    #[test]
+    fn synchronized_update_ends_after_an_error() {
+        let mut out = Vec::new();
+        let result: io::Result<()> = synchronized(&mut out, |_| Err(io::Error::other("oops")));
+        assert!(result.is_err());
+        assert_eq!(out, b"\x1B[?2026h\x1B[?2026l");
+    }
+
+    #[test]
+    fn saved_cursor_csi() {
+        let mut out = Vec::new();
+        SavedCursor::save_csi(&mut out).unwrap().restore().unwrap();
+        assert_eq!(out, b"\x1B[s\x1B[u");
+    }
+
+    #[test]
+    fn double_size_strings() {
+        let strings = [ Red.paint("a"), Blue.paint("b") ];
+        assert_eq!(double_width(ANSIStrings(&strings)).to_string(), "\x1B#6\x1B[31ma\x1B[34mb\x1B[0m");
+    }
+}
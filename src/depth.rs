@@ -0,0 +1,403 @@
+use std::error::Error;
+use std::fmt;
+
+use style::{Colour, Style};
+use web::{nearest_palette, rgb};
+
+
+/// How many colours a terminal can show, which decides which `Colour`s it
+/// understands the codes for.
+///
+/// The depths are ordered, so a terminal that supports one depth supports
+/// everything below it too.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Default)]
+pub enum ColourDepth {
+
+    /// No colours at all, such as a VT100 or `TERM=dumb`. Properties such
+    /// as bold and underline might still work.
+    #[default]
+    Monochrome,
+
+    /// The sixteen basic colours. Only the eight normal ones have codes of
+    /// their own in this crate: they are the named `Colour` variants.
+    Ansi16,
+
+    /// The 256-colour palette, with `Colour::Fixed`.
+    Ansi256,
+
+    /// Any 24-bit colour, with `Colour::RGB`.
+    TrueColour,
+}
+
+impl ColourDepth {
+
+    /// Returns whether a terminal with this colour depth can show the given
+    /// colour.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::ColourDepth;
+    /// use ansi_term::Colour::Red;
+    ///
+    /// assert!(ColourDepth::Ansi16.supports(Red));
+    /// # #[cfg(all(feature = "fixed", feature = "rgb"))] {
+    /// use ansi_term::Colour::{Fixed, RGB};
+    ///
+    /// assert!(!ColourDepth::Ansi16.supports(Fixed(9)));
+    /// assert!(ColourDepth::Ansi256.supports(Fixed(9)));
+    /// assert!(!ColourDepth::Ansi256.supports(RGB(1, 2, 3)));
+    /// # }
+    /// ```
+    pub fn supports(self, colour: Colour) -> bool {
+        self >= colour.depth()
+    }
+}
+
+impl Colour {
+
+    /// Returns the lowest colour depth a terminal needs to show this colour.
+    pub fn depth(self) -> ColourDepth {
+        match self {
+            #[cfg(feature = "fixed")]
+            Colour::Fixed(_)      => ColourDepth::Ansi256,
+            #[cfg(feature = "rgb")]
+            Colour::RGB(_, _, _)  => ColourDepth::TrueColour,
+            #[cfg(feature = "cmyk")]
+            Colour::CMY(..) | Colour::CMYK(..)  => ColourDepth::TrueColour,
+            _                     => ColourDepth::Ansi16,
+        }
+    }
+
+    /// Returns the closest colour to this one that a terminal with the given
+    /// colour depth can show, or `None` for a monochrome terminal, which
+    /// can’t show any colours.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "fixed", feature = "rgb"))] {
+    /// use ansi_term::ColourDepth;
+    /// use ansi_term::Colour::{Red, Fixed, RGB};
+    ///
+    /// assert_eq!(RGB(250, 5, 5).to_depth(ColourDepth::TrueColour), Some(RGB(250, 5, 5)));
+    /// assert_eq!(RGB(250, 5, 5).to_depth(ColourDepth::Ansi256), Some(Fixed(196)));
+    /// assert_eq!(RGB(250, 5, 5).to_depth(ColourDepth::Ansi16), Some(Red));
+    /// assert_eq!(RGB(250, 5, 5).to_depth(ColourDepth::Monochrome), None);
+    /// # }
+    /// ```
+    ///
+    /// `CMY` and `CMYK` colours always get converted, as terminals with
+    /// 24-bit colour understand RGB colours but hardly ever these.
+    pub fn to_depth(self, depth: ColourDepth) -> Option<Colour> {
+        #[cfg(feature = "cmyk")]
+        if let (Colour::CMY(..), ColourDepth::TrueColour) | (Colour::CMYK(..), ColourDepth::TrueColour) = (self, depth) {
+            return Some(self.to_rgb());
+        }
+
+        if depth.supports(self) {
+            return Some(self);
+        }
+
+        let (r, g, b) = rgb(self);
+        match depth {
+            ColourDepth::Monochrome  => None,
+            #[cfg(feature = "fixed")]
+            ColourDepth::Ansi256     => Some(Colour::Fixed(nearest_palette(16 ..= 255, r, g, b))),
+            _                        => Colour::basic_iter().nth(usize::from(nearest_palette(0 ..= 7, r, g, b))),
+        }
+    }
+}
+
+
+/// A reason that a terminal can’t show a style, as returned by
+/// [`Style::validate`].
+///
+/// [`Style::validate`]: struct.Style.html#method.validate
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum StyleError {
+
+    /// The foreground colour needs more colours than the terminal has.
+    Foreground(Colour, ColourDepth),
+
+    /// The background colour needs more colours than the terminal has.
+    Background(Colour, ColourDepth),
+}
+
+impl fmt::Display for StyleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            StyleError::Foreground(colour, depth) => {
+                write!(f, "foreground colour {:?} can’t be shown at colour depth {:?}", colour, depth)
+            },
+            StyleError::Background(colour, depth) => {
+                write!(f, "background colour {:?} can’t be shown at colour depth {:?}", colour, depth)
+            },
+        }
+    }
+}
+
+impl Error for StyleError {}
+
+impl Style {
+
+    /// Checks that a terminal with the given colour depth can show
+    /// everything in this style, returning every reason it can’t.
+    ///
+    /// Programs with strict compatibility requirements can use this to
+    /// check their themes when they start, rather than find out from a user
+    /// whose terminal is showing garbage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::ColourDepth;
+    /// use ansi_term::Colour::Red;
+    ///
+    /// assert_eq!(Red.bold().validate(ColourDepth::Ansi16), Ok(()));
+    /// # #[cfg(feature = "rgb")] {
+    /// use ansi_term::StyleError;
+    /// use ansi_term::Colour::RGB;
+    ///
+    /// let style = RGB(255, 128, 0).on(Red);
+    /// assert_eq!(style.validate(ColourDepth::Ansi256),
+    ///            Err(vec![ StyleError::Foreground(RGB(255, 128, 0), ColourDepth::Ansi256) ]));
+    /// # }
+    /// ```
+    pub fn validate(&self, depth: ColourDepth) -> Result<(), Vec<StyleError>> {
+        let mut errors = Vec::new();
+
+        if let Some(colour) = self.foreground {
+            if !depth.supports(colour) {
+                errors.push(StyleError::Foreground(colour, depth));
+            }
+        }
+
+        if let Some(colour) = self.background {
+            if !depth.supports(colour) {
+                errors.push(StyleError::Background(colour, depth));
+            }
+        }
+
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
+    }
+
+    /// Returns this style with its colours replaced by the closest ones that
+    /// a terminal with the given colour depth can show, as with
+    /// [`Colour::to_depth`]. The other properties stay as they are.
+    ///
+    /// [`Colour::to_depth`]: enum.Colour.html#method.to_depth
+    pub fn to_depth(self, depth: ColourDepth) -> Style {
+        Style {
+            foreground: self.foreground.and_then(|c| c.to_depth(depth)),
+            background: self.background.and_then(|c| c.to_depth(depth)),
+            .. self
+        }
+    }
+}
+
+
+/// What a terminal can do beyond the basics, as it said when asked, or as
+/// its environment variables say.
+///
+/// Each field is the least that the terminal is known to support, so a
+/// terminal that gets detected as `Monochrome` might still have colours
+/// that it didn’t mention. Programs that ask the terminal and look at the
+/// environment can take the `max` of the two colour depths.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct TerminalCapabilities {
+
+    /// The name and version of the terminal, such as `kitty(0.31.0)`, if it
+    /// reported them.
+    pub version: Option<String>,
+
+    /// How many colours the terminal can show.
+    pub colour_depth: ColourDepth,
+
+    /// Whether the terminal can draw curly, dotted, and dashed underlines,
+    /// and underlines in a different colour from the text.
+    pub underline_styles: bool,
+
+    /// Whether the terminal can make text into a link, with `OSC 8`.
+    pub hyperlinks: bool,
+}
+
+/// The terminals that are known by the name they reply to `XTVERSION`
+/// with, and what they support.
+static KNOWN_TERMINALS: &[(&str, ColourDepth, bool, bool)] = &[
+    ("contour",  ColourDepth::TrueColour, true,  true),
+    ("foot",     ColourDepth::TrueColour, true,  true),
+    ("ghostty",  ColourDepth::TrueColour, true,  true),
+    ("iTerm2",   ColourDepth::TrueColour, true,  true),
+    ("kitty",    ColourDepth::TrueColour, true,  true),
+    ("mintty",   ColourDepth::TrueColour, true,  true),
+    ("tmux",     ColourDepth::TrueColour, true,  false),
+    ("WezTerm",  ColourDepth::TrueColour, true,  true),
+    ("XTerm",    ColourDepth::Ansi256,    false, false),
+];
+
+impl TerminalCapabilities {
+
+    /// Works out the capabilities from a terminal’s replies to the primary
+    /// device attributes query (`DA1`), as a list of numbers without the
+    /// leading `?`, and to the `XTVERSION` query, if it sent one.
+    ///
+    /// The device attributes only say whether the terminal has the basic
+    /// colours (attribute 22), so anything more comes from recognising the
+    /// terminal by its name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::{ColourDepth, TerminalCapabilities};
+    ///
+    /// let vt = TerminalCapabilities::from_replies(&[ 62, 22 ], None);
+    /// assert_eq!(vt.colour_depth, ColourDepth::Ansi16);
+    ///
+    /// let kitty = TerminalCapabilities::from_replies(&[ 62, 22 ], Some("kitty(0.31.0)"));
+    /// assert_eq!(kitty.colour_depth, ColourDepth::TrueColour);
+    /// assert!(kitty.hyperlinks);
+    /// ```
+    pub fn from_replies(attributes: &[u16], version: Option<&str>) -> TerminalCapabilities {
+        let mut capabilities = TerminalCapabilities {
+            version: version.map(String::from),
+            .. TerminalCapabilities::default()
+        };
+
+        // The first number is the terminal’s conformance level, not an attribute.
+        if attributes.iter().skip(1).any(|&a| a == 22) {
+            capabilities.colour_depth = ColourDepth::Ansi16;
+        }
+
+        let name = version.and_then(|v| v.split(['(', ' ']).next()).unwrap_or("");
+        capabilities.add_known_terminal(name);
+
+        capabilities
+    }
+
+    /// Works out the capabilities from the environment variables that
+    /// terminals set: `COLORTERM`, which is `truecolor` or `24bit` in
+    /// terminals with 24-bit colour, `TERM`, and `TERM_PROGRAM`.
+    ///
+    /// With the `terminfo` feature, the number of colours comes from the
+    /// terminfo entry for `TERM` instead of from its name, and the `RGB` and
+    /// `Tc` capabilities count as 24-bit colour, so a terminal with no
+    /// colours in its entry gets none.
+    ///
+    /// This is quicker than asking the terminal, and works when the program
+    /// isn’t connected to one, but the variables get lost over `ssh` and
+    /// `sudo`, and terminals that can’t do everything sometimes claim to.
+    pub fn from_env() -> TerminalCapabilities {
+        let var = |name| std::env::var(name).ok();
+
+        #[cfg(feature = "terminfo")]
+        let terminfo = ::terminfo::Terminfo::from_env().ok().map(|t| t.colour_depth());
+        #[cfg(not(feature = "terminfo"))]
+        let terminfo = None;
+
+        TerminalCapabilities::from_vars(var("TERM").as_deref(), var("COLORTERM").as_deref(), var("TERM_PROGRAM").as_deref(), terminfo)
+    }
+
+    fn from_vars(term: Option<&str>, colorterm: Option<&str>, term_program: Option<&str>, terminfo: Option<ColourDepth>) -> TerminalCapabilities {
+        let colour_depth = match (term, colorterm, terminfo) {
+            (_, Some("truecolor"), _) | (_, Some("24bit"), _)      => ColourDepth::TrueColour,
+            (None, _, _) | (Some(""), _, _) | (Some("dumb"), _, _)  => ColourDepth::Monochrome,
+            (_, _, Some(depth))                                     => depth,
+            (Some(term), _, None) if term.contains("256col")        => ColourDepth::Ansi256,
+            (Some(_), _, None)                                      => ColourDepth::Ansi16,
+        };
+
+        let mut capabilities = TerminalCapabilities { colour_depth, .. TerminalCapabilities::default() };
+
+        // Some terminals only say who they are with their TERM.
+        let name = match (term_program, term) {
+            (Some("iTerm.app"), _)  => "iTerm2",
+            (Some(program), _)      => program,
+            (_, Some("xterm"))      => "",
+            (_, Some(term))         => term.trim_start_matches("xterm-"),
+            _                       => "",
+        };
+        capabilities.add_known_terminal(name);
+
+        capabilities
+    }
+
+    fn add_known_terminal(&mut self, name: &str) {
+        let known = KNOWN_TERMINALS.iter().find(|t| t.0.eq_ignore_ascii_case(name));
+        if let Some(&(_, depth, underline_styles, hyperlinks)) = known {
+            self.colour_depth = self.colour_depth.max(depth);
+            self.underline_styles = underline_styles;
+            self.hyperlinks = hyperlinks;
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use style::Colour::*;
+
+    #[test]
+    #[cfg(feature = "cmyk")]
+    fn cmyk_converted_to_rgb() {
+        assert_eq!(CMY(0, 255, 255).to_depth(ColourDepth::TrueColour), Some(RGB(255, 0, 0)));
+        assert_eq!(CMYK(0, 0, 0, 255).to_depth(ColourDepth::TrueColour), Some(RGB(0, 0, 0)));
+        assert_eq!(CMYK(255, 0, 255, 0).to_depth(ColourDepth::Ansi16), Some(Green));
+    }
+
+    #[test]
+    fn monochrome_only_plain_colours() {
+        assert_eq!(Style::new().bold().underline().validate(ColourDepth::Monochrome), Ok(()));
+        assert_eq!(Black.on(White).validate(ColourDepth::Monochrome), Err(vec![
+            StyleError::Foreground(Black, ColourDepth::Monochrome),
+            StyleError::Background(White, ColourDepth::Monochrome),
+        ]));
+    }
+
+    #[test]
+    #[cfg(feature = "fixed")]
+    fn bright_colours_to_basic() {
+        assert_eq!(Fixed(9).to_depth(ColourDepth::Ansi16), Some(Red));
+        assert_eq!(Fixed(15).on(Fixed(236)).to_depth(ColourDepth::Ansi16), White.on(Black));
+        assert_eq!(Blue.bold().to_depth(ColourDepth::Monochrome), Style::new().bold());
+    }
+
+    #[test]
+    #[cfg(all(feature = "fixed", feature = "rgb"))]
+    fn true_colour_supports_everything() {
+        for colour in &[ Red, Fixed(200), RGB(1, 2, 3) ] {
+            assert!(ColourDepth::TrueColour.supports(*colour));
+        }
+    }
+
+    #[test]
+    fn unknown_terminal_without_colours() {
+        let capabilities = TerminalCapabilities::from_replies(&[ 22, 1 ], Some("Mystery 1.0"));
+        assert_eq!(capabilities.colour_depth, ColourDepth::Monochrome);
+        assert!(!capabilities.underline_styles);
+        assert_eq!(capabilities.version.as_deref(), Some("Mystery 1.0"));
+    }
+
+    #[test]
+    fn environment_variables() {
+        let depth = |term, colorterm| TerminalCapabilities::from_vars(term, colorterm, None, None).colour_depth;
+        assert_eq!(depth(None, None), ColourDepth::Monochrome);
+        assert_eq!(depth(Some("dumb"), None), ColourDepth::Monochrome);
+        assert_eq!(depth(Some("screen"), None), ColourDepth::Ansi16);
+        assert_eq!(depth(Some("xterm-256color"), None), ColourDepth::Ansi256);
+        assert_eq!(depth(Some("xterm-256color"), Some("truecolor")), ColourDepth::TrueColour);
+
+        let kitty = TerminalCapabilities::from_vars(Some("xterm-kitty"), None, None, None);
+        assert_eq!(kitty.colour_depth, ColourDepth::TrueColour);
+        assert!(kitty.underline_styles);
+    }
+
+    #[test]
+    fn terminfo_colours() {
+        let depth = |term, terminfo| TerminalCapabilities::from_vars(Some(term), None, None, Some(terminfo)).colour_depth;
+        assert_eq!(depth("vt100", ColourDepth::Monochrome), ColourDepth::Monochrome);
+        assert_eq!(depth("xterm-direct", ColourDepth::TrueColour), ColourDepth::TrueColour);
+        assert_eq!(depth("xterm-kitty", ColourDepth::Ansi256), ColourDepth::TrueColour);
+    }
+}
@@ -0,0 +1,170 @@
+//! A report of what the crate thinks the terminal can do, for programs to
+//! print when a user says the colours look wrong.
+//!
+//! Bug reports about colours usually come down to the terminal, the
+//! multiplexer in front of it, or the environment variables that got lost
+//! on the way, none of which the person reporting the bug can easily see.
+//! A program can add an option that prints [`report`], and ask for its
+//! output along with a screenshot.
+//!
+//! [`report`]: fn.report.html
+
+use std::env;
+
+use depth::{ColourDepth, TerminalCapabilities};
+use display::ANSIString;
+use style::{Colour, Style};
+
+
+/// Returns a styled summary of the terminal’s capabilities, with one line
+/// for each thing that was detected, and a line of sample colours at each
+/// colour depth so the user can see which ones actually work.
+///
+/// The capabilities come from the environment variables, and, with the
+/// `query` feature on Unix, from asking the terminal too. On Windows, this
+/// also tries to turn on escape code support in the console, and says
+/// whether it worked.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ansi_term::ANSIStrings;
+/// use ansi_term::diagnostics::report;
+///
+/// print!("{}", ANSIStrings(&report()));
+/// ```
+pub fn report() -> Vec<ANSIString<'static>> {
+    let environment = TerminalCapabilities::from_env();
+    let mut lines = Vec::new();
+
+    for name in &[ "TERM", "COLORTERM", "TERM_PROGRAM" ] {
+        let value = env::var(name).unwrap_or_else(|_| String::from("(unset)"));
+        lines.push((*name, Style::default().paint(value)));
+    }
+
+    match queried() {
+        Some(Ok(queried)) => {
+            let version = queried.version.clone().unwrap_or_else(|| String::from("(didn’t say)"));
+            lines.push(("Terminal", Style::default().paint(version)));
+            lines.extend(capability_lines(&environment, Some(&queried)));
+        },
+        Some(Err(e)) => {
+            lines.push(("Terminal", Colour::Red.paint(format!("didn’t reply ({})", e))));
+            lines.extend(capability_lines(&environment, None));
+        },
+        None => {
+            lines.extend(capability_lines(&environment, None));
+        },
+    }
+
+    #[cfg(windows)]
+    {
+        let vt = match ::windows::enable_ansi_support() {
+            Ok(())    => yes_no(true),
+            Err(code) => Colour::Red.paint(format!("failed (error {})", code)),
+        };
+        lines.push(("VT processing", vt));
+    }
+
+    render(lines)
+}
+
+#[cfg(all(unix, feature = "query"))]
+fn queried() -> Option<::std::io::Result<TerminalCapabilities>> {
+    Some(::query::capabilities())
+}
+
+#[cfg(not(all(unix, feature = "query")))]
+fn queried() -> Option<::std::io::Result<TerminalCapabilities>> {
+    None
+}
+
+/// Returns the lines for each capability, combining what the environment
+/// says with what the terminal said, if it was asked.
+fn capability_lines(environment: &TerminalCapabilities, queried: Option<&TerminalCapabilities>) -> Vec<(&'static str, ANSIString<'static>)> {
+    let combined = match queried {
+        Some(q) => TerminalCapabilities {
+            version:           q.version.clone(),
+            colour_depth:      environment.colour_depth.max(q.colour_depth),
+            underline_styles:  environment.underline_styles || q.underline_styles,
+            hyperlinks:        environment.hyperlinks || q.hyperlinks,
+        },
+        None => environment.clone(),
+    };
+
+    vec![
+        ("Colour depth", Style::default().paint(format!("{:?}", combined.colour_depth))),
+        ("Underline styles", yes_no(combined.underline_styles)),
+        ("Hyperlinks", yes_no(combined.hyperlinks)),
+    ]
+}
+
+fn yes_no(yes: bool) -> ANSIString<'static> {
+    if yes { Colour::Green.paint("yes") } else { Colour::Red.paint("no") }
+}
+
+/// Lines up the labels and values, and adds the sample colours.
+fn render(lines: Vec<(&'static str, ANSIString<'static>)>) -> Vec<ANSIString<'static>> {
+    let width = lines.iter().map(|l| l.0.len()).max().unwrap_or(0);
+    let mut report = Vec::new();
+
+    for (label, value) in lines {
+        report.push(Style::new().bold().paint(format!("{:width$}  ", label, width = width)));
+        report.push(value);
+        report.push(Style::default().paint("\n"));
+    }
+
+    for &(depth, ref samples) in &samples() {
+        report.push(Style::new().bold().paint(format!("{:width$}  ", format!("{:?}", depth), width = width)));
+        for &colour in samples {
+            report.push(Style::default().on(colour).paint("  "));
+        }
+        report.push(Style::default().paint("\n"));
+    }
+
+    report
+}
+
+/// Returns some colours for each colour depth, which should all look
+/// different from each other on a terminal that supports that depth.
+fn samples() -> Vec<(ColourDepth, Vec<Colour>)> {
+    #[allow(unused_mut)]
+    let mut samples = vec![ (ColourDepth::Ansi16, Colour::basic_iter().collect()) ];
+
+    #[cfg(feature = "fixed")]
+    samples.push((ColourDepth::Ansi256, [ 196, 202, 208, 214, 220, 226, 190, 154 ].iter().map(|&n| Colour::Fixed(n)).collect()));
+
+    #[cfg(feature = "rgb")]
+    samples.push((ColourDepth::TrueColour, (0 .. 8).map(|n| Colour::RGB(255 - n * 32, n * 32, 128)).collect()));
+
+    samples
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use display::ANSIStrings;
+
+    #[test]
+    fn labels_line_up() {
+        let capabilities = TerminalCapabilities { hyperlinks: true, .. TerminalCapabilities::default() };
+        let report = render(capability_lines(&capabilities, None));
+        let plain = ANSIStrings(&report).to_plain_string();
+
+        let mut lines = plain.lines();
+        assert_eq!(lines.next(), Some("Colour depth      Monochrome"));
+        assert_eq!(lines.next(), Some("Underline styles  no"));
+        assert_eq!(lines.next(), Some("Hyperlinks        yes"));
+    }
+
+    #[test]
+    fn queried_capabilities_add_to_environment() {
+        let environment = TerminalCapabilities { colour_depth: ColourDepth::Ansi256, .. TerminalCapabilities::default() };
+        let queried = TerminalCapabilities { colour_depth: ColourDepth::Ansi16, hyperlinks: true, .. TerminalCapabilities::default() };
+        let lines = capability_lines(&environment, Some(&queried));
+
+        assert_eq!(&*lines[0].1, "Ansi256");
+        assert_eq!(&*lines[2].1, "yes");
+    }
+}
@@ -0,0 +1,174 @@
+use display::ANSIString;
+use style::Colour::{Green, Red};
+use style::Style;
+
+
+/// The styles used to paint the parts of a diff made by [`diff_words`] or
+/// [`diff_chars`].
+///
+/// [`diff_words`]: fn.diff_words.html
+/// [`diff_chars`]: fn.diff_chars.html
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct DiffStyle {
+
+    /// The style of text that is only in the new string.
+    pub inserted: Style,
+
+    /// The style of text that is only in the old string.
+    pub deleted: Style,
+
+    /// The style of text that is in both.
+    pub unchanged: Style,
+}
+
+impl Default for DiffStyle {
+
+    /// Insertions in green, deletions in red with a line through them, and
+    /// everything else left plain.
+    fn default() -> DiffStyle {
+        DiffStyle {
+            inserted: Green.normal(),
+            deleted: Red.strikethrough(),
+            unchanged: Style::default(),
+        }
+    }
+}
+
+
+/// Compares two strings word by word, returning the text of both with the
+/// words that were inserted or deleted painted in the given styles.
+///
+/// Runs of whitespace count as words of their own, so changes to spacing
+/// show up too. Deleted text comes before the text that replaced it.
+///
+/// This function is only available with the `diff` feature. It takes time
+/// and memory proportional to the product of the two strings’ word counts,
+/// so it’s meant for lines and short passages rather than whole files.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{diff_words, DiffStyle, ANSIStrings};
+///
+/// let diff = diff_words("the quick fox", "the slow fox", &DiffStyle::default());
+/// assert_eq!(ANSIStrings(&diff).to_string(),
+///            "the \x1B[9;31mquick\x1B[0m\x1B[32mslow\x1B[0m fox");
+/// ```
+pub fn diff_words(old: &str, new: &str, style: &DiffStyle) -> Vec<ANSIString<'static>> {
+    diff_tokens(&words(old), &words(new), style)
+}
+
+/// Compares two strings character by character, returning the text of both
+/// with the characters that were inserted or deleted painted in the given
+/// styles.
+///
+/// This function is only available with the `diff` feature, and has the
+/// same costs as [`diff_words`], but in characters.
+///
+/// [`diff_words`]: fn.diff_words.html
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{diff_chars, DiffStyle, ANSIStrings};
+///
+/// let diff = diff_chars("colour", "color", &DiffStyle::default());
+/// assert_eq!(ANSIStrings(&diff).to_string(),
+///            "colo\x1B[9;31mu\x1B[0mr");
+/// ```
+pub fn diff_chars(old: &str, new: &str, style: &DiffStyle) -> Vec<ANSIString<'static>> {
+    diff_tokens(&chars(old), &chars(new), style)
+}
+
+/// Splits a string into alternating runs of whitespace and everything else.
+fn words(s: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+
+    for (index, c) in s.char_indices().skip(1) {
+        let previous = s[.. index].chars().next_back().unwrap();
+        if previous.is_whitespace() != c.is_whitespace() {
+            tokens.push(&s[start .. index]);
+            start = index;
+        }
+    }
+
+    if start < s.len() {
+        tokens.push(&s[start ..]);
+    }
+
+    tokens
+}
+
+fn chars(s: &str) -> Vec<&str> {
+    s.char_indices().map(|(index, c)| &s[index .. index + c.len_utf8()]).collect()
+}
+
+fn diff_tokens(old: &[&str], new: &[&str], style: &DiffStyle) -> Vec<ANSIString<'static>> {
+    // lcs[i][j] is the length of the longest common subsequence of
+    // old[i ..] and new[j ..].
+    let mut lcs = vec![ vec![ 0_usize; new.len() + 1 ]; old.len() + 1 ];
+    for i in (0 .. old.len()).rev() {
+        for j in (0 .. new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] { lcs[i + 1][j + 1] + 1 }
+                                       else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+        }
+    }
+
+    let mut parts: Vec<(Style, String)> = Vec::new();
+    let mut push = |s: Style, token: &str| {
+        match parts.last_mut() {
+            Some(last) if last.0 == s => last.1.push_str(token),
+            _                         => parts.push((s, String::from(token))),
+        }
+    };
+
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            push(style.unchanged, old[i]);
+            i += 1;
+            j += 1;
+        }
+        else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            push(style.deleted, old[i]);
+            i += 1;
+        }
+        else {
+            push(style.inserted, new[j]);
+            j += 1;
+        }
+    }
+
+    parts.into_iter().map(|(s, text)| s.paint(text)).collect()
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn split_words() {
+        assert_eq!(words("  a bc\td "), vec![ "  ", "a", " ", "bc", "\t", "d", " " ]);
+    }
+
+    #[test]
+    fn identical() {
+        assert_eq!(diff_words("a b", "a b", &DiffStyle::default()), vec![ Style::default().paint("a b") ]);
+    }
+
+    #[test]
+    fn only_insertions() {
+        let style = DiffStyle::default();
+        assert_eq!(diff_chars("", "ab", &style), vec![ style.inserted.paint("ab") ]);
+    }
+
+    #[test]
+    fn deletion_then_insertion() {
+        let style = DiffStyle::default();
+        assert_eq!(diff_words("one two", "one three", &style), vec![
+            style.unchanged.paint("one "), style.deleted.paint("two"), style.inserted.paint("three"),
+        ]);
+    }
+}
@@ -4,6 +4,7 @@ use super::Style;
 /// When printing out one coloured string followed by another, use one of
 /// these rules to figure out which *extra* control codes need to be sent.
 #[derive(PartialEq, Clone, Copy, Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum Difference {
 
     /// Print out the control codes specified by this style to end up looking
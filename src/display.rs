@@ -1,17 +1,30 @@
-use std::borrow::Cow;
+use std::borrow::{Borrow, Cow};
 use std::fmt;
-use std::io;
+use std::io::{self, IoSlice};
 use std::ops::Deref;
+use std::slice;
+use std::str;
 
-use ansi::RESET;
-use difference::Difference;
 use style::{Style, Colour};
-use write::AnyWrite;
+use util::{str_width, visible_width};
+use write::{AnyWrite, write_all_vectored};
 
 
 /// An `ANSIGenericString` includes a generic string type and a `Style` to
 /// display that string.  `ANSIString` and `ANSIByteString` are aliases for
 /// this type on `str` and `\[u8]`, respectively.
+///
+/// The text can itself contain strings that have already been painted. The
+/// outer style gets written again after each of their resets, so the rest
+/// of the text doesn’t lose it:
+///
+/// ```
+/// use ansi_term::Colour::{Red, Blue};
+///
+/// let line = Red.paint(format!("{} world", Blue.paint("hello")));
+/// assert_eq!(line.to_string(),
+///            "\x1B[31m\x1B[34mhello\x1B[0m\x1B[31m world\x1B[0m");
+/// ```
 #[derive(PartialEq, Debug)]
 pub struct ANSIGenericString<'a, S: 'a + ToOwned + ?Sized>
 where <S as ToOwned>::Owned: fmt::Debug {
@@ -92,6 +105,16 @@ pub type ANSIString<'a> = ANSIGenericString<'a, str>;
 /// `ANSIByteString` when styling text with an unknown encoding.
 pub type ANSIByteString<'a> = ANSIGenericString<'a, [u8]>;
 
+/// An `ANSIString` that owns its text, so it can be stored in a struct,
+/// sent to another thread, or returned from a function without borrowing
+/// anything. Use [`into_owned`] to get one from a string that borrows.
+///
+/// [`into_owned`]: struct.ANSIGenericString.html#method.into_owned
+pub type OwnedANSIString = ANSIString<'static>;
+
+/// An `ANSIByteString` that owns its bytes.
+pub type OwnedANSIByteString = ANSIByteString<'static>;
+
 impl<'a, I, S: 'a + ToOwned + ?Sized> From<I> for ANSIGenericString<'a, S>
 where I: Into<Cow<'a, S>>,
       <S as ToOwned>::Owned: fmt::Debug {
@@ -115,6 +138,30 @@ impl<'a, S: 'a + ToOwned + ?Sized> ANSIGenericString<'a, S>
     pub fn style_ref_mut(&mut self) -> &mut Style {
         &mut self.style
     }
+
+    /// Returns a string with the same style that owns its text, copying the
+    /// text if it was borrowed, so it no longer has a lifetime tied to it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::OwnedANSIString;
+    /// use ansi_term::Colour::Red;
+    ///
+    /// fn label(name: &str) -> OwnedANSIString {
+    ///     Red.paint(name).into_owned()
+    /// }
+    ///
+    /// let name = String::from("error");
+    /// assert_eq!(label(&name), Red.paint("error"));
+    /// ```
+    pub fn into_owned(self) -> ANSIGenericString<'static, S>
+    where S: 'static {
+        ANSIGenericString {
+            style: self.style,
+            string: Cow::Owned(self.string.into_owned()),
+        }
+    }
 }
 
 impl<'a, S: 'a + ToOwned + ?Sized> Deref for ANSIGenericString<'a, S>
@@ -154,6 +201,68 @@ pub fn ANSIByteStrings<'a>(arg: &'a [ANSIByteString<'a>]) -> ANSIByteStrings<'a>
     ANSIGenericStrings(arg)
 }
 
+impl<'a, S: 'a + ToOwned + ?Sized + PartialEq> ANSIGenericStrings<'a, S>
+where <S as ToOwned>::Owned: fmt::Debug {
+
+    /// Returns an iterator over the strings, in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::ANSIStrings;
+    /// use ansi_term::Colour::{Red, Blue};
+    ///
+    /// let strings = [ Red.paint("a"), Blue.paint("bc") ];
+    /// let lengths: Vec<usize> = ANSIStrings(&strings).iter().map(|s| s.len()).collect();
+    /// assert_eq!(lengths, vec![ 1, 2 ]);
+    /// ```
+    pub fn iter(&self) -> slice::Iter<'a, ANSIGenericString<'a, S>> {
+        self.0.iter()
+    }
+
+    /// Returns copies of the strings that own their text, so they can be
+    /// kept after the slice they came from has gone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::{ANSIStrings, OwnedANSIString};
+    /// use ansi_term::Colour::{Red, Blue};
+    ///
+    /// let saved: Vec<OwnedANSIString> = {
+    ///     let text = String::from("ab");
+    ///     let strings = [ Red.paint(&text[.. 1]), Blue.paint(&text[1 ..]) ];
+    ///     ANSIStrings(&strings).into_owned()
+    /// };
+    ///
+    /// assert_eq!(ANSIStrings(&saved).to_string(), "\x1B[31ma\x1B[34mb\x1B[0m");
+    /// ```
+    pub fn into_owned(self) -> Vec<ANSIGenericString<'static, S>>
+    where S: 'static {
+        self.0.iter().map(|s| s.clone().into_owned()).collect()
+    }
+}
+
+impl<'a, S: 'a + ToOwned + ?Sized + PartialEq> IntoIterator for ANSIGenericStrings<'a, S>
+where <S as ToOwned>::Owned: fmt::Debug {
+    type Item = &'a ANSIGenericString<'a, S>;
+    type IntoIter = slice::Iter<'a, ANSIGenericString<'a, S>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<'a, S: 'a + ToOwned + ?Sized + PartialEq> IntoIterator for &ANSIGenericStrings<'a, S>
+where <S as ToOwned>::Owned: fmt::Debug {
+    type Item = &'a ANSIGenericString<'a, S>;
+    type IntoIter = slice::Iter<'a, ANSIGenericString<'a, S>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
 
 // ---- paint functions ----
 
@@ -169,6 +278,59 @@ impl Style {
             style:  self,
         }
     }
+
+    /// Paints whatever the given closure writes with this style, without
+    /// having to write it to a string first.
+    ///
+    /// The value returned implements `Display`, calling the closure with the
+    /// formatter between the style’s prefix and suffix each time it gets
+    /// displayed. This is a way to paint values that are complicated to
+    /// format, without defining a new type to implement `Display` on.
+    ///
+    /// ```
+    /// use ansi_term::Colour::Green;
+    ///
+    /// let items = [ "a", "b", "c" ];
+    /// let list = Green.normal().paint_with(|f| {
+    ///     for (i, item) in items.iter().enumerate() {
+    ///         if i > 0 { f.write_str(", ")?; }
+    ///         f.write_str(item)?;
+    ///     }
+    ///     Ok(())
+    /// });
+    ///
+    /// assert_eq!(list.to_string(), "\x1B[32ma, b, c\x1B[0m");
+    /// ```
+    #[must_use]
+    pub fn paint_with<F>(self, write: F) -> PaintedWith<F>
+    where F: Fn(&mut fmt::Formatter) -> fmt::Result {
+        PaintedWith { style: self, write }
+    }
+}
+
+/// Whatever a closure writes, painted with a style, as returned by
+/// [`Style::paint_with`].
+///
+/// [`Style::paint_with`]: struct.Style.html#method.paint_with
+#[derive(Clone, Copy)]
+pub struct PaintedWith<F> {
+    style: Style,
+    write: F,
+}
+
+impl<F> fmt::Display for PaintedWith<F>
+where F: Fn(&mut fmt::Formatter) -> fmt::Result {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.style.prefix())?;
+        (self.write)(f)?;
+        write!(f, "{}", self.style.suffix())
+    }
+}
+
+impl<F> fmt::Debug for PaintedWith<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PaintedWith").field("style", &self.style).finish()
+    }
 }
 
 
@@ -194,40 +356,273 @@ impl Colour {
 }
 
 
+// ---- nested styles ----
+
+/// Text that might have escape codes of its own in it, such as another
+/// string that has already been painted.
+///
+/// When a string like that gets painted again, the reset at the end of the
+/// inner string would turn off the outer style for the rest of the text, so
+/// the outer style’s prefix has to be written again after every reset.
+pub(crate) trait Nested {
+
+    /// Splits the text just after the first reset code in it, if there is
+    /// one with more text after it.
+    fn split_after_reset(&self) -> Option<(&Self, &Self)>;
+}
+
+impl Nested for str {
+    fn split_after_reset(&self) -> Option<(&str, &str)> {
+        let (head, tail) = self.as_bytes().split_after_reset()?;
+        Some((&self[.. head.len()], &self[self.len() - tail.len() ..]))
+    }
+}
+
+impl Nested for [u8] {
+    fn split_after_reset(&self) -> Option<(&[u8], &[u8])> {
+        let mut from = 0;
+
+        while let Some(offset) = self[from ..].iter().position(|&b| b == 0x1B) {
+            let start = from + offset;
+            for code in &[ &b"\x1B[0m"[..], &b"\x1B[m"[..] ] {
+                let end = start + code.len();
+                if self[start ..].starts_with(code) && end < self.len() {
+                    return Some(self.split_at(end));
+                }
+            }
+
+            from = start + 1;
+        }
+
+        None
+    }
+}
+
+/// Writes the text of a string painted with the given style, writing the
+/// style’s prefix again after any reset codes already in the text.
+fn write_nested<S, W>(w: &mut W, style: Style, text: &S) -> Result<(), W::Error>
+where S: Nested + ?Sized,
+      W: AnyWrite<wstr=S> + ?Sized {
+    let mut rest = text;
+
+    if !style.is_plain() {
+        while let Some((head, tail)) = rest.split_after_reset() {
+            w.write_str(head)?;
+            write!(w, "{}", style.prefix())?;
+            rest = tail;
+        }
+    }
+
+    w.write_str(rest)
+}
+
+
 // ---- writers for individual ANSI strings ----
 
+/// Writes whatever `write` writes, padded with the formatter’s fill
+/// character to the width it asks for, if any. The padding goes outside of
+/// the escape codes, and `width` measures the visible width of what gets
+/// written, which only happens when there’s a width to pad to.
+fn pad_visible<M, F>(f: &mut fmt::Formatter, width: M, write: F) -> fmt::Result
+where M: FnOnce() -> usize,
+      F: FnOnce(&mut fmt::Formatter) -> fmt::Result {
+    let padding = match f.width() {
+        Some(w)  => w.saturating_sub(width()),
+        None     => return write(f),
+    };
+
+    if padding == 0 {
+        return write(f);
+    }
+
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Right)   => (padding, 0),
+        Some(fmt::Alignment::Center)  => (padding / 2, padding - padding / 2),
+        _                             => (0, padding),
+    };
+
+    let fill = f.fill();
+    for _ in 0 .. before {
+        fmt::Write::write_char(f, fill)?;
+    }
+
+    write(f)?;
+
+    for _ in 0 .. after {
+        fmt::Write::write_char(f, fill)?;
+    }
+
+    Ok(())
+}
+
+/// Formatting an `ANSIString` with a width, such as with `{:>10}`, pads it
+/// according to the width of its text on the screen, ignoring the escape
+/// codes. The fill character and alignment are honoured too, and the
+/// padding is left unstyled.
+///
+/// ```
+/// use ansi_term::Colour::Red;
+///
+/// assert_eq!(format!("[{:>4}]", Red.paint("ab")), "[  \x1B[31mab\x1B[0m]");
+/// assert_eq!(format!("[{:.^5}]", Red.paint("ab")), "[.\x1B[31mab\x1B[0m..]");
+/// ```
 impl<'a> fmt::Display for ANSIString<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let w: &mut fmt::Write = f;
+        pad_visible(f, || str_width(&self.string), |f| {
+            let w: &mut dyn fmt::Write = f;
+            self.write_to_any(w)
+        })
+    }
+}
+
+impl<'a> ANSIString<'a> {
+    /// Write an `ANSIString` to a `fmt::Write`, such as a `String`.  This
+    /// writes the escape sequences for the associated `Style` around the
+    /// text, without going through the `format!` machinery.
+    ///
+    /// ```
+    /// use ansi_term::Colour::Red;
+    ///
+    /// let mut output = String::from("> ");
+    /// Red.paint("error").write_to_fmt(&mut output).unwrap();
+    /// assert_eq!(output, "> \x1B[31merror\x1B[0m");
+    /// ```
+    pub fn write_to_fmt<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        let w: &mut dyn fmt::Write = w;
         self.write_to_any(w)
     }
 }
 
 impl<'a> ANSIByteString<'a> {
     /// Write an `ANSIByteString` to an `io::Write`.  This writes the escape
-    /// sequences for the associated `Style` around the bytes.
+    /// sequences for the associated `Style` around the bytes, using a single
+    /// vectored write where the writer supports it.
     pub fn write_to<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
-        let w: &mut io::Write = w;
-        self.write_to_any(w)
+        if self.is_nested() {
+            let w: &mut dyn io::Write = w;
+            return self.write_to_any(w);
+        }
+
+        let prefix = self.style.prefix().to_string();
+        let suffix = self.style.suffix().to_string();
+
+        let mut slices = [
+            IoSlice::new(prefix.as_bytes()),
+            IoSlice::new(&self.string),
+            IoSlice::new(suffix.as_bytes()),
+        ];
+
+        write_all_vectored(w, &mut slices)
+    }
+
+    /// Returns the bytes of this string with the escape sequences for its
+    /// `Style` around them, as one buffer.
+    ///
+    /// ```
+    /// use ansi_term::Colour::Blue;
+    ///
+    /// assert_eq!(Blue.paint(&b"data"[..]).render_bytes(), b"\x1B[34mdata\x1B[0m");
+    /// ```
+    pub fn render_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.string.len() + 16);
+        self.write_to(&mut buf).expect("writing to a Vec");
+        buf
+    }
+
+    /// Write an `ANSIByteString` to an `io::Write` with a single call to
+    /// `write_all`, so the escape sequences never get written without the
+    /// bytes they surround.
+    pub fn write_to_buffered<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(&self.render_bytes())
+    }
+
+    /// Returns an `ANSIString` with the same style, which can be displayed
+    /// with `println!` and friends. Any bytes that aren’t valid UTF-8 get
+    /// shown as U+FFFD REPLACEMENT CHARACTER, inside the style.
+    ///
+    /// The bytes only get copied if some of them had to be replaced.
+    ///
+    /// ```
+    /// use ansi_term::Colour::Red;
+    ///
+    /// let bytes = Red.paint(&b"caf\xE9"[..]);
+    /// assert_eq!(bytes.display_lossy().to_string(), "\x1B[31mcaf\u{FFFD}\x1B[0m");
+    /// ```
+    pub fn display_lossy<'s>(&'s self) -> ANSIString<'s> {
+        self.style.paint(String::from_utf8_lossy(&self.string))
+    }
+
+    /// Checks that the bytes are valid UTF-8, returning a string that
+    /// borrows them as text if they are, which can then be displayed or
+    /// formatted. If they aren’t, the error says how far into the bytes the
+    /// invalid part starts.
+    ///
+    /// ```
+    /// use ansi_term::Colour::Red;
+    ///
+    /// let bytes = Red.paint(&b"caf\xC3\xA9"[..]);
+    /// assert_eq!(bytes.to_str().unwrap(), Red.paint("café"));
+    ///
+    /// let bytes = Red.paint(&b"caf\xE9"[..]);
+    /// assert_eq!(bytes.to_str().unwrap_err().valid_up_to(), 3);
+    /// ```
+    pub fn to_str(&self) -> Result<ANSIString<'_>, str::Utf8Error> {
+        Ok(self.style.paint(str::from_utf8(&self.string)?))
+    }
+
+    /// Renders this byte string as a `String`, with the same replacements
+    /// as [`display_lossy`].
+    ///
+    /// [`display_lossy`]: #method.display_lossy
+    pub fn to_string_lossy(&self) -> String {
+        self.display_lossy().to_string()
     }
 }
 
 impl<'a, S: 'a + ToOwned + ?Sized> ANSIGenericString<'a, S>
 where <S as ToOwned>::Owned: fmt::Debug, &'a S: AsRef<[u8]> {
-    fn write_to_any<W: AnyWrite<wstr=S> + ?Sized>(&self, w: &mut W) -> Result<(), W::Error> {
+    pub(crate) fn write_to_any<W: AnyWrite<wstr=S> + ?Sized>(&self, w: &mut W) -> Result<(), W::Error>
+    where S: Nested {
         write!(w, "{}", self.style.prefix())?;
-        w.write_str(self.string.as_ref())?;
+        write_nested(w, self.style, self.string.as_ref())?;
         write!(w, "{}", self.style.suffix())
     }
+
+    /// Whether any of the text needs the style to be written again partway
+    /// through, which the vectored writers can’t do.
+    fn is_nested(&self) -> bool
+    where S: Nested {
+        !self.style.is_plain() && self.string.as_ref().split_after_reset().is_some()
+    }
 }
 
 
 // ---- writers for combined ANSI strings ----
 
+/// Formatting `ANSIStrings` with a width pads them according to the width
+/// of their text on the screen, in the same way as a single `ANSIString`.
 impl<'a> fmt::Display for ANSIStrings<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let f: &mut fmt::Write = f;
-        self.write_to_any(f)
+        pad_visible(f, || visible_width(self), |f| {
+            let f: &mut dyn fmt::Write = f;
+            self.write_to_any(f)
+        })
+    }
+}
+
+impl<'a> ANSIStrings<'a> {
+    /// Write `ANSIStrings` to a `fmt::Write`, such as a `String`.  This
+    /// writes the minimal escape sequences for the associated `Style`s
+    /// around each string.
+    pub fn write_to_fmt<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        let w: &mut dyn fmt::Write = w;
+        self.write_to_any(w)
+    }
+
+    /// Write `ANSIStrings` to an `io::Write` with a single call to
+    /// `write_all`, by rendering them into one `String` first.
+    pub fn write_to_buffered<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(self.to_string().as_bytes())
     }
 }
 
@@ -235,46 +630,168 @@ impl<'a> ANSIByteStrings<'a> {
     /// Write `ANSIByteStrings` to an `io::Write`.  This writes the minimal
     /// escape sequences for the associated `Style`s around each set of
     /// bytes.
+    ///
+    /// All the escape sequences get rendered up front, so the whole lot can
+    /// be handed to the writer in as few vectored writes as it accepts,
+    /// rather than two or three writes per string.
     pub fn write_to<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
-        let w: &mut io::Write = w;
-        self.write_to_any(w)
+        use std::fmt::Write;
+
+        if self.0.iter().any(|s| s.is_nested()) {
+            let w: &mut dyn io::Write = w;
+            return self.write_to_any(w);
+        }
+
+        // The codes go into one string, and each string remembers where its
+        // codes start and end, so they can be borrowed once it’s complete.
+        let mut codes = String::new();
+        let mut pieces = Vec::with_capacity(self.0.len());
+        let mut previous: Option<Style> = None;
+
+        for fragment in self.0 {
+            let start = codes.len();
+            match previous {
+                None         => write!(codes, "{}", fragment.style.prefix()),
+                Some(style)  => write!(codes, "{}", style.infix(fragment.style)),
+            }.expect("writing to a String");
+
+            pieces.push((start .. codes.len(), &*fragment.string));
+            previous = Some(fragment.style);
+        }
+
+        let end = codes.len();
+        if let Some(last) = previous {
+            codes.push_str(&last.suffix().to_string());
+        }
+
+        let codes = codes.as_bytes();
+        let mut slices = Vec::with_capacity(pieces.len() * 2 + 1);
+        for (range, bytes) in pieces {
+            if !range.is_empty() { slices.push(IoSlice::new(&codes[range])) }
+            if !bytes.is_empty() { slices.push(IoSlice::new(bytes)) }
+        }
+        slices.push(IoSlice::new(&codes[end ..]));
+
+        write_all_vectored(w, &mut slices)
+    }
+
+    /// Returns all the byte strings with the minimal escape sequences between
+    /// them, as one buffer.
+    pub fn render_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.write_to(&mut buf).expect("writing to a Vec");
+        buf
+    }
+
+    /// Write `ANSIByteStrings` to an `io::Write` with a single call to
+    /// `write_all`, instead of one or more writes per string. If the program
+    /// is interrupted part-way through, the terminal is less likely to be
+    /// left in the middle of a style.
+    ///
+    /// ```
+    /// use ansi_term::ANSIByteStrings;
+    /// use ansi_term::Colour::{Red, Green};
+    ///
+    /// let mut out = Vec::new();
+    /// ANSIByteStrings(&[ Red.paint(&b"a"[..]), Green.paint(&b"b"[..]) ])
+    ///     .write_to_buffered(&mut out).unwrap();
+    /// assert_eq!(out, b"\x1B[31ma\x1B[32mb\x1B[0m");
+    /// ```
+    pub fn write_to_buffered<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(&self.render_bytes())
+    }
+
+    /// Renders all the byte strings as a `String`, with the minimal escape
+    /// sequences between them, showing any bytes that aren’t valid UTF-8 as
+    /// U+FFFD REPLACEMENT CHARACTER.
+    pub fn to_string_lossy(&self) -> String {
+        let strings: Vec<ANSIString> = self.0.iter().map(|s| s.display_lossy()).collect();
+        ANSIStrings(&strings).to_string()
     }
 }
 
 impl<'a, S: 'a + ToOwned + ?Sized + PartialEq> ANSIGenericStrings<'a, S>
-where <S as ToOwned>::Owned: fmt::Debug, &'a S: AsRef<[u8]> {
-    fn write_to_any<W: AnyWrite<wstr=S> + ?Sized>(&self, w: &mut W) -> Result<(), W::Error> {
-        use self::Difference::*;
-
-        let first = match self.0.first() {
-            None => return Ok(()),
-            Some(f) => f,
-        };
-
-        write!(w, "{}", first.style.prefix())?;
-        w.write_str(first.string.as_ref())?;
-
-        for window in self.0.windows(2) {
-            match Difference::between(&window[0].style, &window[1].style) {
-                ExtraStyles(style) => write!(w, "{}", style.prefix())?,
-                Reset              => write!(w, "{}{}", RESET, window[1].style.prefix())?,
-                NoDifference       => {/* Do nothing! */},
-            }
+where <S as ToOwned>::Owned: fmt::Debug {
+    pub(crate) fn write_to_any<W: AnyWrite<wstr=S> + ?Sized>(&self, w: &mut W) -> Result<(), W::Error>
+    where S: Nested {
+        write_iter_to_any(self.0, w)
+    }
+}
 
-            w.write_str(&window[1].string)?;
-        }
 
-        // Write the final reset string after all of the ANSIStrings have been
-        // written, *except* if the last one has no styles, because it would
-        // have already been written by this point.
-        if let Some(last) = self.0.last() {
-            if !last.style.is_plain() {
-                write!(w, "{}", RESET)?;
-            }
+// ---- writers for streams of ANSI strings ----
+
+/// Writes the strings produced by an iterator to a `fmt::Write`, with the
+/// minimum of control characters between them, just like `ANSIStrings`.
+///
+/// Unlike `ANSIStrings`, the strings don’t need to be collected into a slice
+/// first: each one gets written as soon as it is produced, and only the
+/// style of the previous one is kept around. The iterator can yield either
+/// `ANSIString` values or references to them.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::write_strings_to_fmt;
+/// use ansi_term::Colour::{Red, Green};
+///
+/// let lines = (1 ..= 3).map(|n| {
+///     let colour = if n % 2 == 0 { Red } else { Green };
+///     colour.paint(n.to_string())
+/// });
+///
+/// let mut output = String::new();
+/// write_strings_to_fmt(lines, &mut output).unwrap();
+/// assert_eq!(output, "\x1B[32m1\x1B[31m2\x1B[32m3\x1B[0m");
+/// ```
+pub fn write_strings_to_fmt<'a, I, B, W>(strings: I, w: &mut W) -> fmt::Result
+where I: IntoIterator<Item = B>,
+      B: Borrow<ANSIString<'a>>,
+      W: fmt::Write {
+    let w: &mut dyn fmt::Write = w;
+    write_iter_to_any(strings, w)
+}
+
+/// Writes the byte strings produced by an iterator to an `io::Write`, with
+/// the minimum of control characters between them, just like
+/// `ANSIByteStrings`, but without collecting them into a slice first.
+pub fn write_byte_strings_to<'a, I, B, W>(strings: I, w: &mut W) -> io::Result<()>
+where I: IntoIterator<Item = B>,
+      B: Borrow<ANSIByteString<'a>>,
+      W: io::Write {
+    let w: &mut dyn io::Write = w;
+    write_iter_to_any(strings, w)
+}
+
+/// Writes each string with the codes that take the terminal from the
+/// previous string’s style to its own, in a single pass that only keeps
+/// that previous style around. Collections of strings get written with this
+/// too, so there’s only the one way they can be rendered.
+fn write_iter_to_any<'a, S, I, B, W>(strings: I, w: &mut W) -> Result<(), W::Error>
+where S: 'a + ToOwned + ?Sized + Nested,
+      <S as ToOwned>::Owned: fmt::Debug,
+      I: IntoIterator<Item = B>,
+      B: Borrow<ANSIGenericString<'a, S>>,
+      W: AnyWrite<wstr=S> + ?Sized {
+    let mut previous: Option<Style> = None;
+
+    for string in strings {
+        let string = string.borrow();
+
+        match previous {
+            None         => write!(w, "{}", string.style.prefix())?,
+            Some(style)  => write!(w, "{}", style.infix(string.style))?,
         }
 
-        Ok(())
+        write_nested(w, string.style, &*string.string)?;
+        previous = Some(string.style);
     }
+
+    if let Some(last) = previous {
+        write!(w, "{}", last.suffix())?;
+    }
+
+    Ok(())
 }
 
 
@@ -282,7 +799,7 @@ where <S as ToOwned>::Owned: fmt::Debug, &'a S: AsRef<[u8]> {
 
 #[cfg(test)]
 mod tests {
-    pub use super::super::ANSIStrings;
+    pub use super::super::{ANSIStrings, ANSIByteStrings};
     pub use style::Style;
     pub use style::Colour::*;
 
@@ -293,4 +810,93 @@ mod tests {
         let output = format!("{}", ANSIStrings( &[ one, two ] ));
         assert_eq!(&*output, "onetwo");
     }
+
+    #[test]
+    fn write_to_vectored() {
+        use display::ANSIByteStrings;
+
+        let strings = [
+            Red.paint(&b"a"[..]), Red.bold().paint(&b""[..]), Style::default().paint(&b"b"[..]),
+            Blue.paint(&b"c"[..]), Blue.paint(&b"d"[..]),
+        ];
+        let mut output = Vec::new();
+        ANSIByteStrings(&strings).write_to(&mut output).unwrap();
+        assert_eq!(output, b"\x1B[31ma\x1B[1m\x1B[0mb\x1B[34mcd\x1B[0m");
+    }
+
+    #[test]
+    fn write_iterators() {
+        use display::{write_strings_to_fmt, write_byte_strings_to};
+
+        let strings = [ Red.paint("a"), Red.bold().paint("b"), Style::default().paint("c"), Blue.paint("d") ];
+        let mut output = String::new();
+        write_strings_to_fmt(&strings, &mut output).unwrap();
+        assert_eq!(output, ANSIStrings(&strings).to_string());
+
+        let mut bytes = Vec::new();
+        write_byte_strings_to(strings.iter().map(|s| s.style_ref().paint(s.as_bytes())), &mut bytes).unwrap();
+        assert_eq!(bytes, output.as_bytes());
+    }
+
+    #[test]
+    fn padding_ignores_codes() {
+        let strings = [ Red.paint("a"), Blue.paint("bc") ];
+        assert_eq!(format!("{:<5}|", ANSIStrings(&strings)), "\x1B[31ma\x1B[34mbc\x1B[0m  |");
+        assert_eq!(format!("{:2}|", ANSIStrings(&strings)), "\x1B[31ma\x1B[34mbc\x1B[0m|");
+    }
+
+    #[test]
+    fn nested_strings() {
+        let inner = format!("{} world", Blue.paint("hello"));
+        assert_eq!(Red.paint(inner).to_string(), "\x1B[31m\x1B[34mhello\x1B[0m\x1B[31m world\x1B[0m");
+    }
+
+    #[test]
+    fn nested_reset_at_the_end() {
+        let inner = Blue.paint("hello").to_string();
+        assert_eq!(Red.paint(inner).to_string(), "\x1B[31m\x1B[34mhello\x1B[0m\x1B[0m");
+    }
+
+    #[test]
+    fn nested_bytes() {
+        use display::ANSIByteStrings;
+
+        let strings = [ Red.paint(&b"a\x1B[mb"[..]), Blue.paint(&b"c"[..]) ];
+        let mut output = Vec::new();
+        ANSIByteStrings(&strings).write_to(&mut output).unwrap();
+        assert_eq!(output, b"\x1B[31ma\x1B[m\x1B[31mb\x1B[34mc\x1B[0m");
+    }
+
+    #[test]
+    fn paint_with_plain() {
+        let painted = Style::default().paint_with(|f| write!(f, "{}", 12));
+        assert_eq!(painted.to_string(), "12");
+    }
+
+    #[test]
+    fn write_to_fmt() {
+        let strings = [ Red.paint("a"), Red.bold().paint("b") ];
+        let mut output = String::new();
+        ANSIStrings(&strings).write_to_fmt(&mut output).unwrap();
+        assert_eq!(output, ANSIStrings(&strings).to_string());
+    }
+
+    #[test]
+    fn byte_strings_lossy() {
+        let strings = [ Red.paint(&b"a\xFF"[..]), Red.bold().paint(&b"b"[..]) ];
+        assert_eq!(ANSIByteStrings(&strings).to_string_lossy(), "\x1B[31ma\u{FFFD}\x1B[1mb\x1B[0m");
+    }
+
+    #[test]
+    fn strings_in_for_loops() {
+        let strings = [ Red.paint("a"), Blue.paint("b") ];
+        let ansi = ANSIStrings(&strings);
+
+        let mut text = String::new();
+        for string in &ansi {
+            text.push_str(string);
+        }
+        assert_eq!(text, "ab");
+        assert_eq!(ansi.into_iter().count(), 2);
+    }
 }
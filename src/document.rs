@@ -0,0 +1,239 @@
+use std::io;
+
+use styled_text::StyledText;
+
+
+/// A document made of lines of [`StyledText`], which remembers which lines
+/// have changed since it was last rendered.
+///
+/// Interactive programs can keep their whole screen in a `StyledDocument`,
+/// edit whichever lines they like, and then call [`render_dirty`] to redraw
+/// only the lines that actually changed.
+///
+/// Every way of getting at a line mutably marks it as dirty, whether or not
+/// it ends up being changed. Inserting or removing a line marks every line
+/// after it as dirty too, because they have all moved.
+///
+/// [`StyledText`]: struct.StyledText.html
+/// [`render_dirty`]: #method.render_dirty
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{StyledDocument, StyledText};
+/// use ansi_term::Colour::Green;
+///
+/// let mut doc = StyledDocument::new();
+/// doc.push_line(StyledText::new());
+/// doc.push_line(StyledText::new());
+/// doc.mark_clean();
+///
+/// doc.line_mut(1).push(Green.normal(), "ok");
+///
+/// let mut out = Vec::new();
+/// doc.render_dirty(&mut out).unwrap();
+/// assert_eq!(out, b"\x1B[2;1H\x1B[2K\x1B[32mok\x1B[0m");
+/// assert_eq!(doc.dirty_lines().count(), 0);
+/// ```
+#[derive(PartialEq, Clone, Default, Debug)]
+pub struct StyledDocument {
+    lines: Vec<StyledText>,
+    dirty: Vec<bool>,
+
+    /// How many lines there were when the document was last rendered, so
+    /// lines that have been removed since then can be cleared.
+    rendered: usize,
+}
+
+impl StyledDocument {
+
+    /// Creates a new document with no lines.
+    pub fn new() -> StyledDocument {
+        StyledDocument::default()
+    }
+
+    /// Returns the number of lines in the document.
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Returns whether the document has no lines.
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Returns the line at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no line at that index.
+    pub fn line(&self, index: usize) -> &StyledText {
+        &self.lines[index]
+    }
+
+    /// Returns the line at the given index for editing, marking it as dirty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no line at that index.
+    pub fn line_mut(&mut self, index: usize) -> &mut StyledText {
+        self.dirty[index] = true;
+        &mut self.lines[index]
+    }
+
+    /// Replaces the line at the given index. The line only gets marked as
+    /// dirty if it’s different from the one it replaces.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no line at that index.
+    pub fn set_line(&mut self, index: usize, line: StyledText) {
+        if self.lines[index] != line {
+            self.lines[index] = line;
+            self.dirty[index] = true;
+        }
+    }
+
+    /// Adds a line to the end of the document.
+    pub fn push_line(&mut self, line: StyledText) {
+        self.lines.push(line);
+        self.dirty.push(true);
+    }
+
+    /// Inserts a line before the given index, marking it and every line
+    /// after it as dirty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than the number of lines.
+    pub fn insert_line(&mut self, index: usize, line: StyledText) {
+        self.lines.insert(index, line);
+        self.dirty.insert(index, true);
+        self.mark_dirty_from(index);
+    }
+
+    /// Removes and returns the line at the given index, marking every line
+    /// after it as dirty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no line at that index.
+    pub fn remove_line(&mut self, index: usize) -> StyledText {
+        self.dirty.remove(index);
+        self.mark_dirty_from(index);
+        self.lines.remove(index)
+    }
+
+    /// Returns whether the line at the given index has changed since the
+    /// document was last rendered.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no line at that index.
+    pub fn is_dirty(&self, index: usize) -> bool {
+        self.dirty[index]
+    }
+
+    /// Returns the indices of the lines that have changed since the document
+    /// was last rendered, in order.
+    pub fn dirty_lines<'a>(&'a self) -> impl Iterator<Item=usize> + 'a {
+        self.dirty.iter().enumerate().filter(|d| *d.1).map(|d| d.0)
+    }
+
+    /// Marks every line as dirty, so the whole document gets redrawn, such
+    /// as after the screen has been cleared.
+    pub fn mark_all_dirty(&mut self) {
+        self.mark_dirty_from(0);
+    }
+
+    /// Marks every line as clean, as though it had just been rendered.
+    pub fn mark_clean(&mut self) {
+        for d in &mut self.dirty {
+            *d = false;
+        }
+
+        self.rendered = self.lines.len();
+    }
+
+    /// Redraws the lines that have changed since the document was last
+    /// rendered, then marks them as clean.
+    ///
+    /// Line *n* of the document gets drawn on row *n* of the screen,
+    /// counting from zero. Each dirty line is written by moving the cursor to
+    /// the start of its row, clearing the row, and writing the line; rows
+    /// that held lines that have since been removed get cleared.
+    pub fn render_dirty<W: io::Write>(&mut self, w: &mut W) -> io::Result<()> {
+        for index in 0 .. self.lines.len().max(self.rendered) {
+            match self.lines.get(index) {
+                Some(line) if self.dirty[index] => write!(w, "\x1B[{};1H\x1B[2K{}", index + 1, line)?,
+                Some(_)                          => {},
+                None                             => write!(w, "\x1B[{};1H\x1B[2K", index + 1)?,
+            }
+        }
+
+        self.mark_clean();
+        Ok(())
+    }
+
+    fn mark_dirty_from(&mut self, index: usize) {
+        for d in &mut self.dirty[index ..] {
+            *d = true;
+        }
+    }
+}
+
+impl From<Vec<StyledText>> for StyledDocument {
+
+    /// Creates a document out of the given lines, all of them dirty.
+    fn from(lines: Vec<StyledText>) -> StyledDocument {
+        let dirty = vec![true; lines.len()];
+        StyledDocument { lines, dirty, rendered: 0 }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use style::Colour::*;
+
+    fn doc() -> StyledDocument {
+        let mut doc = StyledDocument::new();
+        for word in &["one", "two", "three"] {
+            let mut line = StyledText::new();
+            line.push(Red.normal(), word);
+            doc.push_line(line);
+        }
+        doc.mark_clean();
+        doc
+    }
+
+    #[test]
+    fn set_same_line() {
+        let mut d = doc();
+        let line = d.line(0).clone();
+        d.set_line(0, line);
+        assert_eq!(d.dirty_lines().count(), 0);
+    }
+
+    #[test]
+    fn insert_marks_following() {
+        let mut d = doc();
+        d.insert_line(1, StyledText::new());
+        assert_eq!(d.dirty_lines().collect::<Vec<_>>(), vec![ 1, 2, 3 ]);
+    }
+
+    #[test]
+    fn removed_lines_get_cleared() {
+        let mut d = doc();
+        d.remove_line(2);
+
+        let mut out = Vec::new();
+        d.render_dirty(&mut out).unwrap();
+        assert_eq!(out, b"\x1B[3;1H\x1B[2K");
+
+        out.clear();
+        d.render_dirty(&mut out).unwrap();
+        assert_eq!(out, b"");
+    }
+}
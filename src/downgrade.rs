@@ -0,0 +1,241 @@
+use std::io::{self, Write};
+
+use web::nearest_palette;
+
+
+/// The longest a control sequence can get before it’s taken to be garbage
+/// and written as it is, so a sequence that never gets finished can’t hold
+/// back the rest of the output.
+const MAX_PENDING: usize = 64;
+
+
+/// A writer that turns the RGB colours in the codes written through it into
+/// the closest colours from the 256-colour palette, for terminals that show
+/// garbage instead of RGB colours, such as older versions of `screen` and
+/// `tmux`.
+///
+/// Every `38;2;r;g;b` and `48;2;r;g;b` code gets rewritten, as do the ones
+/// separated by colons such as `38:2::r:g:b`, whether they came from this
+/// crate or from another program’s output that’s being passed through, and
+/// codes split across several writes get rewritten too. Everything else
+/// gets written as it is.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+/// use ansi_term::Ansi256Writer;
+///
+/// let mut out = Ansi256Writer::new(Vec::new());
+/// out.write_all(b"\x1B[1;38;2;255;0;0mhot\x1B[0m").unwrap();
+///
+/// assert_eq!(out.into_inner(), b"\x1B[1;38;5;196mhot\x1B[0m");
+/// ```
+#[derive(Debug)]
+pub struct Ansi256Writer<W: Write> {
+    inner: SgrRewriter<W>,
+}
+
+impl<W: Write> Ansi256Writer<W> {
+
+    /// Returns a writer that rewrites RGB colours on their way to the given
+    /// writer.
+    pub fn new(writer: W) -> Ansi256Writer<W> {
+        Ansi256Writer { inner: SgrRewriter::new(writer) }
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner.writer
+    }
+
+    /// Returns the underlying writer. The start of a sequence that hasn’t
+    /// been finished gets thrown away.
+    pub fn into_inner(self) -> W {
+        self.inner.writer
+    }
+}
+
+impl<W: Write> Write for Ansi256Writer<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf, rewrite)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.writer.flush()
+    }
+}
+
+
+/// The part of a writer that rewrites the parameters of the SGR codes
+/// written through it, including codes split across several writes, and
+/// writes everything else as it is.
+#[derive(Debug)]
+pub(crate) struct SgrRewriter<W: Write> {
+    pub(crate) writer: W,
+
+    /// The start of a control sequence that hasn’t been finished yet.
+    pending: Vec<u8>,
+}
+
+impl<W: Write> SgrRewriter<W> {
+
+    pub(crate) fn new(writer: W) -> SgrRewriter<W> {
+        SgrRewriter { writer, pending: Vec::new() }
+    }
+
+    /// Writes some bytes, with the parameters of each SGR code in them
+    /// passed through the given function.
+    pub(crate) fn write<F>(&mut self, buf: &[u8], rewrite: F) -> io::Result<usize>
+    where F: Fn(&[u8]) -> String {
+        let mut start = 0;
+
+        for (index, &byte) in buf.iter().enumerate() {
+            if !self.pending.is_empty() {
+                self.push(byte, &rewrite)?;
+                start = index + 1;
+            }
+            else if byte == 0x1B {
+                self.writer.write_all(&buf[start .. index])?;
+                self.push(byte, &rewrite)?;
+                start = index + 1;
+            }
+        }
+
+        self.writer.write_all(&buf[start ..])?;
+        Ok(buf.len())
+    }
+
+    /// Handles a byte that’s part of a sequence, writing the sequence once
+    /// it’s finished.
+    fn push<F>(&mut self, byte: u8, rewrite: &F) -> io::Result<()>
+    where F: Fn(&[u8]) -> String {
+        self.pending.push(byte);
+
+        let finished = match self.pending.len() {
+            2  => byte != b'[',
+            _  => (0x40 ..= 0x7E).contains(&byte),
+        };
+
+        if finished {
+            if byte == b'm' && self.pending[1] == b'[' {
+                let params = &self.pending[2 .. self.pending.len() - 1];
+                let code = format!("\x1B[{}m", rewrite(params));
+                self.writer.write_all(code.as_bytes())?;
+            }
+            else {
+                self.writer.write_all(&self.pending)?;
+            }
+
+            self.pending.clear();
+        }
+        else if self.pending.len() >= MAX_PENDING {
+            self.writer.write_all(&self.pending)?;
+            self.pending.clear();
+        }
+
+        Ok(())
+    }
+}
+
+/// Rewrites the parameters of an SGR code, replacing the RGB colours with
+/// palette colours. Parameters that can’t be read get left as they are.
+///
+/// Only the colour cube and the greys get used, as terminals often change
+/// what the first sixteen colours look like.
+fn rewrite(params: &[u8]) -> String {
+    let params = String::from_utf8_lossy(params);
+    let params: Vec<&str> = params.split(';').collect();
+    let mut output = Vec::with_capacity(params.len());
+    let mut index = 0;
+
+    while index < params.len() {
+        let param = params[index];
+        let rest = &params[index + 1 ..];
+
+        if param == "38" || param == "48" {
+            if rest.first() == Some(&"2") && rest.len() >= 4 {
+                let channels: Vec<u8> = rest[1 .. 4].iter().filter_map(|c| c.parse().ok()).collect();
+                if let [ r, g, b ] = channels[..] {
+                    output.push(format!("{};5;{}", param, nearest_palette(16 ..= 255, r, g, b)));
+                    index += 5;
+                    continue;
+                }
+            }
+            else if rest.first() == Some(&"5") && rest.len() >= 2 {
+                output.push(params[index .. index + 3].join(";"));
+                index += 3;
+                continue;
+            }
+        }
+
+        // A colour in the colon form, with or without its colour space,
+        // which stays in that form.
+        let subparams: Vec<&str> = param.split(':').collect();
+        if let [ kind @ ("38" | "48"), "2", .. ] = subparams[..] {
+            let channels: Vec<u8> = subparams[subparams.len().saturating_sub(3) ..].iter().filter_map(|c| c.parse().ok()).collect();
+            if let (5 | 6, [ r, g, b ]) = (subparams.len(), &channels[..]) {
+                output.push(format!("{}:5:{}", kind, nearest_palette(16 ..= 255, *r, *g, *b)));
+                index += 1;
+                continue;
+            }
+        }
+
+        output.push(param.to_string());
+        index += 1;
+    }
+
+    output.join(";")
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn downgrade(chunks: &[&str]) -> String {
+        let mut out = Ansi256Writer::new(Vec::new());
+        for chunk in chunks {
+            out.write_all(chunk.as_bytes()).unwrap();
+        }
+        String::from_utf8(out.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn split_across_writes() {
+        assert_eq!(downgrade(&[ "a\x1B[48;2", ";0;0;2", "55mb" ]), "a\x1B[48;5;21mb");
+    }
+
+    #[test]
+    fn palette_colours_kept() {
+        assert_eq!(downgrade(&[ "\x1B[38;5;2;1m" ]), "\x1B[38;5;2;1m");
+    }
+
+    #[test]
+    fn other_sequences_kept() {
+        assert_eq!(downgrade(&[ "\x1B[2J\x1B]0;38;2;1;2;3m\x07\x1B[38;2;x;0;0m" ]),
+                   "\x1B[2J\x1B]0;38;2;1;2;3m\x07\x1B[38;2;x;0;0m");
+    }
+
+    #[test]
+    fn colon_syntax() {
+        assert_eq!(downgrade(&[ "\x1B[1;38:2::255:0:0;48:2:0:0:255;38:2:1:2;38:2m" ]), "\x1B[1;38:5:196;48:5:21;38:2:1:2;38:2m");
+    }
+
+    #[cfg(feature = "rgb")]
+    #[test]
+    fn crate_output_in_colon_syntax() {
+        use syntax::{ColourSyntax, ColourSyntaxWriter};
+        use style::Colour::RGB;
+
+        let mut out = ColourSyntaxWriter::new(Ansi256Writer::new(Vec::new()), ColourSyntax::Colon);
+        write!(out, "{}", RGB(255, 0, 0).paint("hot")).unwrap();
+        assert_eq!(out.into_inner().into_inner(), b"\x1B[38:5:196mhot\x1B[0m");
+    }
+
+    #[test]
+    fn unfinished_sequence_let_through() {
+        let garbage = format!("\x1B[{}", "1;".repeat(40));
+        assert_eq!(downgrade(&[ &garbage, "text" ]), format!("{}text", garbage));
+    }
+}
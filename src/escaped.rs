@@ -0,0 +1,106 @@
+use std::fmt;
+
+use display::{ANSIString, ANSIStrings};
+
+
+/// A value that gets displayed with its escape codes made visible, instead
+/// of being interpreted by the terminal, as returned by the `escaped`
+/// methods on `ANSIString` and `ANSIStrings`.
+///
+/// By default, the escape character is written as `\x1B`, the way it would
+/// be in a Rust string literal, so the output can be pasted into a test.
+/// [`symbols`] writes it as `␛` instead, which is shorter and easier to read
+/// in logs and bug reports.
+///
+/// [`symbols`]: #method.symbols
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::Colour::Red;
+///
+/// let painted = Red.bold().paint("text");
+/// assert_eq!(painted.escaped().to_string(), r"\x1B[1;31mtext\x1B[0m");
+/// assert_eq!(painted.escaped().symbols().to_string(), "␛[1;31mtext␛[0m");
+/// assert_eq!(format!("{:?}", painted.escaped()), r#""\x1B[1;31mtext\x1B[0m""#);
+/// ```
+#[derive(PartialEq, Clone, Copy)]
+pub struct Escaped<T> {
+    value: T,
+    symbols: bool,
+}
+
+impl<T: fmt::Display> Escaped<T> {
+
+    /// Wraps any value that can be displayed, such as a string that has
+    /// already been rendered, so its escape codes get made visible.
+    pub fn new(value: T) -> Escaped<T> {
+        Escaped { value, symbols: false }
+    }
+
+    /// Returns a value that writes the escape character as `␛`.
+    pub fn symbols(self) -> Escaped<T> {
+        Escaped { symbols: true, .. self }
+    }
+}
+
+impl<'a> ANSIString<'a> {
+
+    /// Returns a value that displays this string with its escape codes made
+    /// visible.
+    pub fn escaped(&self) -> Escaped<&ANSIString<'a>> {
+        Escaped::new(self)
+    }
+}
+
+impl<'a> ANSIStrings<'a> {
+
+    /// Returns a value that displays these strings with their escape codes
+    /// made visible.
+    pub fn escaped(&self) -> Escaped<&ANSIStrings<'a>> {
+        Escaped::new(self)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Escaped<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use std::fmt::Write;
+
+        for c in self.value.to_string().chars() {
+            match c {
+                '\x1B' if self.symbols  => f.write_char('␛')?,
+                '\x1B'                  => f.write_str(r"\x1B")?,
+                '\\'                    => f.write_str(r"\\")?,
+                c if c.is_control()     => write!(f, "{}", c.escape_default())?,
+                c                       => f.write_char(c)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Debugging an `Escaped` value writes it in quotes, like a string.
+impl<T: fmt::Display> fmt::Debug for Escaped<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\"{}\"", self)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use style::Colour::*;
+
+    #[test]
+    fn other_control_characters() {
+        assert_eq!(Escaped::new("a\tb\\").to_string(), r"a\tb\\");
+    }
+
+    #[test]
+    fn strings() {
+        let strings = [ Red.paint("a"), Blue.paint("b") ];
+        assert_eq!(ANSIStrings(&strings).escaped().symbols().to_string(), "␛[31ma␛[34mb␛[0m");
+    }
+}
@@ -0,0 +1,73 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+
+use style::Style;
+
+
+/// A function that rewrites styles just before they get rendered, as
+/// installed by [`set_style_filter`].
+///
+/// [`set_style_filter`]: fn.set_style_filter.html
+pub type StyleFilter = Box<dyn Fn(Style) -> Style + Send + Sync>;
+
+static FILTER: RwLock<Option<StyleFilter>> = RwLock::new(None);
+
+/// Whether there’s a filter in `FILTER`, so rendering doesn’t have to take
+/// the lock when there isn’t one.
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+
+/// Installs a filter that every style gets passed through when its codes
+/// are rendered, replacing any filter that was installed before.
+///
+/// This puts policy decisions — such as never making text blink, or only
+/// using colours an old terminal can show — in one place, instead of in
+/// every part of a program that paints something. The filter applies to all
+/// the output of this crate from every thread, whether it’s written through
+/// `Display`, `write_to`, or a style’s `prefix`, `infix`, and `suffix`.
+///
+/// The exception is a [`CompiledStyle`], which renders its prefix when it
+/// gets compiled, so it only uses the filter that was installed at the time.
+///
+/// [`CompiledStyle`]: struct.CompiledStyle.html
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{set_style_filter, clear_style_filter, Style};
+/// use ansi_term::Colour::Red;
+///
+/// set_style_filter(|style| Style { is_blink: false, is_hidden: false, .. style });
+/// assert_eq!(Red.blink().paint("alert").to_string(), "\x1B[31malert\x1B[0m");
+///
+/// clear_style_filter();
+/// assert_eq!(Red.blink().paint("alert").to_string(), "\x1B[5;31malert\x1B[0m");
+/// ```
+pub fn set_style_filter<F>(filter: F)
+where F: Fn(Style) -> Style + Send + Sync + 'static {
+    let mut lock = FILTER.write().unwrap_or_else(|e| e.into_inner());
+    *lock = Some(Box::new(filter));
+    ACTIVE.store(true, Ordering::SeqCst);
+}
+
+/// Removes the filter installed by [`set_style_filter`], if any, so styles
+/// get rendered as they are again.
+///
+/// [`set_style_filter`]: fn.set_style_filter.html
+pub fn clear_style_filter() {
+    let mut lock = FILTER.write().unwrap_or_else(|e| e.into_inner());
+    *lock = None;
+    ACTIVE.store(false, Ordering::SeqCst);
+}
+
+/// Passes the style through the installed filter, if there is one.
+pub(crate) fn filter_style(style: Style) -> Style {
+    if !ACTIVE.load(Ordering::Relaxed) {
+        return style;
+    }
+
+    match *FILTER.read().unwrap_or_else(|e| e.into_inner()) {
+        Some(ref filter)  => filter(style),
+        None              => style,
+    }
+}
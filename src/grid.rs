@@ -0,0 +1,172 @@
+use display::ANSIStrings;
+use util::visible_width;
+
+
+/// Which way the items in a grid run.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Debug)]
+pub enum Direction {
+
+    /// Down each column, then on to the next one, as `ls` does.
+    #[default]
+    TopToBottom,
+
+    /// Along each row, then on to the next one, as `ls -x` does.
+    LeftToRight,
+}
+
+/// A list of styled items, such as file names, to be laid out in as many
+/// columns as fit in the width of the terminal, the way `ls` does it.
+///
+/// Column widths are worked out from the *visible* width of each item, so
+/// escape codes never throw the columns out of line. (Enable the
+/// `unicode-width` feature to have wide characters measured correctly too.)
+/// If the widest item is wider than the terminal, the items get put in one
+/// column.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{ANSIStrings, Grid};
+/// use ansi_term::Colour::{Blue, Green};
+///
+/// let names = [ Blue.paint("src"), Green.paint("build.sh"), "README".into(), "LICENCE".into() ];
+///
+/// let mut grid = Grid::new();
+/// for name in names.chunks(1) {
+///     grid.add(ANSIStrings(name));
+/// }
+///
+/// assert_eq!(grid.lines(20), vec![
+///     "\x1B[34msrc\x1B[0m       README",
+///     "\x1B[32mbuild.sh\x1B[0m  LICENCE",
+/// ]);
+/// ```
+#[derive(Default, Debug)]
+pub struct Grid<'a> {
+    items: Vec<ANSIStrings<'a>>,
+    direction: Direction,
+    spacing: usize,
+}
+
+impl<'a> Grid<'a> {
+
+    /// Creates a new grid with no items that runs from top to bottom, with
+    /// two spaces between its columns.
+    pub fn new() -> Grid<'a> {
+        Grid { spacing: 2, .. Grid::default() }
+    }
+
+    /// Returns a grid that runs in the given direction.
+    pub fn direction(self, direction: Direction) -> Grid<'a> {
+        Grid { direction, .. self }
+    }
+
+    /// Returns a grid with the given number of spaces between its columns.
+    pub fn spacing(self, spacing: usize) -> Grid<'a> {
+        Grid { spacing, .. self }
+    }
+
+    /// Adds an item to the end of the grid.
+    pub fn add(&mut self, item: ANSIStrings<'a>) {
+        self.items.push(item);
+    }
+
+    /// Returns the lines of the grid with as many columns as fit in the
+    /// given width, with each item padded to the width of its column. The
+    /// items in the last column don’t get padded, and the lines don’t have
+    /// newlines at the end.
+    pub fn lines(&self, width: usize) -> Vec<String> {
+        let widths: Vec<usize> = self.items.iter().map(visible_width).collect();
+        let (rows, column_widths) = self.layout(&widths, width);
+        let columns = column_widths.len();
+        let mut lines = Vec::with_capacity(rows);
+
+        for row in 0 .. rows {
+            let mut line = String::new();
+            let cells: Vec<usize> = (0 .. columns).filter_map(|column| self.index(row, column, rows, columns)).collect();
+
+            for (column, &index) in cells.iter().enumerate() {
+                line.push_str(&self.items[index].to_string());
+
+                if column + 1 < cells.len() {
+                    let padding = column_widths[column] - widths[index] + self.spacing;
+                    line.extend((0 .. padding).map(|_| ' '));
+                }
+            }
+
+            lines.push(line);
+        }
+
+        lines
+    }
+
+    /// Works out the fewest rows that the items fit in, returning the
+    /// number of rows and the width of each column.
+    fn layout(&self, widths: &[usize], width: usize) -> (usize, Vec<usize>) {
+        let count = widths.len();
+
+        for rows in 1 ..= count {
+            let columns = count.div_ceil(rows);
+            let mut column_widths = vec![ 0; columns ];
+
+            for row in 0 .. rows {
+                for (column, column_width) in column_widths.iter_mut().enumerate() {
+                    if let Some(index) = self.index(row, column, rows, columns) {
+                        *column_width = (*column_width).max(widths[index]);
+                    }
+                }
+            }
+
+            let total = column_widths.iter().sum::<usize>() + self.spacing * (columns - 1);
+            if total <= width || rows == count {
+                return (rows, column_widths);
+            }
+        }
+
+        (0, Vec::new())
+    }
+
+    /// Returns the index of the item at the given row and column, if there
+    /// is one there.
+    fn index(&self, row: usize, column: usize, rows: usize, columns: usize) -> Option<usize> {
+        let index = match self.direction {
+            Direction::TopToBottom  => column * rows + row,
+            Direction::LeftToRight  => row * columns + column,
+        };
+
+        if index < self.items.len() { Some(index) } else { None }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use display::ANSIString;
+
+    fn grid<'a>(items: &'a [ANSIString<'a>], direction: Direction) -> Grid<'a> {
+        let mut grid = Grid::new().direction(direction).spacing(1);
+        for item in items.chunks(1) {
+            grid.add(ANSIStrings(item));
+        }
+        grid
+    }
+
+    #[test]
+    fn left_to_right() {
+        let items: Vec<ANSIString> = [ "a", "bb", "c", "d", "e" ].iter().map(|&i| i.into()).collect();
+        assert_eq!(grid(&items, Direction::LeftToRight).lines(8), vec![ "a bb c", "d e" ]);
+    }
+
+    #[test]
+    fn too_wide_for_one_row() {
+        let items: Vec<ANSIString> = [ "abcdef", "b", "c" ].iter().map(|&i| i.into()).collect();
+        assert_eq!(grid(&items, Direction::TopToBottom).lines(4), vec![ "abcdef", "b", "c" ]);
+        assert_eq!(grid(&items, Direction::TopToBottom).lines(8), vec![ "abcdef c", "b" ]);
+    }
+
+    #[test]
+    fn no_items() {
+        assert!(grid(&[], Direction::TopToBottom).lines(80).is_empty());
+    }
+}
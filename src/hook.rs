@@ -0,0 +1,127 @@
+use std::cell::Cell;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+
+use compiled::CodeBuffer;
+use style::Style;
+
+
+/// A function that gets called with every escape code the crate writes, as
+/// installed by [`set_escape_code_hook`].
+///
+/// [`set_escape_code_hook`]: fn.set_escape_code_hook.html
+pub type EscapeCodeHook = Box<dyn Fn(&str, Style) + Send + Sync>;
+
+static HOOK: RwLock<Option<EscapeCodeHook>> = RwLock::new(None);
+
+/// Whether there’s a hook in `HOOK`, so rendering doesn’t have to take the
+/// lock when there isn’t one.
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    /// Whether the hook is running on this thread, so that a hook that
+    /// paints something itself doesn’t get called for its own codes.
+    static IN_HOOK: Cell<bool> = const { Cell::new(false) };
+}
+
+
+/// Installs a function that gets called with every escape code that gets
+/// rendered from a style, along with the style it came from, replacing any
+/// hook that was installed before.
+///
+/// This is for tracking down where an unexpected code came from in a large
+/// program: the hook can log the code, or capture a backtrace when it sees
+/// the one it’s looking for. It gets called for the prefixes, infixes, and
+/// suffixes of every style, including the ones written between the strings
+/// in an `ANSIStrings`, but not for codes that don’t do anything. For an
+/// infix, the style is the one being changed to; for a suffix, it’s the one
+/// being reset.
+///
+/// Hooks slow rendering down, so they’re best left out of release builds.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::{Arc, Mutex};
+/// use ansi_term::{set_escape_code_hook, clear_escape_code_hook};
+/// use ansi_term::Colour::Red;
+///
+/// let codes = Arc::new(Mutex::new(Vec::new()));
+/// let seen = Arc::clone(&codes);
+/// set_escape_code_hook(move |code, style| seen.lock().unwrap().push((code.to_string(), style)));
+///
+/// let _ = Red.paint("oops").to_string();
+/// clear_escape_code_hook();
+///
+/// assert_eq!(*codes.lock().unwrap(), vec![
+///     ("\x1B[31m".to_string(), Red.normal()),
+///     ("\x1B[0m".to_string(), Red.normal()),
+/// ]);
+/// ```
+pub fn set_escape_code_hook<F>(hook: F)
+where F: Fn(&str, Style) + Send + Sync + 'static {
+    let mut lock = HOOK.write().unwrap_or_else(|e| e.into_inner());
+    *lock = Some(Box::new(hook));
+    ACTIVE.store(true, Ordering::SeqCst);
+}
+
+/// Removes the hook installed by [`set_escape_code_hook`], if any.
+///
+/// [`set_escape_code_hook`]: fn.set_escape_code_hook.html
+pub fn clear_escape_code_hook() {
+    let mut lock = HOOK.write().unwrap_or_else(|e| e.into_inner());
+    *lock = None;
+    ACTIVE.store(false, Ordering::SeqCst);
+}
+
+/// Installs a hook that logs every escape code the crate writes, with the
+/// style it came from, as a trace message with the target `ansi_term`.
+///
+/// This function is only available with the `log` feature.
+#[cfg(feature = "log")]
+pub fn log_escape_codes() {
+    use escaped::Escaped;
+
+    set_escape_code_hook(|code, style| {
+        log::trace!(target: "ansi_term", "wrote {} for {:?}", Escaped::new(code), style);
+    });
+}
+
+/// Writes a code with the given function, passing it to the installed hook
+/// on the way if there is one.
+pub(crate) fn write_code<F>(f: &mut dyn fmt::Write, style: Style, write: F) -> fmt::Result
+where F: Fn(&mut dyn fmt::Write) -> fmt::Result {
+    if !ACTIVE.load(Ordering::Relaxed) || IN_HOOK.with(Cell::get) {
+        return write(f);
+    }
+
+    let mut code = CodeBuffer::new();
+    write(&mut code)?;
+
+    if !code.is_empty() {
+        if let Some(ref hook) = *HOOK.read().unwrap_or_else(|e| e.into_inner()) {
+            let _guard = InHook::enter();
+            hook(code.as_str(), style);
+        }
+    }
+
+    f.write_str(code.as_str())
+}
+
+/// Marks the hook as running on this thread until it gets dropped, so the
+/// mark gets removed even if the hook panics.
+struct InHook;
+
+impl InHook {
+    fn enter() -> InHook {
+        IN_HOOK.with(|h| h.set(true));
+        InHook
+    }
+}
+
+impl Drop for InHook {
+    fn drop(&mut self) {
+        IN_HOOK.with(|h| h.set(false));
+    }
+}
@@ -0,0 +1,183 @@
+use std::fmt;
+
+use control::OscTerminator;
+
+
+/// How big an inline image should be drawn, in one direction.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum ImageSize {
+
+    /// The terminal picks the size, based on the image’s own size.
+    #[default]
+    Auto,
+
+    /// A number of character cells.
+    Cells(u32),
+
+    /// A number of pixels.
+    Pixels(u32),
+
+    /// A percentage of the width or height of the terminal’s window.
+    Percent(u32),
+}
+
+impl fmt::Display for ImageSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ImageSize::Auto        => f.write_str("auto"),
+            ImageSize::Cells(n)    => write!(f, "{}", n),
+            ImageSize::Pixels(n)   => write!(f, "{}px", n),
+            ImageSize::Percent(n)  => write!(f, "{}%", n),
+        }
+    }
+}
+
+
+/// A value that shows an image in the terminal when it gets displayed,
+/// using the `OSC 1337 File=` sequence that iTerm2 came up with, and
+/// WezTerm and some others also understand.
+///
+/// The image gets drawn at the cursor, and the cursor moves past it, so it
+/// can go in the middle of other styled output. The image data can be in any
+/// format the terminal can read, such as PNG, JPEG, or GIF; it gets encoded
+/// as base64 while it’s being written, without being copied.
+///
+/// Terminals that don’t understand the sequence should ignore it, but may
+/// leave an empty space or, in the worst case, print the data.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{inline_image, ImageSize};
+///
+/// let image = inline_image(b"GIF89a").name("tiny.gif").width(ImageSize::Cells(4));
+/// assert_eq!(image.to_string(),
+///            "\x1B]1337;File=name=dGlueS5naWY=;size=6;width=4;inline=1:R0lGODlh\x07");
+/// ```
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct InlineImage<'a> {
+    data: &'a [u8],
+    name: Option<&'a str>,
+    width: ImageSize,
+    height: ImageSize,
+    preserve_aspect_ratio: bool,
+    terminator: OscTerminator,
+}
+
+/// Returns a value that shows the image with the given data inline when it
+/// gets displayed.
+pub fn inline_image<'a>(data: &'a [u8]) -> InlineImage<'a> {
+    InlineImage {
+        data,
+        name: None,
+        width: ImageSize::Auto,
+        height: ImageSize::Auto,
+        preserve_aspect_ratio: true,
+        terminator: OscTerminator::default(),
+    }
+}
+
+impl<'a> InlineImage<'a> {
+
+    /// Returns an image with the given file name, which some terminals show
+    /// or use when it gets saved.
+    pub fn name(self, name: &'a str) -> InlineImage<'a> {
+        InlineImage { name: Some(name), .. self }
+    }
+
+    /// Returns an image drawn with the given width.
+    pub fn width(self, width: ImageSize) -> InlineImage<'a> {
+        InlineImage { width, .. self }
+    }
+
+    /// Returns an image drawn with the given height.
+    pub fn height(self, height: ImageSize) -> InlineImage<'a> {
+        InlineImage { height, .. self }
+    }
+
+    /// Returns an image that gets stretched to fill the width and height it
+    /// was given, instead of keeping its proportions, which it does unless
+    /// this is called with `false`.
+    pub fn preserve_aspect_ratio(self, preserve: bool) -> InlineImage<'a> {
+        InlineImage { preserve_aspect_ratio: preserve, .. self }
+    }
+
+    /// Returns an image that ends with the given terminator instead.
+    pub fn terminated_by(self, terminator: OscTerminator) -> InlineImage<'a> {
+        InlineImage { terminator, .. self }
+    }
+}
+
+impl<'a> fmt::Display for InlineImage<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("\x1B]1337;File=")?;
+
+        if let Some(name) = self.name {
+            f.write_str("name=")?;
+            write_base64(f, name.as_bytes())?;
+            f.write_str(";")?;
+        }
+
+        write!(f, "size={}", self.data.len())?;
+        if self.width != ImageSize::Auto   { write!(f, ";width={}", self.width)? }
+        if self.height != ImageSize::Auto  { write!(f, ";height={}", self.height)? }
+        if !self.preserve_aspect_ratio     { f.write_str(";preserveAspectRatio=0")? }
+
+        f.write_str(";inline=1:")?;
+        write_base64(f, self.data)?;
+        write!(f, "{}", self.terminator)
+    }
+}
+
+/// Writes the bytes encoded as standard base64, with padding.
+fn write_base64(f: &mut fmt::Formatter, bytes: &[u8]) -> fmt::Result {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0_u32, |n, (i, &b)| n | u32::from(b) << (16 - 8 * i));
+
+        let mut quad = [b'='; 4];
+        for (i, out) in quad.iter_mut().enumerate().take(chunk.len() + 1) {
+            *out = ALPHABET[(n >> (18 - 6 * i) & 0x3F) as usize];
+        }
+
+        f.write_str(std::str::from_utf8(&quad).expect("base64 is ASCII"))?;
+    }
+
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Base64<'a>(&'a [u8]);
+
+    impl<'a> fmt::Display for Base64<'a> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write_base64(f, self.0)
+        }
+    }
+
+    #[test]
+    fn base64_padding() {
+        assert_eq!(Base64(b"").to_string(), "");
+        assert_eq!(Base64(b"f").to_string(), "Zg==");
+        assert_eq!(Base64(b"fo").to_string(), "Zm8=");
+        assert_eq!(Base64(b"foo").to_string(), "Zm9v");
+        assert_eq!(Base64(b"foob").to_string(), "Zm9vYg==");
+    }
+
+    #[test]
+    fn every_option() {
+        let image = inline_image(b"x")
+            .height(ImageSize::Percent(50))
+            .width(ImageSize::Pixels(10))
+            .preserve_aspect_ratio(false)
+            .terminated_by(OscTerminator::StringTerminator);
+
+        assert_eq!(image.to_string(),
+                   "\x1B]1337;File=size=1;width=10px;height=50%;preserveAspectRatio=0;inline=1:eA==\x1B\\");
+    }
+}
@@ -0,0 +1,224 @@
+use std::convert::TryFrom;
+
+use crossterm::style::{Attribute, Attributes, Color, ContentStyle};
+
+use style::{Colour, Style};
+
+
+impl From<Colour> for Color {
+
+    /// Converts a `Colour` into the equivalent crossterm `Color`.
+    ///
+    /// crossterm names the eight standard colours after their *bright*
+    /// versions, so `Colour::Red` becomes `Color::DarkRed`, and
+    /// `Colour::White` becomes `Color::Grey`.
+    ///
+    /// ```
+    /// extern crate crossterm;
+    /// use ansi_term::Colour;
+    /// use crossterm::style::Color;
+    ///
+    /// assert_eq!(Color::DarkRed, Color::from(Colour::Red));
+    /// assert_eq!(Color::AnsiValue(9), Colour::Fixed(9).into());
+    /// ```
+    fn from(colour: Colour) -> Color {
+        match colour {
+            Colour::Black       => Color::Black,
+            Colour::Red         => Color::DarkRed,
+            Colour::Green       => Color::DarkGreen,
+            Colour::Yellow      => Color::DarkYellow,
+            Colour::Blue        => Color::DarkBlue,
+            Colour::Purple      => Color::DarkMagenta,
+            Colour::Cyan        => Color::DarkCyan,
+            Colour::White       => Color::Grey,
+            Colour::Fixed(num)  => Color::AnsiValue(num),
+            Colour::RGB(r,g,b)  => Color::Rgb { r, g, b },
+            #[cfg(feature = "cmyk")]
+            Colour::CMY(..) | Colour::CMYK(..) => Color::from(colour.to_rgb()),
+        }
+    }
+}
+
+impl TryFrom<Color> for Colour {
+    type Error = Color;
+
+    /// Converts a crossterm `Color` into a `Colour`, with the bright colours
+    /// becoming their numbers in the 256-colour palette.
+    ///
+    /// `Color::Reset` has no equivalent, as it means “no colour” to a
+    /// `Style`, and gets returned as the error.
+    ///
+    /// ```
+    /// extern crate crossterm;
+    /// use std::convert::TryFrom;
+    /// use ansi_term::Colour;
+    /// use crossterm::style::Color;
+    ///
+    /// assert_eq!(Ok(Colour::Red), Colour::try_from(Color::DarkRed));
+    /// assert_eq!(Ok(Colour::Fixed(9)), Colour::try_from(Color::Red));
+    /// assert_eq!(Err(Color::Reset), Colour::try_from(Color::Reset));
+    /// ```
+    fn try_from(color: Color) -> Result<Colour, Color> {
+        Ok(match color {
+            Color::Black               => Colour::Black,
+            Color::DarkRed             => Colour::Red,
+            Color::DarkGreen           => Colour::Green,
+            Color::DarkYellow          => Colour::Yellow,
+            Color::DarkBlue            => Colour::Blue,
+            Color::DarkMagenta         => Colour::Purple,
+            Color::DarkCyan            => Colour::Cyan,
+            Color::Grey                => Colour::White,
+            Color::DarkGrey            => Colour::Fixed(8),
+            Color::Red                 => Colour::Fixed(9),
+            Color::Green               => Colour::Fixed(10),
+            Color::Yellow              => Colour::Fixed(11),
+            Color::Blue                => Colour::Fixed(12),
+            Color::Magenta             => Colour::Fixed(13),
+            Color::Cyan                => Colour::Fixed(14),
+            Color::White               => Colour::Fixed(15),
+            Color::AnsiValue(num)      => Colour::Fixed(num),
+            Color::Rgb { r, g, b }     => Colour::RGB(r, g, b),
+            Color::Reset               => return Err(color),
+        })
+    }
+}
+
+
+impl From<Style> for Attributes {
+
+    /// Converts the text properties of a `Style` into a set of crossterm
+    /// `Attributes`, ignoring its colours.
+    ///
+    /// ```
+    /// extern crate crossterm;
+    /// use ansi_term::Style;
+    /// use crossterm::style::{Attribute, Attributes};
+    ///
+    /// let attributes = Attributes::from(Style::new().bold().underline());
+    /// assert!(attributes.has(Attribute::Bold));
+    /// assert!(attributes.has(Attribute::Underlined));
+    /// ```
+    fn from(style: Style) -> Attributes {
+        let mut attributes = Attributes::default();
+
+        if style.is_bold           { attributes.set(Attribute::Bold) }
+        if style.is_dimmed         { attributes.set(Attribute::Dim) }
+        if style.is_italic         { attributes.set(Attribute::Italic) }
+        if style.is_underline      { attributes.set(Attribute::Underlined) }
+        if style.is_blink          { attributes.set(Attribute::SlowBlink) }
+        if style.is_reverse        { attributes.set(Attribute::Reverse) }
+        if style.is_hidden         { attributes.set(Attribute::Hidden) }
+        if style.is_strikethrough  { attributes.set(Attribute::CrossedOut) }
+
+        attributes
+    }
+}
+
+impl From<Attributes> for Style {
+
+    /// Converts a set of crossterm `Attributes` into a `Style` with no
+    /// colours. Every kind of underline becomes a plain underline, and both
+    /// blinking speeds become blinking.
+    ///
+    /// ```
+    /// extern crate crossterm;
+    /// use ansi_term::Style;
+    /// use crossterm::style::{Attribute, Attributes};
+    ///
+    /// let attributes = Attributes::from(Attribute::Undercurled) | Attribute::Italic;
+    /// assert_eq!(Style::new().italic().underline(), Style::from(attributes));
+    /// ```
+    fn from(attributes: Attributes) -> Style {
+        let has_any = |list: &[Attribute]| list.iter().any(|a| attributes.has(*a));
+
+        Style {
+            is_bold:           attributes.has(Attribute::Bold),
+            is_dimmed:         attributes.has(Attribute::Dim),
+            is_italic:         attributes.has(Attribute::Italic),
+            is_underline:      has_any(&[ Attribute::Underlined, Attribute::DoubleUnderlined, Attribute::Undercurled,
+                                          Attribute::Underdotted, Attribute::Underdashed ]),
+            is_blink:          has_any(&[ Attribute::SlowBlink, Attribute::RapidBlink ]),
+            is_reverse:        attributes.has(Attribute::Reverse),
+            is_hidden:         attributes.has(Attribute::Hidden),
+            is_strikethrough:  attributes.has(Attribute::CrossedOut),
+            .. Style::default()
+        }
+    }
+}
+
+
+impl From<Style> for ContentStyle {
+
+    /// Converts a `Style` into a crossterm `ContentStyle`.
+    ///
+    /// ```
+    /// extern crate crossterm;
+    /// use ansi_term::Colour::{Blue, Yellow};
+    /// use crossterm::style::{Color, ContentStyle};
+    ///
+    /// let style = ContentStyle::from(Yellow.on(Blue));
+    /// assert_eq!(Some(Color::DarkYellow), style.foreground_color);
+    /// assert_eq!(Some(Color::DarkBlue), style.background_color);
+    /// ```
+    fn from(style: Style) -> ContentStyle {
+        ContentStyle {
+            foreground_color:  style.foreground.map(Color::from),
+            background_color:  style.background.map(Color::from),
+            underline_color:   None,
+            attributes:        Attributes::from(style),
+        }
+    }
+}
+
+impl From<ContentStyle> for Style {
+
+    /// Converts a crossterm `ContentStyle` into a `Style`. The underline
+    /// colour has no equivalent, so it gets left out.
+    ///
+    /// ```
+    /// extern crate crossterm;
+    /// use ansi_term::{Colour, Style};
+    /// use crossterm::style::{Attribute, Color, ContentStyle};
+    ///
+    /// let mut style = ContentStyle::new();
+    /// style.foreground_color = Some(Color::Red);
+    /// style.attributes.set(Attribute::Bold);
+    /// assert_eq!(Colour::Fixed(9).bold(), Style::from(style));
+    /// ```
+    fn from(content: ContentStyle) -> Style {
+        Style {
+            foreground:  content.foreground_color.and_then(|c| Colour::try_from(c).ok()),
+            background:  content.background_color.and_then(|c| Colour::try_from(c).ok()),
+            .. Style::from(content.attributes)
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::convert::TryFrom;
+
+    use crossterm::style::{Color, ContentStyle};
+    use style::{Colour, Style};
+
+    #[test]
+    fn colour_round_trip() {
+        for colour in &[ Colour::Black, Colour::Purple, Colour::White, Colour::Fixed(9), Colour::RGB(1, 2, 3) ] {
+            assert_eq!(Ok(*colour), Colour::try_from(Color::from(*colour)));
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let style = Colour::Fixed(200).on(Colour::RGB(1, 2, 3)).blink().reverse().hidden();
+        assert_eq!(style, Style::from(ContentStyle::from(style)));
+    }
+
+    #[test]
+    fn reset_is_no_colour() {
+        let mut content = ContentStyle::new();
+        content.background_color = Some(Color::Reset);
+        assert_eq!(Style::default(), Style::from(content));
+    }
+}
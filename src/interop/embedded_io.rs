@@ -0,0 +1,140 @@
+use std::fmt;
+
+use embedded_io::{Write, WriteFmtError};
+
+use display::{ANSIString, ANSIStrings, ANSIByteString, ANSIByteStrings};
+use write::AnyWrite;
+
+
+/// Lets the crate’s writers write text to an `embedded_io::Write`.
+struct TextWriter<'w, W: 'w + ?Sized>(&'w mut W);
+
+/// Lets the crate’s writers write bytes to an `embedded_io::Write`.
+struct ByteWriter<'w, W: 'w + ?Sized>(&'w mut W);
+
+/// The only errors from formatting come from the escape codes, which never
+/// fail, so anything else is the writer’s.
+fn write_fmt<W: Write + ?Sized>(w: &mut W, fmt: fmt::Arguments) -> Result<(), W::Error> {
+    match w.write_fmt(fmt) {
+        Ok(())                           => Ok(()),
+        Err(WriteFmtError::Other(e))     => Err(e),
+        Err(WriteFmtError::FmtError)     => panic!("formatting an escape code failed"),
+    }
+}
+
+impl<'w, W: Write + ?Sized> AnyWrite for TextWriter<'w, W> {
+    type wstr = str;
+    type Error = W::Error;
+
+    fn write_fmt(&mut self, fmt: fmt::Arguments) -> Result<(), Self::Error> {
+        write_fmt(self.0, fmt)
+    }
+
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.0.write_all(s.as_bytes())
+    }
+}
+
+impl<'w, W: Write + ?Sized> AnyWrite for ByteWriter<'w, W> {
+    type wstr = [u8];
+    type Error = W::Error;
+
+    fn write_fmt(&mut self, fmt: fmt::Arguments) -> Result<(), Self::Error> {
+        write_fmt(self.0, fmt)
+    }
+
+    fn write_str(&mut self, s: &[u8]) -> Result<(), Self::Error> {
+        self.0.write_all(s)
+    }
+}
+
+
+impl<'a> ANSIString<'a> {
+
+    /// Writes this string, with the escape codes for its style around it,
+    /// to an `embedded_io::Write`, such as a UART or RTT channel.
+    ///
+    /// This method is only available with the `embedded-io` feature.
+    ///
+    /// ```
+    /// extern crate embedded_io;
+    /// use ansi_term::Colour::Red;
+    ///
+    /// let mut buf = [0_u8; 16];
+    /// let mut out = &mut buf[..];
+    /// Red.paint("fault").write_to_embedded(&mut out).unwrap();
+    /// assert_eq!(&buf[.. 14], b"\x1B[31mfault\x1B[0m");
+    /// ```
+    pub fn write_to_embedded<W: Write + ?Sized>(&self, w: &mut W) -> Result<(), W::Error> {
+        self.write_to_any(&mut TextWriter(w))
+    }
+}
+
+impl<'a> ANSIStrings<'a> {
+
+    /// Writes these strings, with the minimal escape codes between them, to
+    /// an `embedded_io::Write`.
+    ///
+    /// This method is only available with the `embedded-io` feature.
+    pub fn write_to_embedded<W: Write + ?Sized>(&self, w: &mut W) -> Result<(), W::Error> {
+        self.write_to_any(&mut TextWriter(w))
+    }
+}
+
+impl<'a> ANSIByteString<'a> {
+
+    /// Writes these bytes, with the escape codes for their style around
+    /// them, to an `embedded_io::Write`.
+    ///
+    /// This method is only available with the `embedded-io` feature.
+    pub fn write_to_embedded<W: Write + ?Sized>(&self, w: &mut W) -> Result<(), W::Error> {
+        self.write_to_any(&mut ByteWriter(w))
+    }
+}
+
+impl<'a> ANSIByteStrings<'a> {
+
+    /// Writes these byte strings, with the minimal escape codes between
+    /// them, to an `embedded_io::Write`.
+    ///
+    /// This method is only available with the `embedded-io` feature.
+    pub fn write_to_embedded<W: Write + ?Sized>(&self, w: &mut W) -> Result<(), W::Error> {
+        self.write_to_any(&mut ByteWriter(w))
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use embedded_io::SliceWriteError;
+
+    use display::{ANSIStrings, ANSIByteStrings};
+    use style::Colour::*;
+
+    #[test]
+    fn strings() {
+        let mut buf = [0_u8; 32];
+        let mut out = &mut buf[..];
+        ANSIStrings(&[ Red.paint("a"), Blue.paint("b") ]).write_to_embedded(&mut out).unwrap();
+
+        let left = out.len();
+        assert_eq!(&buf[.. 32 - left], b"\x1B[31ma\x1B[34mb\x1B[0m");
+    }
+
+    #[test]
+    fn byte_strings() {
+        let mut buf = [0_u8; 32];
+        let mut out = &mut buf[..];
+        ANSIByteStrings(&[ Red.paint(&b"a"[..]), Red.bold().paint(&b"b"[..]) ]).write_to_embedded(&mut out).unwrap();
+
+        let left = out.len();
+        assert_eq!(&buf[.. 32 - left], b"\x1B[31ma\x1B[1mb\x1B[0m");
+    }
+
+    #[test]
+    fn full_writer() {
+        let mut buf = [0_u8; 4];
+        let mut out = &mut buf[..];
+        assert_eq!(Red.paint("too long").write_to_embedded(&mut out), Err(SliceWriteError::Full));
+    }
+}
@@ -0,0 +1,72 @@
+use std::fmt;
+
+use log::{Level, Record};
+
+use logging::{LogLevel, LogTheme};
+
+
+impl From<Level> for LogLevel {
+    fn from(level: Level) -> LogLevel {
+        match level {
+            Level::Error  => LogLevel::Error,
+            Level::Warn   => LogLevel::Warn,
+            Level::Info   => LogLevel::Info,
+            Level::Debug  => LogLevel::Debug,
+            Level::Trace  => LogLevel::Trace,
+        }
+    }
+}
+
+
+/// A `log` record painted with a `LogTheme`, as returned by
+/// [`LogTheme::paint_record`].
+///
+/// This type implements the `Display` trait, writing the level, target, and
+/// message on one line, such as `ERROR app::db: connection lost`. The level
+/// is padded to five characters so that the messages line up.
+///
+/// [`LogTheme::paint_record`]: struct.LogTheme.html#method.paint_record
+#[derive(Clone, Copy, Debug)]
+pub struct PaintedRecord<'a> {
+    theme: &'a LogTheme,
+    record: &'a Record<'a>,
+}
+
+impl LogTheme {
+
+    /// Paints a `log` record with this theme. This is meant to be used from
+    /// the format function of a logger such as `env_logger` or `fern`:
+    ///
+    /// ```
+    /// extern crate log;
+    /// use ansi_term::LogTheme;
+    /// use log::{Level, Record};
+    ///
+    /// let theme = LogTheme::default();
+    /// let record = Record::builder()
+    ///     .level(Level::Warn)
+    ///     .target("app::db")
+    ///     .args(format_args!("slow query"))
+    ///     .build();
+    ///
+    /// assert_eq!("\x1B[33mWARN \x1B[0m \x1B[2mapp::db\x1B[0m: slow query",
+    ///            theme.paint_record(&record).to_string());
+    /// ```
+    pub fn paint_record<'a>(&'a self, record: &'a Record<'a>) -> PaintedRecord<'a> {
+        PaintedRecord { theme: self, record }
+    }
+}
+
+impl<'a> fmt::Display for PaintedRecord<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let level = LogLevel::from(self.record.level());
+        let level_style = self.theme.level(level);
+        write!(f, "{}{:<5}{} ", level_style.prefix(), level.name(), level_style.suffix())?;
+
+        let target = self.theme.target;
+        write!(f, "{}{}{}: ", target.prefix(), self.record.target(), target.suffix())?;
+
+        let message = self.theme.message;
+        write!(f, "{}{}{}", message.prefix(), self.record.args(), message.suffix())
+    }
+}
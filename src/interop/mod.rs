@@ -0,0 +1,33 @@
+//! Conversions between this crate’s types and those of other crates, and
+//! helpers for using them together. Each one is behind a feature with the
+//! same name as the crate it works with.
+
+#[cfg(feature = "termcolor")]
+mod termcolor;
+
+#[cfg(feature = "crossterm")]
+mod crossterm;
+
+#[cfg(feature = "nu-ansi-term")]
+mod nu_ansi_term;
+
+#[cfg(feature = "owo-colors")]
+mod owo_colors;
+
+#[cfg(feature = "syntect")]
+mod syntect;
+#[cfg(feature = "syntect")]
+pub use self::syntect::*;
+
+#[cfg(feature = "embedded-io")]
+mod embedded_io;
+
+#[cfg(feature = "log")]
+mod log;
+#[cfg(feature = "log")]
+pub use self::log::*;
+
+#[cfg(feature = "tracing")]
+mod tracing;
+#[cfg(feature = "tracing")]
+pub use self::tracing::*;
@@ -0,0 +1,155 @@
+use std::convert::TryFrom;
+
+use nu_ansi_term::{Color, Style as NuStyle};
+
+use style::{Colour, Style};
+
+
+impl From<Colour> for Color {
+
+    /// Converts a `Colour` into the equivalent nu-ansi-term `Color`.
+    ///
+    /// ```
+    /// extern crate nu_ansi_term;
+    /// use ansi_term::Colour;
+    /// use nu_ansi_term::Color;
+    ///
+    /// assert_eq!(Color::Purple, Color::from(Colour::Purple));
+    /// assert_eq!(Color::Fixed(10), Colour::Fixed(10).into());
+    /// ```
+    fn from(colour: Colour) -> Color {
+        match colour {
+            Colour::Black       => Color::Black,
+            Colour::Red         => Color::Red,
+            Colour::Green       => Color::Green,
+            Colour::Yellow      => Color::Yellow,
+            Colour::Blue        => Color::Blue,
+            Colour::Purple      => Color::Purple,
+            Colour::Cyan        => Color::Cyan,
+            Colour::White       => Color::White,
+            Colour::Fixed(num)  => Color::Fixed(num),
+            Colour::RGB(r,g,b)  => Color::Rgb(r, g, b),
+            #[cfg(feature = "cmyk")]
+            Colour::CMY(..) | Colour::CMYK(..) => Color::from(colour.to_rgb()),
+        }
+    }
+}
+
+impl TryFrom<Color> for Colour {
+    type Error = Color;
+
+    /// Converts a nu-ansi-term `Color` into a `Colour`, with the light
+    /// colours becoming their numbers in the 256-colour palette.
+    ///
+    /// `Color::Default` has no equivalent, and gets returned as the error.
+    ///
+    /// ```
+    /// extern crate nu_ansi_term;
+    /// use std::convert::TryFrom;
+    /// use ansi_term::Colour;
+    /// use nu_ansi_term::Color;
+    ///
+    /// assert_eq!(Ok(Colour::Fixed(9)), Colour::try_from(Color::LightRed));
+    /// assert_eq!(Err(Color::Default), Colour::try_from(Color::Default));
+    /// ```
+    fn try_from(color: Color) -> Result<Colour, Color> {
+        Ok(match color {
+            Color::Black                          => Colour::Black,
+            Color::Red                            => Colour::Red,
+            Color::Green                          => Colour::Green,
+            Color::Yellow                         => Colour::Yellow,
+            Color::Blue                           => Colour::Blue,
+            Color::Purple | Color::Magenta        => Colour::Purple,
+            Color::Cyan                           => Colour::Cyan,
+            Color::White                          => Colour::White,
+            Color::DarkGray                       => Colour::Fixed(8),
+            Color::LightRed                       => Colour::Fixed(9),
+            Color::LightGreen                     => Colour::Fixed(10),
+            Color::LightYellow                    => Colour::Fixed(11),
+            Color::LightBlue                      => Colour::Fixed(12),
+            Color::LightPurple | Color::LightMagenta  => Colour::Fixed(13),
+            Color::LightCyan                      => Colour::Fixed(14),
+            Color::LightGray                      => Colour::Fixed(15),
+            Color::Fixed(num)                     => Colour::Fixed(num),
+            Color::Rgb(r, g, b)                   => Colour::RGB(r, g, b),
+            Color::Default                        => return Err(color),
+        })
+    }
+}
+
+
+impl From<Style> for NuStyle {
+
+    /// Converts a `Style` into the equivalent nu-ansi-term `Style`.
+    ///
+    /// ```
+    /// extern crate nu_ansi_term;
+    /// use ansi_term::Colour::Red;
+    ///
+    /// let style = nu_ansi_term::Style::from(Red.bold());
+    /// assert_eq!(nu_ansi_term::Color::Red.bold(), style);
+    /// ```
+    fn from(style: Style) -> NuStyle {
+        NuStyle {
+            foreground:        style.foreground.map(Color::from),
+            background:        style.background.map(Color::from),
+            is_bold:           style.is_bold,
+            is_dimmed:         style.is_dimmed,
+            is_italic:         style.is_italic,
+            is_underline:      style.is_underline,
+            is_blink:          style.is_blink,
+            is_reverse:        style.is_reverse,
+            is_hidden:         style.is_hidden,
+            is_strikethrough:  style.is_strikethrough,
+            prefix_with_reset: false,
+        }
+    }
+}
+
+impl From<NuStyle> for Style {
+
+    /// Converts a nu-ansi-term `Style` into a `Style`.
+    ///
+    /// The default colour becomes no colour at all, and the
+    /// `prefix_with_reset` setting gets ignored.
+    ///
+    /// ```
+    /// extern crate nu_ansi_term;
+    /// use ansi_term::{Colour, Style};
+    ///
+    /// let style = nu_ansi_term::Color::LightBlue.underline();
+    /// assert_eq!(Colour::Fixed(12).underline(), Style::from(style));
+    /// ```
+    fn from(style: NuStyle) -> Style {
+        Style {
+            foreground:        style.foreground.and_then(|c| Colour::try_from(c).ok()),
+            background:        style.background.and_then(|c| Colour::try_from(c).ok()),
+            is_bold:           style.is_bold,
+            is_dimmed:         style.is_dimmed,
+            is_italic:         style.is_italic,
+            is_underline:      style.is_underline,
+            is_blink:          style.is_blink,
+            is_reverse:        style.is_reverse,
+            is_hidden:         style.is_hidden,
+            is_strikethrough:  style.is_strikethrough,
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use nu_ansi_term::{Color, Style as NuStyle};
+    use style::{Colour, Style};
+
+    #[test]
+    fn round_trip() {
+        let style = Colour::RGB(4, 5, 6).on(Colour::Cyan).dimmed().italic().strikethrough();
+        assert_eq!(style, Style::from(NuStyle::from(style)));
+    }
+
+    #[test]
+    fn default_is_no_colour() {
+        assert_eq!(Style::default(), Style::from(Color::Default.normal()));
+    }
+}
@@ -0,0 +1,204 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use owo_colors::{AnsiColors, DynColor, DynColors, Effect, Rgb, XtermColors, Style as OwoStyle};
+
+use style::{Colour, Style};
+
+
+impl From<Colour> for DynColors {
+
+    /// Converts a `Colour` into the equivalent owo-colors `DynColors`.
+    ///
+    /// ```
+    /// extern crate owo_colors;
+    /// use ansi_term::Colour;
+    /// use owo_colors::{AnsiColors, DynColors};
+    ///
+    /// assert_eq!(DynColors::Ansi(AnsiColors::Magenta), DynColors::from(Colour::Purple));
+    /// assert_eq!(DynColors::Rgb(1, 2, 3), Colour::RGB(1, 2, 3).into());
+    /// ```
+    fn from(colour: Colour) -> DynColors {
+        match colour {
+            Colour::Black       => DynColors::Ansi(AnsiColors::Black),
+            Colour::Red         => DynColors::Ansi(AnsiColors::Red),
+            Colour::Green       => DynColors::Ansi(AnsiColors::Green),
+            Colour::Yellow      => DynColors::Ansi(AnsiColors::Yellow),
+            Colour::Blue        => DynColors::Ansi(AnsiColors::Blue),
+            Colour::Purple      => DynColors::Ansi(AnsiColors::Magenta),
+            Colour::Cyan        => DynColors::Ansi(AnsiColors::Cyan),
+            Colour::White       => DynColors::Ansi(AnsiColors::White),
+            Colour::Fixed(num)  => DynColors::Xterm(XtermColors::from(num)),
+            Colour::RGB(r,g,b)  => DynColors::Rgb(r, g, b),
+            #[cfg(feature = "cmyk")]
+            Colour::CMY(..) | Colour::CMYK(..) => DynColors::from(colour.to_rgb()),
+        }
+    }
+}
+
+impl From<Rgb> for Colour {
+
+    /// Converts an owo-colors `Rgb` value into `Colour::RGB`.
+    fn from(rgb: Rgb) -> Colour {
+        Colour::RGB(rgb.0, rgb.1, rgb.2)
+    }
+}
+
+impl From<XtermColors> for Colour {
+
+    /// Converts an owo-colors `XtermColors` value into a `Colour::Fixed`
+    /// with the same number.
+    ///
+    /// ```
+    /// extern crate owo_colors;
+    /// use ansi_term::Colour;
+    /// use owo_colors::XtermColors;
+    ///
+    /// assert_eq!(Colour::Fixed(208), Colour::from(XtermColors::from(208)));
+    /// ```
+    fn from(colour: XtermColors) -> Colour {
+        Colour::Fixed(u8::from(colour))
+    }
+}
+
+impl TryFrom<AnsiColors> for Colour {
+    type Error = AnsiColors;
+
+    /// Converts an owo-colors `AnsiColors` value into a `Colour`, with the
+    /// bright colours becoming their numbers in the 256-colour palette.
+    ///
+    /// `AnsiColors::Default` has no equivalent, and gets returned as the error.
+    ///
+    /// ```
+    /// extern crate owo_colors;
+    /// use std::convert::TryFrom;
+    /// use ansi_term::Colour;
+    /// use owo_colors::AnsiColors;
+    ///
+    /// assert_eq!(Ok(Colour::Fixed(14)), Colour::try_from(AnsiColors::BrightCyan));
+    /// assert_eq!(Err(AnsiColors::Default), Colour::try_from(AnsiColors::Default));
+    /// ```
+    fn try_from(colour: AnsiColors) -> Result<Colour, AnsiColors> {
+        Ok(match colour {
+            AnsiColors::Black          => Colour::Black,
+            AnsiColors::Red            => Colour::Red,
+            AnsiColors::Green          => Colour::Green,
+            AnsiColors::Yellow         => Colour::Yellow,
+            AnsiColors::Blue           => Colour::Blue,
+            AnsiColors::Magenta        => Colour::Purple,
+            AnsiColors::Cyan           => Colour::Cyan,
+            AnsiColors::White          => Colour::White,
+            AnsiColors::BrightBlack    => Colour::Fixed(8),
+            AnsiColors::BrightRed      => Colour::Fixed(9),
+            AnsiColors::BrightGreen    => Colour::Fixed(10),
+            AnsiColors::BrightYellow   => Colour::Fixed(11),
+            AnsiColors::BrightBlue     => Colour::Fixed(12),
+            AnsiColors::BrightMagenta  => Colour::Fixed(13),
+            AnsiColors::BrightCyan     => Colour::Fixed(14),
+            AnsiColors::BrightWhite    => Colour::Fixed(15),
+            AnsiColors::Default        => return Err(colour),
+        })
+    }
+}
+
+impl TryFrom<DynColors> for Colour {
+    type Error = DynColors;
+
+    /// Converts an owo-colors `DynColors` value into a `Colour`. CSS colours
+    /// become their RGB values.
+    ///
+    /// `AnsiColors::Default` has no equivalent, and gets returned as the error.
+    ///
+    /// ```
+    /// extern crate owo_colors;
+    /// use std::convert::TryFrom;
+    /// use ansi_term::Colour;
+    /// use owo_colors::{CssColors, DynColors};
+    ///
+    /// assert_eq!(Ok(Colour::RGB(70, 130, 180)), Colour::try_from(DynColors::Css(CssColors::SteelBlue)));
+    /// ```
+    fn try_from(colour: DynColors) -> Result<Colour, DynColors> {
+        match colour {
+            DynColors::Ansi(ansi)     => Colour::try_from(ansi).map_err(|_| colour),
+            DynColors::Xterm(xterm)   => Ok(Colour::from(xterm)),
+            DynColors::Rgb(r, g, b)   => Ok(Colour::RGB(r, g, b)),
+            DynColors::Css(css)       => css_rgb(css).ok_or(colour),
+        }
+    }
+}
+
+/// owo-colors doesn’t give out the RGB values of its CSS colours, so this
+/// reads them back out of the `38;2;r;g;b` code it writes for them.
+fn css_rgb<C: DynColor>(colour: C) -> Option<Colour> {
+    struct RawForeground<C>(C);
+
+    impl<C: DynColor> fmt::Display for RawForeground<C> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            self.0.fmt_raw_ansi_fg(f)
+        }
+    }
+
+    let code = RawForeground(colour).to_string();
+    let mut numbers = code.strip_prefix("38;2;")?.split(';').map(|n| n.parse::<u8>().ok());
+
+    match (numbers.next(), numbers.next(), numbers.next(), numbers.next()) {
+        (Some(Some(r)), Some(Some(g)), Some(Some(b)), None) => Some(Colour::RGB(r, g, b)),
+        _ => None,
+    }
+}
+
+
+impl From<Style> for OwoStyle {
+
+    /// Converts a `Style` into the equivalent owo-colors `Style`.
+    ///
+    /// owo-colors doesn’t give out the properties of its styles, so there is
+    /// no conversion in the other direction.
+    ///
+    /// ```
+    /// extern crate owo_colors;
+    /// use ansi_term::Colour::Red;
+    ///
+    /// let style = owo_colors::Style::from(Red.bold());
+    /// assert_eq!(owo_colors::Style::new().red().bold(), style);
+    /// ```
+    fn from(style: Style) -> OwoStyle {
+        let mut owo = OwoStyle::new();
+
+        if let Some(fg) = style.foreground { owo = owo.color(DynColors::from(fg)) }
+        if let Some(bg) = style.background { owo = owo.on_color(DynColors::from(bg)) }
+
+        if style.is_bold           { owo = owo.effect(Effect::Bold) }
+        if style.is_dimmed         { owo = owo.effect(Effect::Dimmed) }
+        if style.is_italic         { owo = owo.effect(Effect::Italic) }
+        if style.is_underline      { owo = owo.effect(Effect::Underline) }
+        if style.is_blink          { owo = owo.effect(Effect::Blink) }
+        if style.is_reverse        { owo = owo.effect(Effect::Reversed) }
+        if style.is_hidden         { owo = owo.effect(Effect::Hidden) }
+        if style.is_strikethrough  { owo = owo.effect(Effect::Strikethrough) }
+
+        owo
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::convert::TryFrom;
+    use owo_colors::{DynColors, Style as OwoStyle, XtermColors};
+    use style::Colour::{self, *};
+
+    #[test]
+    fn style() {
+        let style = Blue.on(Fixed(100)).italic().strikethrough();
+        let owo = OwoStyle::new().blue().on_color(XtermColors::from(100)).italic().strikethrough();
+        assert_eq!(owo, OwoStyle::from(style));
+    }
+
+    #[test]
+    fn colour_round_trip() {
+        for colour in &[ Black, Purple, White, Fixed(3), RGB(7, 8, 9) ] {
+            assert_eq!(Ok(*colour), Colour::try_from(DynColors::from(*colour)));
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use syntect::highlighting::{Color, FontStyle, Style as SyntectStyle};
+
+use display::ANSIString;
+use style::{Colour, Style};
+
+
+impl From<Color> for Colour {
+
+    /// Converts a syntect `Color` into `Colour::RGB`, ignoring its alpha
+    /// channel.
+    ///
+    /// ```
+    /// extern crate syntect;
+    /// use ansi_term::Colour;
+    /// use syntect::highlighting::Color;
+    ///
+    /// assert_eq!(Colour::RGB(1, 2, 3), Colour::from(Color { r: 1, g: 2, b: 3, a: 0xFF }));
+    /// ```
+    fn from(colour: Color) -> Colour {
+        Colour::RGB(colour.r, colour.g, colour.b)
+    }
+}
+
+impl From<FontStyle> for Style {
+
+    /// Converts a set of syntect `FontStyle` flags into a `Style` with no
+    /// colours, and the same properties set.
+    fn from(font_style: FontStyle) -> Style {
+        Style {
+            is_bold:       font_style.contains(FontStyle::BOLD),
+            is_italic:     font_style.contains(FontStyle::ITALIC),
+            is_underline:  font_style.contains(FontStyle::UNDERLINE),
+            .. Style::default()
+        }
+    }
+}
+
+impl From<SyntectStyle> for Style {
+
+    /// Converts a syntect `Style` into the equivalent `Style`, with both of
+    /// its colours and its font style.
+    ///
+    /// ```
+    /// extern crate syntect;
+    /// use ansi_term::Colour::RGB;
+    /// use ansi_term::Style;
+    /// use syntect::highlighting::{Color, FontStyle};
+    ///
+    /// let style = syntect::highlighting::Style {
+    ///     foreground: Color { r: 200, g: 0, b: 0, a: 0xFF },
+    ///     background: Color::BLACK,
+    ///     font_style: FontStyle::BOLD | FontStyle::ITALIC,
+    /// };
+    ///
+    /// assert_eq!(Style::from(style), RGB(200, 0, 0).on(RGB(0, 0, 0)).bold().italic());
+    /// ```
+    fn from(style: SyntectStyle) -> Style {
+        Style {
+            foreground: Some(Colour::from(style.foreground)),
+            background: Some(Colour::from(style.background)),
+            .. Style::from(style.font_style)
+        }
+    }
+}
+
+/// Turns the ranges that syntect’s highlighters return for a line into
+/// strings that can be painted together as `ANSIStrings`.
+///
+/// Every range from a highlighter has the theme’s background colour, so the
+/// backgrounds get left out, and the terminal’s own background shows
+/// through. To keep them, convert each style with `Style::from` instead.
+///
+/// This function is only available with the `syntect` feature.
+///
+/// # Examples
+///
+/// ```
+/// extern crate syntect;
+/// use ansi_term::{from_highlighted, ANSIStrings};
+/// use ansi_term::Colour::RGB;
+/// use syntect::highlighting::{Color, FontStyle, Style};
+///
+/// let keyword = Style { foreground: Color { r: 0, g: 0, b: 255, a: 0xFF }, background: Color::WHITE, font_style: FontStyle::BOLD };
+/// let plain = Style { foreground: Color::BLACK, background: Color::WHITE, font_style: FontStyle::empty() };
+///
+/// let strings = from_highlighted(&[ (keyword, "fn"), (plain, " main") ]);
+/// assert_eq!(strings, vec![ RGB(0, 0, 255).bold().paint("fn"), RGB(0, 0, 0).paint(" main") ]);
+/// println!("{}", ANSIStrings(&strings));
+/// ```
+pub fn from_highlighted<'a>(ranges: &[(SyntectStyle, &'a str)]) -> Vec<ANSIString<'a>> {
+    ranges.iter()
+          .map(|&(style, text)| Style { background: None, .. Style::from(style) }.paint(text))
+          .collect()
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn font_styles() {
+        assert_eq!(Style::from(FontStyle::empty()), Style::default());
+        assert_eq!(Style::from(FontStyle::UNDERLINE | FontStyle::BOLD), Style::new().underline().bold());
+    }
+
+    #[test]
+    fn highlighted_ranges_lose_backgrounds() {
+        let style = SyntectStyle { foreground: Color::WHITE, background: Color::BLACK, font_style: FontStyle::ITALIC };
+        assert_eq!(from_highlighted(&[ (style, "x") ])[0].style_ref(), &Colour::RGB(255, 255, 255).italic());
+    }
+}
@@ -0,0 +1,188 @@
+use std::io;
+
+use termcolor::{Color, ColorSpec, WriteColor};
+
+use display::{ANSIString, ANSIStrings};
+use style::{Colour, Style};
+
+
+impl From<Colour> for Color {
+
+    /// Converts a `Colour` into the equivalent termcolor `Color`.
+    ///
+    /// ```
+    /// extern crate termcolor;
+    /// use ansi_term::Colour;
+    /// use termcolor::Color;
+    ///
+    /// assert_eq!(Color::Magenta, Color::from(Colour::Purple));
+    /// assert_eq!(Color::Rgb(1, 2, 3), Colour::RGB(1, 2, 3).into());
+    /// ```
+    fn from(colour: Colour) -> Color {
+        match colour {
+            Colour::Black       => Color::Black,
+            Colour::Red         => Color::Red,
+            Colour::Green       => Color::Green,
+            Colour::Yellow      => Color::Yellow,
+            Colour::Blue        => Color::Blue,
+            Colour::Purple      => Color::Magenta,
+            Colour::Cyan        => Color::Cyan,
+            Colour::White       => Color::White,
+            Colour::Fixed(num)  => Color::Ansi256(num),
+            Colour::RGB(r,g,b)  => Color::Rgb(r, g, b),
+            #[cfg(feature = "cmyk")]
+            Colour::CMY(..) | Colour::CMYK(..) => Color::from(colour.to_rgb()),
+        }
+    }
+}
+
+impl From<Style> for ColorSpec {
+
+    /// Converts a `Style` into a termcolor `ColorSpec`.
+    ///
+    /// termcolor has no way to express blinking, reversed, or hidden text, so
+    /// those properties get left out.
+    ///
+    /// ```
+    /// extern crate termcolor;
+    /// use ansi_term::Colour::Red;
+    /// use termcolor::{Color, ColorSpec};
+    ///
+    /// let spec = ColorSpec::from(Red.bold());
+    /// assert_eq!(Some(&Color::Red), spec.fg());
+    /// assert!(spec.bold());
+    /// ```
+    fn from(style: Style) -> ColorSpec {
+        let mut spec = ColorSpec::new();
+        spec.set_fg(style.foreground.map(Color::from))
+            .set_bg(style.background.map(Color::from))
+            .set_bold(style.is_bold)
+            .set_dimmed(style.is_dimmed)
+            .set_italic(style.is_italic)
+            .set_underline(style.is_underline)
+            .set_strikethrough(style.is_strikethrough);
+        spec
+    }
+}
+
+impl<'a> From<&'a ColorSpec> for Style {
+
+    /// Converts a termcolor `ColorSpec` into a `Style`.
+    ///
+    /// An intense foreground colour becomes the bright version of that colour
+    /// out of the 256-colour palette.
+    ///
+    /// ```
+    /// extern crate termcolor;
+    /// use ansi_term::Colour;
+    /// use ansi_term::Style;
+    /// use termcolor::{Color, ColorSpec};
+    ///
+    /// let mut spec = ColorSpec::new();
+    /// spec.set_fg(Some(Color::Green)).set_intense(true).set_italic(true);
+    /// assert_eq!(Colour::Fixed(10).italic(), Style::from(&spec));
+    /// ```
+    fn from(spec: &'a ColorSpec) -> Style {
+        let foreground = spec.fg().and_then(|c| from_color(*c)).map(|c| {
+            match c {
+                Colour::Fixed(_) | Colour::RGB(..) => c,
+                _ if spec.intense() => Colour::Fixed(basic_index(c) + 8),
+                _ => c,
+            }
+        });
+
+        Style {
+            foreground,
+            background:        spec.bg().and_then(|c| from_color(*c)),
+            is_bold:           spec.bold(),
+            is_dimmed:         spec.dimmed(),
+            is_italic:         spec.italic(),
+            is_underline:      spec.underline(),
+            is_strikethrough:  spec.strikethrough(),
+            .. Style::default()
+        }
+    }
+}
+
+fn from_color(color: Color) -> Option<Colour> {
+    match color {
+        Color::Black          => Some(Colour::Black),
+        Color::Red            => Some(Colour::Red),
+        Color::Green          => Some(Colour::Green),
+        Color::Yellow         => Some(Colour::Yellow),
+        Color::Blue           => Some(Colour::Blue),
+        Color::Magenta        => Some(Colour::Purple),
+        Color::Cyan           => Some(Colour::Cyan),
+        Color::White          => Some(Colour::White),
+        Color::Ansi256(num)   => Some(Colour::Fixed(num)),
+        Color::Rgb(r, g, b)   => Some(Colour::RGB(r, g, b)),
+        _                     => None,
+    }
+}
+
+fn basic_index(colour: Colour) -> u8 {
+    match colour {
+        Colour::Black   => 0,
+        Colour::Red     => 1,
+        Colour::Green   => 2,
+        Colour::Yellow  => 3,
+        Colour::Blue    => 4,
+        Colour::Purple  => 5,
+        Colour::Cyan    => 6,
+        _               => 7,
+    }
+}
+
+
+impl<'a> ANSIString<'a> {
+
+    /// Writes this string to a termcolor `WriteColor`, letting it decide how
+    /// (and whether) to display the style. This is how the string gets
+    /// coloured on Windows consoles that don’t understand ANSI codes.
+    pub fn write_to_color<W: WriteColor + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+        write_fragment(self, w)?;
+        w.reset()
+    }
+}
+
+impl<'a> ANSIStrings<'a> {
+
+    /// Writes these strings to a termcolor `WriteColor`, changing its colour
+    /// specification for each one in turn, and resetting it at the end.
+    ///
+    /// ```
+    /// extern crate termcolor;
+    /// use ansi_term::ANSIStrings;
+    /// use ansi_term::Colour::{Red, Blue};
+    /// use termcolor::{Ansi, WriteColor};
+    ///
+    /// let mut out = Ansi::new(Vec::new());
+    /// ANSIStrings(&[ Red.paint("a"), Blue.paint("b") ]).write_to_color(&mut out).unwrap();
+    /// assert!(out.into_inner().starts_with(b"\x1B[0m\x1B[31ma"));
+    /// ```
+    pub fn write_to_color<W: WriteColor + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+        for fragment in self.0 {
+            write_fragment(fragment, w)?;
+        }
+
+        w.reset()
+    }
+}
+
+fn write_fragment<W: WriteColor + ?Sized>(fragment: &ANSIString, w: &mut W) -> io::Result<()> {
+    w.set_color(&ColorSpec::from(*fragment.style_ref()))?;
+    w.write_all(fragment.as_bytes())
+}
+
+
+#[cfg(test)]
+mod test {
+    use termcolor::ColorSpec;
+    use style::{Colour, Style};
+
+    #[test]
+    fn round_trip() {
+        let style = Colour::RGB(1, 2, 3).on(Colour::Fixed(100)).underline().strikethrough();
+        assert_eq!(style, Style::from(&ColorSpec::from(style)));
+    }
+}
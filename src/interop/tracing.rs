@@ -0,0 +1,202 @@
+use std::error::Error;
+use std::fmt;
+
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level};
+
+use logging::{LogLevel, LogTheme};
+
+
+impl From<Level> for LogLevel {
+    fn from(level: Level) -> LogLevel {
+        if      level == Level::ERROR  { LogLevel::Error }
+        else if level == Level::WARN   { LogLevel::Warn }
+        else if level == Level::INFO   { LogLevel::Info }
+        else if level == Level::DEBUG  { LogLevel::Debug }
+        else                           { LogLevel::Trace }
+    }
+}
+
+impl<'a> From<&'a Level> for LogLevel {
+    fn from(level: &'a Level) -> LogLevel {
+        LogLevel::from(*level)
+    }
+}
+
+
+/// A `tracing` field visitor that writes each field it visits to a
+/// `fmt::Write`, painted with a `LogTheme`.
+///
+/// The field called `message` gets written on its own, in the message style.
+/// Every other field gets written as `name=value`, separated by spaces, with
+/// strings quoted. This makes it the core of a formatter for
+/// `tracing-subscriber`, or any other subscriber.
+///
+/// # Examples
+///
+/// ```
+/// extern crate tracing;
+/// use ansi_term::{FieldWriter, LogTheme};
+///
+/// let theme = LogTheme::default();
+/// let mut output = String::new();
+///
+/// // in a subscriber’s `event` method:
+/// # fn record(event: &tracing::Event, theme: &LogTheme, output: &mut String) {
+/// let mut writer = FieldWriter::new(theme, output);
+/// event.record(&mut writer);
+/// writer.finish().unwrap();
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FieldWriter<'a, W: fmt::Write + 'a> {
+    theme: &'a LogTheme,
+    writer: &'a mut W,
+    written_anything: bool,
+    result: fmt::Result,
+}
+
+impl<'a, W: fmt::Write> FieldWriter<'a, W> {
+
+    /// Creates a new visitor that writes to the given writer.
+    pub fn new(theme: &'a LogTheme, writer: &'a mut W) -> FieldWriter<'a, W> {
+        FieldWriter { theme, writer, written_anything: false, result: Ok(()) }
+    }
+
+    /// Returns the first error that happened while writing, if any.
+    pub fn finish(self) -> fmt::Result {
+        self.result
+    }
+
+    fn write_message(&mut self, value: fmt::Arguments) {
+        let style = self.theme.message;
+        self.write_separated(format_args!("{}{}{}", style.prefix(), value, style.suffix()));
+    }
+
+    fn write_field(&mut self, field: &Field, value: fmt::Arguments, is_error: bool) {
+        let name = self.theme.field_name;
+        let value_style = if is_error { self.theme.field_error } else { self.theme.field_value };
+
+        self.write_separated(format_args!("{}{}{}={}{}{}",
+            name.prefix(), field.name(), name.suffix(),
+            value_style.prefix(), value, value_style.suffix()));
+    }
+
+    fn write_separated(&mut self, args: fmt::Arguments) {
+        if self.result.is_err() {
+            return;
+        }
+
+        if self.written_anything {
+            self.result = self.writer.write_char(' ');
+        }
+
+        self.written_anything = true;
+        self.result = self.result.and_then(|_| self.writer.write_fmt(args));
+    }
+}
+
+impl<'a, W: fmt::Write> Visit for FieldWriter<'a, W> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.write_message(format_args!("{}", value));
+        }
+        else {
+            self.write_field(field, format_args!("{:?}", value), false);
+        }
+    }
+
+    fn record_error(&mut self, field: &Field, value: &(dyn Error + 'static)) {
+        self.write_field(field, format_args!("{}", value), true);
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.write_message(format_args!("{:?}", value));
+        }
+        else {
+            self.write_field(field, format_args!("{:?}", value), false);
+        }
+    }
+}
+
+
+/// A `tracing` event painted with a `LogTheme`, as returned by
+/// [`LogTheme::paint_event`].
+///
+/// This type implements the `Display` trait, writing the level, target, and
+/// fields of the event on one line, in the same layout as the `log` feature’s
+/// `PaintedRecord`.
+///
+/// [`LogTheme::paint_event`]: struct.LogTheme.html#method.paint_event
+#[derive(Clone, Copy, Debug)]
+pub struct PaintedEvent<'a> {
+    theme: &'a LogTheme,
+    event: &'a Event<'a>,
+}
+
+impl LogTheme {
+
+    /// Paints a `tracing` event with this theme, for use in a subscriber’s
+    /// formatting code.
+    pub fn paint_event<'a>(&'a self, event: &'a Event<'a>) -> PaintedEvent<'a> {
+        PaintedEvent { theme: self, event }
+    }
+}
+
+impl<'a> fmt::Display for PaintedEvent<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let metadata = self.event.metadata();
+
+        let level = LogLevel::from(metadata.level());
+        let level_style = self.theme.level(level);
+        write!(f, "{}{:<5}{} ", level_style.prefix(), level.name(), level_style.suffix())?;
+
+        let target = self.theme.target;
+        write!(f, "{}{}{}: ", target.prefix(), metadata.target(), target.suffix())?;
+
+        let mut writer = FieldWriter::new(self.theme, f);
+        self.event.record(&mut writer);
+        writer.finish()
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use tracing::{self, Event, Metadata, Subscriber};
+    use tracing::span::{Attributes, Id, Record};
+
+    use logging::LogTheme;
+
+    struct Capture(Arc<Mutex<Vec<String>>>);
+
+    impl Subscriber for Capture {
+        fn enabled(&self, _: &Metadata) -> bool { true }
+        fn new_span(&self, _: &Attributes) -> Id { Id::from_u64(1) }
+        fn record(&self, _: &Id, _: &Record) {}
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+
+        fn event(&self, event: &Event) {
+            let theme = LogTheme::default();
+            self.0.lock().unwrap().push(theme.paint_event(event).to_string());
+        }
+    }
+
+    #[test]
+    fn event() {
+        let lines = Arc::new(Mutex::new(Vec::new()));
+
+        tracing::subscriber::with_default(Capture(lines.clone()), || {
+            tracing::error!(target: "app", user = "ben", tries = 3, "login failed");
+        });
+
+        assert_eq!(*lines.lock().unwrap(), vec![
+            "\x1B[1;31mERROR\x1B[0m \x1B[2mapp\x1B[0m: login failed \x1B[3muser\x1B[0m=\"ben\" \x1B[3mtries\x1B[0m=3"
+        ]);
+    }
+}
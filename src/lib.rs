@@ -110,10 +110,12 @@
 //! included wherever you would use a `Colour`:
 //!
 //! ```
+//! # #[cfg(feature = "fixed")] {
 //! use ansi_term::Colour::Fixed;
 //!
 //! Fixed(134).paint("A sort of light purple");
 //! Fixed(221).on(Fixed(124)).paint("Mustard in the ketchup");
+//! # }
 //! ```
 //!
 //! The first sixteen of these values are the same as the normal and bold
@@ -125,11 +127,18 @@
 //! which takes separate `u8` arguments for red, green, and blue:
 //!
 //! ```
+//! # #[cfg(feature = "rgb")] {
 //! use ansi_term::Colour::RGB;
 //!
 //! RGB(70, 130, 180).paint("Steel blue");
+//! # }
 //! ```
 //!
+//! These two variants are behind the `fixed` and `rgb` features, which are
+//! on by default. Programs that only ever use the eight basic colours, such
+//! as those for embedded devices, can turn off the default features to leave
+//! out the code that renders them.
+//!
 //! ## Combining successive coloured strings
 //!
 //! The benefit of writing ANSI escape codes to the terminal is that they
@@ -240,6 +249,34 @@
 
 #[cfg(target_os="windows")]
 extern crate winapi;
+#[cfg(feature = "termcolor")]
+extern crate termcolor;
+#[cfg(feature = "crossterm")]
+extern crate crossterm;
+#[cfg(feature = "nu-ansi-term")]
+extern crate nu_ansi_term;
+#[cfg(feature = "owo-colors")]
+extern crate owo_colors;
+#[cfg(feature = "syntect")]
+extern crate syntect;
+#[cfg(feature = "log")]
+extern crate log;
+#[cfg(feature = "tracing")]
+extern crate tracing;
+#[cfg(all(unix, feature = "query"))]
+extern crate libc;
+#[cfg(feature = "embedded-io")]
+extern crate embedded_io;
+#[cfg(feature = "unicode-width")]
+extern crate unicode_width;
+#[cfg(feature = "regex")]
+extern crate regex;
+#[cfg(feature = "unicode-segmentation")]
+extern crate unicode_segmentation;
+#[cfg(feature = "quickcheck")]
+extern crate quickcheck;
+#[cfg(feature = "rand")]
+extern crate rand;
 #[cfg(test)]
 #[macro_use]
 extern crate doc_comment;
@@ -251,7 +288,7 @@ mod ansi;
 pub use ansi::{Prefix, Infix, Suffix};
 
 mod style;
-pub use style::{Colour, Style};
+pub use style::{Attribute, Colour, Style};
 
 /// Color is a type alias for `Colour`.
 pub use Colour as Color;
@@ -263,9 +300,155 @@ pub use display::*;
 mod write;
 
 mod windows;
+#[cfg(windows)]
 pub use windows::*;
 
 mod util;
 pub use util::*;
 
+mod filter;
+pub use filter::*;
+
+mod hook;
+pub use hook::*;
+
+mod backend;
+pub use backend::*;
+
+mod depth;
+pub use depth::*;
+
+mod control;
+pub use control::*;
+
+mod image;
+pub use image::*;
+
+#[cfg(feature = "fixed")]
+mod chart;
+#[cfg(feature = "fixed")]
+pub use chart::*;
+
+#[cfg(feature = "query")]
+mod query;
+#[cfg(feature = "query")]
+pub use query::*;
+
+#[cfg(feature = "terminfo")]
+mod terminfo;
+#[cfg(feature = "terminfo")]
+pub use terminfo::*;
+
+mod reset;
+pub use reset::*;
+
+mod style_guard;
+pub use style_guard::*;
+
+mod painted_writer;
+pub use painted_writer::*;
+
+mod strip;
+pub use strip::*;
+
+mod downgrade;
+pub use downgrade::*;
+
+mod syntax;
+pub use syntax::*;
+
+mod rewrite;
+pub use rewrite::*;
+
+mod record;
+pub use record::*;
+
+mod compiled;
+pub use compiled::*;
+
+mod shared;
+pub use shared::*;
+
+mod styled_text;
+pub use styled_text::*;
+
+mod document;
+pub use document::*;
+
+mod screen;
+pub use screen::*;
+
+mod table;
+pub use table::*;
+
+mod grid;
+pub use grid::*;
+
+mod boxes;
+pub use boxes::*;
+
+mod progress;
+pub use progress::*;
+
+mod search;
+pub use search::*;
+
+mod tagged;
+pub use tagged::*;
+
+mod markers;
+pub use markers::*;
+
+mod theme;
+pub use theme::*;
+
+mod style_list;
+pub use style_list::*;
+
+mod sgr;
+
+mod escaped;
+pub use escaped::*;
+
+mod web;
+pub use web::*;
+
+pub mod testing;
+
+pub mod diagnostics;
+
+#[cfg(feature = "diff")]
+mod diff;
+#[cfg(feature = "diff")]
+pub use diff::*;
+
+#[cfg(feature = "ansi-art")]
+mod ansi_art;
+#[cfg(feature = "ansi-art")]
+pub use ansi_art::*;
+
+mod buffer;
+pub use buffer::*;
+
+mod stream;
+pub use stream::*;
+
+mod logging;
+pub use logging::*;
+
+mod interop;
+#[allow(unused_imports)]
+pub use interop::*;
+
 mod debug;
+
+#[cfg(feature = "rgb")]
+mod adjust;
+
+#[cfg(feature = "quickcheck")]
+mod arbitrary;
+
+#[cfg(feature = "rand")]
+mod random;
+#[cfg(feature = "rand")]
+pub use random::*;
@@ -0,0 +1,132 @@
+use style::{Colour, Style};
+
+
+/// The severity of a log message, as used by the `log` and `tracing`
+/// crates. Their own level types convert into this one when the matching
+/// feature is turned on.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Hash)]
+pub enum LogLevel {
+
+    /// Something has gone wrong.
+    Error,
+
+    /// Something might have gone wrong.
+    Warn,
+
+    /// Something worth knowing about has happened.
+    Info,
+
+    /// Something only useful when debugging has happened.
+    Debug,
+
+    /// Something very low-level has happened.
+    Trace,
+}
+
+impl LogLevel {
+
+    /// The upper-case name of this level, as most loggers print it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::LogLevel;
+    ///
+    /// assert_eq!("WARN", LogLevel::Warn.name());
+    /// ```
+    pub fn name(self) -> &'static str {
+        match self {
+            LogLevel::Error  => "ERROR",
+            LogLevel::Warn   => "WARN",
+            LogLevel::Info   => "INFO",
+            LogLevel::Debug  => "DEBUG",
+            LogLevel::Trace  => "TRACE",
+        }
+    }
+}
+
+
+/// The styles to paint the parts of a log message with.
+///
+/// The default theme paints errors in bold red, warnings in yellow, info
+/// messages in green, debug messages in blue, trace messages in purple, and
+/// the target dimmed, leaving the message itself alone. Field names are in
+/// italics, and errors in field values are red.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{LogLevel, LogTheme, Colour::Cyan};
+///
+/// let theme = LogTheme { info: Cyan.normal(), .. LogTheme::default() };
+/// assert_eq!(Cyan.normal(), theme.level(LogLevel::Info));
+/// ```
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct LogTheme {
+
+    /// The style for the level of error messages.
+    pub error: Style,
+
+    /// The style for the level of warning messages.
+    pub warn: Style,
+
+    /// The style for the level of info messages.
+    pub info: Style,
+
+    /// The style for the level of debug messages.
+    pub debug: Style,
+
+    /// The style for the level of trace messages.
+    pub trace: Style,
+
+    /// The style for the target (usually the module path) of a message.
+    pub target: Style,
+
+    /// The style for the message itself.
+    pub message: Style,
+
+    /// The style for the names of any structured fields attached to a
+    /// message.
+    pub field_name: Style,
+
+    /// The style for the values of any structured fields.
+    pub field_value: Style,
+
+    /// The style for the values of any structured fields that are errors.
+    pub field_error: Style,
+}
+
+impl LogTheme {
+
+    /// Returns the style for the given level.
+    ///
+    /// With the `log` or `tracing` features, this accepts their level types
+    /// as well.
+    pub fn level<L: Into<LogLevel>>(&self, level: L) -> Style {
+        match level.into() {
+            LogLevel::Error  => self.error,
+            LogLevel::Warn   => self.warn,
+            LogLevel::Info   => self.info,
+            LogLevel::Debug  => self.debug,
+            LogLevel::Trace  => self.trace,
+        }
+    }
+}
+
+impl Default for LogTheme {
+    fn default() -> LogTheme {
+        LogTheme {
+            error:    Colour::Red.bold(),
+            warn:     Colour::Yellow.normal(),
+            info:     Colour::Green.normal(),
+            debug:    Colour::Blue.normal(),
+            trace:    Colour::Purple.normal(),
+            target:   Style::new().dimmed(),
+            message:  Style::new(),
+
+            field_name:   Style::new().italic(),
+            field_value:  Style::new(),
+            field_error:  Colour::Red.normal(),
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use std::fmt;
+
+use display::ANSIStrings;
+use style::Style;
+
+
+/// A renderer that writes styled text with textual markers in place of
+/// escape codes, for screen readers and terminals such as `TERM=dumb` that
+/// can’t show colours.
+///
+/// Styles that have been given a name with [`name`] get wrapped in tags, as
+/// in `[error]disk full[/error]`. Any other style that isn’t plain gets
+/// wrapped in emphasis characters for its properties: `*` for bold, `_` for
+/// italic or underline, and `~` for strikethrough. Colours on their own
+/// aren’t marked, as there’s no good way to say them in text; give the style
+/// a name if its colour means something.
+///
+/// [`name`]: #method.name
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{Markers, ANSIStrings, Style};
+/// use ansi_term::Colour::Red;
+///
+/// let markers = Markers::new().name(Red.bold(), "error");
+/// let line = [ Red.bold().paint("error"), Style::new().paint(": file "),
+///              Style::new().underline().paint("a.txt"), Style::new().paint(" is missing") ];
+///
+/// assert_eq!(markers.render(&ANSIStrings(&line)).to_string(),
+///            "[error]error[/error]: file _a.txt_ is missing");
+/// ```
+#[derive(PartialEq, Clone, Default, Debug)]
+pub struct Markers {
+    names: Vec<(Style, String)>,
+}
+
+impl Markers {
+
+    /// Creates a renderer with no named styles.
+    pub fn new() -> Markers {
+        Markers::default()
+    }
+
+    /// Returns a renderer that wraps text in the given style in tags with
+    /// the given name, replacing any name it had before.
+    pub fn name(mut self, style: Style, name: &str) -> Markers {
+        self.names.retain(|n| n.0 != style);
+        self.names.push((style, String::from(name)));
+        self
+    }
+
+    /// Returns the markers that go before and after text in the given style.
+    pub fn markers_for(&self, style: Style) -> (String, String) {
+        if let Some((_, name)) = self.names.iter().find(|n| n.0 == style) {
+            return (format!("[{}]", name), format!("[/{}]", name));
+        }
+
+        let mut open = String::new();
+        if style.is_bold                         { open.push('*') }
+        if style.is_italic || style.is_underline { open.push('_') }
+        if style.is_strikethrough                { open.push('~') }
+
+        let close = open.chars().rev().collect();
+        (open, close)
+    }
+
+    /// Returns a value that writes the strings with markers when displayed.
+    pub fn render<'m, 'a, 's>(&'m self, strs: &'s ANSIStrings<'a>) -> MarkedStrings<'m, 's, 'a> {
+        MarkedStrings { markers: self, strs }
+    }
+}
+
+
+/// Styled strings that get displayed with markers instead of escape codes,
+/// as returned by [`Markers::render`].
+///
+/// [`Markers::render`]: struct.Markers.html#method.render
+#[derive(Debug)]
+pub struct MarkedStrings<'m, 's, 'a: 's> {
+    markers: &'m Markers,
+    strs: &'s ANSIStrings<'a>,
+}
+
+impl<'m, 's, 'a> fmt::Display for MarkedStrings<'m, 's, 'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut close = String::new();
+        let mut previous = None;
+
+        for fragment in self.strs.0.iter() {
+            let style = *fragment.style_ref();
+
+            // Runs of fragments in the same style get marked as one.
+            if previous != Some(style) {
+                f.write_str(&close)?;
+                let (open, next_close) = self.markers.markers_for(style);
+                f.write_str(&open)?;
+                close = next_close;
+                previous = Some(style);
+            }
+
+            f.write_str(fragment)?;
+        }
+
+        f.write_str(&close)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use style::Colour::*;
+
+    #[test]
+    fn emphasis_nests() {
+        let (open, close) = Markers::new().markers_for(Blue.bold().italic());
+        assert_eq!((open.as_str(), close.as_str()), ("*_", "_*"));
+    }
+
+    #[test]
+    fn runs_are_merged() {
+        let line = [ Red.paint("a"), Red.paint("b"), Blue.paint("c") ];
+        let markers = Markers::new().name(Red.normal(), "r").name(Blue.normal(), "b");
+        assert_eq!(markers.render(&ANSIStrings(&line)).to_string(), "[r]ab[/r][b]c[/b]");
+    }
+}
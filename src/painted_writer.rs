@@ -0,0 +1,154 @@
+use std::io::{self, Write};
+
+use style::Style;
+
+
+/// A writer that paints everything written through it in one style, such as
+/// the output of a child process or a log stream that should be tinted as a
+/// whole.
+///
+/// The style’s prefix gets written just before the first text of each line,
+/// and its suffix just before each newline, so empty lines get no codes and
+/// a line-based program reading the output never sees the style spill over
+/// into the next line. The style also gets reset whenever the writer gets
+/// flushed or dropped, so nothing written to the same stream afterwards
+/// ends up painted.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+/// use ansi_term::PaintedWriter;
+/// use ansi_term::Colour::Red;
+///
+/// let mut out = PaintedWriter::new(Red.normal(), Vec::new());
+/// write!(out, "one\n\ntwo").unwrap();
+///
+/// assert_eq!(out.into_inner().unwrap(),
+///            b"\x1B[31mone\x1B[0m\n\n\x1B[31mtwo\x1B[0m");
+/// ```
+#[derive(Debug)]
+pub struct PaintedWriter<W: Write> {
+    writer: Option<W>,
+    style: Style,
+
+    /// Whether the prefix has been written without a suffix after it.
+    painting: bool,
+}
+
+impl<W: Write> PaintedWriter<W> {
+
+    /// Returns a writer that paints everything written to the given writer
+    /// in the given style.
+    pub fn new(style: Style, writer: W) -> PaintedWriter<W> {
+        PaintedWriter { writer: Some(writer), style, painting: false }
+    }
+
+    /// Returns the style the text gets painted in.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        self.writer.as_ref().expect("writer is only taken when consumed")
+    }
+
+    /// Resets the style if it’s needed, and returns the underlying writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.finish()?;
+        Ok(self.writer.take().expect("writer is only taken when consumed"))
+    }
+
+    /// Writes the suffix if the prefix has been written.
+    fn finish(&mut self) -> io::Result<()> {
+        if self.painting {
+            self.painting = false;
+            let suffix = self.style.suffix();
+            write!(self.writer_mut(), "{}", suffix)?;
+        }
+
+        Ok(())
+    }
+
+    fn writer_mut(&mut self) -> &mut W {
+        self.writer.as_mut().expect("writer is only taken when consumed")
+    }
+}
+
+impl<W: Write> Write for PaintedWriter<W> {
+
+    /// Writes up to the end of the first line in the buffer, painting the
+    /// text on it.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let (text, newline) = match buf.iter().position(|&b| b == b'\n') {
+            Some(index)  => (&buf[.. index], true),
+            None         => (buf, false),
+        };
+
+        if !text.is_empty() {
+            if !self.painting {
+                let prefix = self.style.prefix();
+                write!(self.writer_mut(), "{}", prefix)?;
+                self.painting = true;
+            }
+
+            self.writer_mut().write_all(text)?;
+        }
+
+        if newline {
+            self.finish()?;
+            self.writer_mut().write_all(b"\n")?;
+            Ok(text.len() + 1)
+        }
+        else {
+            Ok(text.len())
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.finish()?;
+        self.writer_mut().flush()
+    }
+}
+
+impl<W: Write> Drop for PaintedWriter<W> {
+    fn drop(&mut self) {
+        if self.writer.is_some() {
+            let _ = self.flush();
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use style::Colour::*;
+
+    #[test]
+    fn resets_on_flush() {
+        let mut out = PaintedWriter::new(Blue.bold(), Vec::new());
+        write!(out, "a").unwrap();
+        out.flush().unwrap();
+        out.write_all(b"b\n").unwrap();
+        assert_eq!(out.get_ref(), b"\x1B[1;34ma\x1B[0m\x1B[1;34mb\x1B[0m\n");
+    }
+
+    #[test]
+    fn resets_on_drop() {
+        let mut buf = Vec::new();
+        {
+            let mut out = PaintedWriter::new(Green.normal(), &mut buf);
+            write!(out, "a").unwrap();
+        }
+        assert_eq!(buf, b"\x1B[32ma\x1B[0m");
+    }
+
+    #[test]
+    fn plain_style_writes_no_codes() {
+        let mut out = PaintedWriter::new(Style::new(), Vec::new());
+        write!(out, "a\nb").unwrap();
+        assert_eq!(out.into_inner().unwrap(), b"a\nb");
+    }
+}
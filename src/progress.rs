@@ -0,0 +1,147 @@
+use display::ANSIString;
+use style::Style;
+
+
+/// The glyphs and styles used to draw a progress bar with [`progress_bar`].
+///
+/// [`progress_bar`]: fn.progress_bar.html
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct BarStyle {
+
+    /// The glyph for a column that is completely filled.
+    pub filled: char,
+
+    /// The glyphs for a partly-filled column, from least to most filled.
+    /// With none, the bar only ever moves a whole column at a time.
+    pub partial: &'static [char],
+
+    /// The glyph for a column with nothing in it yet.
+    pub empty: char,
+
+    /// The style of the filled columns.
+    pub filled_style: Style,
+
+    /// The style of the partly-filled column.
+    pub partial_style: Style,
+
+    /// The style of the empty columns.
+    pub empty_style: Style,
+}
+
+impl BarStyle {
+
+    /// A bar made of `#` and `-` characters, for terminals without Unicode.
+    pub fn ascii() -> BarStyle {
+        BarStyle { filled: '#', partial: &[], empty: '-', .. BarStyle::default() }
+    }
+}
+
+impl Default for BarStyle {
+
+    /// A smooth bar made of block elements, which can be filled an eighth of
+    /// a column at a time.
+    fn default() -> BarStyle {
+        BarStyle {
+            filled: '█',
+            partial: &[ '▏', '▎', '▍', '▌', '▋', '▊', '▉' ],
+            empty: ' ',
+            filled_style: Style::default(),
+            partial_style: Style::default(),
+            empty_style: Style::default(),
+        }
+    }
+}
+
+
+/// Renders a ratio between 0 and 1 as a progress bar of the given width, in
+/// columns.
+///
+/// The bar is returned as up to three fragments — the filled columns, the
+/// partly-filled column, and the empty columns — so it can be put in an
+/// `ANSIStrings` alongside other text and written with a minimum of control
+/// characters. Fragments with nothing in them are left out. Ratios outside
+/// the range get clamped to it.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{progress_bar, BarStyle, ANSIStrings};
+/// use ansi_term::Colour::{Green, Black};
+///
+/// let style = BarStyle {
+///     filled_style: Green.normal(),
+///     empty_style: Black.bold(),
+///     .. BarStyle::ascii()
+/// };
+///
+/// let bar = progress_bar(0.5, 6, &style);
+/// assert_eq!(ANSIStrings(&bar).to_string(),
+///            "\x1B[32m###\x1B[1;30m---\x1B[0m");
+/// ```
+pub fn progress_bar(ratio: f64, width: usize, style: &BarStyle) -> Vec<ANSIString<'static>> {
+    let ratio = if ratio.is_nan() { 0.0 } else { ratio.clamp(0.0, 1.0) };
+    let steps = style.partial.len() + 1;
+    let filled_steps = (ratio * (width * steps) as f64).floor() as usize;
+
+    let filled = filled_steps / steps;
+    let partial = match filled_steps % steps {
+        0     => None,
+        step  => Some(style.partial[step - 1]),
+    };
+    let empty = width - filled - partial.map_or(0, |_| 1);
+
+    let mut segments = Vec::with_capacity(3);
+
+    if filled > 0 {
+        segments.push(style.filled_style.paint(repeat(style.filled, filled)));
+    }
+
+    if let Some(glyph) = partial {
+        segments.push(style.partial_style.paint(glyph.to_string()));
+    }
+
+    if empty > 0 {
+        segments.push(style.empty_style.paint(repeat(style.empty, empty)));
+    }
+
+    segments
+}
+
+fn repeat(glyph: char, count: usize) -> String {
+    (0 .. count).map(|_| glyph).collect()
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use display::ANSIStrings;
+    use util::unstyle;
+
+    fn bar(ratio: f64, width: usize) -> String {
+        unstyle(&ANSIStrings(&progress_bar(ratio, width, &BarStyle::default())))
+    }
+
+    #[test]
+    fn partial_column() {
+        assert_eq!(bar(0.3, 4), "█▏  ");
+    }
+
+    #[test]
+    fn full_and_empty() {
+        assert_eq!(bar(1.0, 3), "███");
+        assert_eq!(bar(0.0, 3), "   ");
+    }
+
+    #[test]
+    fn clamped() {
+        assert_eq!(bar(7.5, 2), "██");
+        assert_eq!(bar(-1.0, 2), "  ");
+        assert_eq!(bar(f64::NAN, 2), "  ");
+    }
+
+    #[test]
+    fn empty_segments_left_out() {
+        assert_eq!(progress_bar(1.0, 2, &BarStyle::default()).len(), 1);
+    }
+}
@@ -0,0 +1,321 @@
+use std::io::{self, Read, Write};
+
+use depth::TerminalCapabilities;
+
+
+/// The code that asks the terminal where the cursor is (`CSI 6n`), which it
+/// replies to with `ESC [ row ; column R`.
+pub static REQUEST_CURSOR_POSITION: &str = "\x1B[6n";
+
+/// The code that asks the terminal for its primary device attributes
+/// (`DA1`), which it replies to with `ESC [ ? level ; attributes… c`.
+pub static REQUEST_DEVICE_ATTRIBUTES: &str = "\x1B[c";
+
+/// The code that asks the terminal for its name and version (`XTVERSION`),
+/// which it replies to with `ESC P > | name ESC \`, if it understands it.
+pub static REQUEST_TERMINAL_VERSION: &str = "\x1B[>0q";
+
+
+/// Asks the terminal where the cursor is, by writing the request to
+/// `output` and reading the reply from `input`, returning the row and the
+/// column, both counted from 1.
+///
+/// The terminal sends its reply as if it had been typed, so the terminal
+/// has to be in raw mode for this to work: otherwise the reply only gets
+/// read once the user presses Enter, and gets echoed to the screen too. Use
+/// [`RawMode`], or the [`cursor_position`] function that does it all, on
+/// Unix. Anything else the user typed before the reply gets skipped.
+///
+/// This function is only available with the `query` feature.
+///
+/// [`RawMode`]: struct.RawMode.html
+/// [`cursor_position`]: fn.cursor_position.html
+///
+/// # Errors
+///
+/// Returns an error of kind `TimedOut` if the input ends before the reply
+/// does, which is what happens when a terminal in raw mode with a timeout
+/// doesn’t reply, and `InvalidData` if the reply can’t be understood.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::query_cursor_position;
+///
+/// let mut output = Vec::new();
+/// let mut input = &b"\x1B[12;40R"[..];
+///
+/// assert_eq!(query_cursor_position(&mut input, &mut output).unwrap(), (12, 40));
+/// assert_eq!(output, b"\x1B[6n");
+/// ```
+pub fn query_cursor_position<R: Read, W: Write>(input: &mut R, output: &mut W) -> io::Result<(u16, u16)> {
+    output.write_all(REQUEST_CURSOR_POSITION.as_bytes())?;
+    output.flush()?;
+
+    let reply = read_reply(input, b'R')?;
+    parse_cursor_position(&reply)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid cursor position report"))
+}
+
+fn parse_cursor_position(params: &[u8]) -> Option<(u16, u16)> {
+    let params = std::str::from_utf8(params).ok()?;
+    let mut numbers = params.split(';').map(|n| n.parse().ok());
+
+    match (numbers.next(), numbers.next(), numbers.next()) {
+        (Some(Some(row)), Some(Some(col)), None)  => Some((row, col)),
+        _                                        => None,
+    }
+}
+
+/// Asks the terminal what it can do, by writing the `XTVERSION` and device
+/// attributes requests to `output` and reading the replies from `input`.
+///
+/// Every terminal replies to the device attributes request, but not every
+/// one replies to the version request, so the version reply only gets used
+/// if it comes first. See [`TerminalCapabilities::from_replies`] for how the
+/// replies get turned into capabilities.
+///
+/// Like [`query_cursor_position`], this needs the terminal to be in raw
+/// mode, which the [`capabilities`] function takes care of on Unix.
+///
+/// This function is only available with the `query` feature.
+///
+/// [`TerminalCapabilities::from_replies`]: struct.TerminalCapabilities.html#method.from_replies
+/// [`query_cursor_position`]: fn.query_cursor_position.html
+/// [`capabilities`]: fn.capabilities.html
+///
+/// # Errors
+///
+/// Returns an error of kind `TimedOut` if the input ends before the device
+/// attributes reply does.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{query_capabilities, ColourDepth};
+///
+/// let mut output = Vec::new();
+/// let mut input = &b"\x1BP>|WezTerm 20240203\x1B\\\x1B[?65;4;6;22c"[..];
+///
+/// let capabilities = query_capabilities(&mut input, &mut output).unwrap();
+/// assert_eq!(capabilities.colour_depth, ColourDepth::TrueColour);
+/// assert!(capabilities.underline_styles);
+/// assert_eq!(output, b"\x1B[>0q\x1B[c");
+/// ```
+pub fn query_capabilities<R: Read, W: Write>(input: &mut R, output: &mut W) -> io::Result<TerminalCapabilities> {
+    output.write_all(REQUEST_TERMINAL_VERSION.as_bytes())?;
+    output.write_all(REQUEST_DEVICE_ATTRIBUTES.as_bytes())?;
+    output.flush()?;
+
+    let mut version = None;
+    let attributes = loop {
+        if read_byte(input)? != 0x1B { continue }
+
+        match read_byte(input)? {
+            b'P'  => version = Some(read_string(input)?),
+            b'['  => break read_until(input, b'c')?,
+            _     => {},
+        }
+    };
+
+    let attributes: Vec<u16> = attributes.strip_prefix(b"?").unwrap_or(&attributes)
+        .split(|&b| b == b';')
+        .filter_map(|a| std::str::from_utf8(a).ok()?.parse().ok())
+        .collect();
+
+    let version = version.map(|v| String::from_utf8_lossy(v.strip_prefix(b">|").unwrap_or(&v)).into_owned());
+    Ok(TerminalCapabilities::from_replies(&attributes, version.as_deref()))
+}
+
+/// Reads a control sequence reply, skipping anything before its `ESC [`,
+/// and returns the bytes between that and its final byte.
+pub(crate) fn read_reply<R: Read>(input: &mut R, final_byte: u8) -> io::Result<Vec<u8>> {
+    let mut previous = 0;
+    loop {
+        let byte = read_byte(input)?;
+        if previous == 0x1B && byte == b'[' { break }
+        previous = byte;
+    }
+
+    read_until(input, final_byte)
+}
+
+/// Reads the bytes up to the given final byte, without including it.
+fn read_until<R: Read>(input: &mut R, final_byte: u8) -> io::Result<Vec<u8>> {
+    let mut reply = Vec::new();
+    loop {
+        match read_byte(input)? {
+            b if b == final_byte  => return Ok(reply),
+            b                     => reply.push(b),
+        }
+    }
+}
+
+/// Reads the rest of a control string, such as a `DCS` reply, up to the
+/// string terminator `ESC \`.
+fn read_string<R: Read>(input: &mut R) -> io::Result<Vec<u8>> {
+    let mut string = Vec::new();
+    loop {
+        match read_byte(input)? {
+            0x1B  => match read_byte(input)? {
+                b'\\'  => return Ok(string),
+                b      => string.extend_from_slice(&[ 0x1B, b ]),
+            },
+            b     => string.push(b),
+        }
+    }
+}
+
+fn read_byte<R: Read>(input: &mut R) -> io::Result<u8> {
+    let mut byte = [0];
+    loop {
+        match input.read(&mut byte) {
+            Ok(0)  => return Err(io::Error::new(io::ErrorKind::TimedOut, "the terminal didn’t reply")),
+            Ok(_)  => return Ok(byte[0]),
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {},
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+
+#[cfg(unix)]
+pub use self::unix::*;
+
+#[cfg(unix)]
+mod unix {
+    use std::fs::{File, OpenOptions};
+    use std::io;
+    use std::mem;
+    use std::os::unix::io::{AsRawFd, RawFd};
+
+    use libc;
+
+    use depth::TerminalCapabilities;
+    use super::{query_capabilities, query_cursor_position};
+
+
+    /// Puts a terminal into raw mode until it gets dropped, so it sends
+    /// every byte it receives straight to the program, without waiting for
+    /// Enter or echoing it.
+    ///
+    /// Reads from the terminal also give up after half a second without
+    /// any input, returning no bytes, so that waiting for a reply from a
+    /// terminal that doesn’t send one doesn’t block forever.
+    ///
+    /// This type is only available with the `query` feature, on Unix.
+    #[derive(Debug)]
+    pub struct RawMode {
+        fd: RawFd,
+        original: libc::termios,
+    }
+
+    impl RawMode {
+
+        /// Puts the terminal with the given file descriptor into raw mode.
+        pub fn enable<F: AsRawFd>(terminal: &F) -> io::Result<RawMode> {
+            let fd = terminal.as_raw_fd();
+
+            // SAFETY: `termios` is plain old data, and gets filled in by
+            // `tcgetattr` before it’s used.
+            let mut original: libc::termios = unsafe { mem::zeroed() };
+            if unsafe { libc::tcgetattr(fd, &mut original) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            let mut raw = original;
+            unsafe { libc::cfmakeraw(&mut raw) };
+            raw.c_cc[libc::VMIN] = 0;
+            raw.c_cc[libc::VTIME] = 5;
+
+            if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &raw) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(RawMode { fd, original })
+        }
+    }
+
+    impl Drop for RawMode {
+        fn drop(&mut self) {
+            unsafe { libc::tcsetattr(self.fd, libc::TCSANOW, &self.original) };
+        }
+    }
+
+    /// Opens the controlling terminal, which works even when standard input
+    /// or output has been redirected.
+    pub(crate) fn open_terminal() -> io::Result<File> {
+        OpenOptions::new().read(true).write(true).open("/dev/tty")
+    }
+
+    /// Asks the controlling terminal where the cursor is, returning the row
+    /// and the column, both counted from 1.
+    ///
+    /// This puts the terminal into raw mode while it waits for the reply,
+    /// and puts it back afterwards.
+    ///
+    /// This function is only available with the `query` feature, on Unix.
+    pub fn cursor_position() -> io::Result<(u16, u16)> {
+        let mut terminal = open_terminal()?;
+        let _raw = RawMode::enable(&terminal)?;
+        let mut output = terminal.try_clone()?;
+        query_cursor_position(&mut terminal, &mut output)
+    }
+
+    /// Asks the controlling terminal what it can do.
+    ///
+    /// This puts the terminal into raw mode while it waits for the replies,
+    /// and puts it back afterwards.
+    ///
+    /// This function is only available with the `query` feature, on Unix.
+    pub fn capabilities() -> io::Result<TerminalCapabilities> {
+        let mut terminal = open_terminal()?;
+        let _raw = RawMode::enable(&terminal)?;
+        let mut output = terminal.try_clone()?;
+        query_capabilities(&mut terminal, &mut output)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use depth::ColourDepth;
+
+    #[test]
+    fn typed_input_is_skipped() {
+        let mut input = &b"ab\x1B[3;7R"[..];
+        assert_eq!(query_cursor_position(&mut input, &mut Vec::new()).unwrap(), (3, 7));
+    }
+
+    #[test]
+    fn no_reply() {
+        let mut input = &b""[..];
+        let error = query_cursor_position(&mut input, &mut Vec::new()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn invalid_reply() {
+        let mut input = &b"\x1B[3R"[..];
+        let error = query_cursor_position(&mut input, &mut Vec::new()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn device_attributes_without_version() {
+        let mut input = &b"\x1B[?62;1;22c"[..];
+        let capabilities = query_capabilities(&mut input, &mut Vec::new()).unwrap();
+        assert_eq!(capabilities.colour_depth, ColourDepth::Ansi16);
+        assert_eq!(capabilities.version, None);
+    }
+
+    #[test]
+    fn version_with_parentheses() {
+        let mut input = &b"\x1BP>|XTerm(390)\x1B\\\x1B[?64;1;2;22c"[..];
+        let capabilities = query_capabilities(&mut input, &mut Vec::new()).unwrap();
+        assert_eq!(capabilities.version.as_deref(), Some("XTerm(390)"));
+        assert_eq!(capabilities.colour_depth, ColourDepth::Ansi256);
+        assert!(!capabilities.hyperlinks);
+    }
+}
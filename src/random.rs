@@ -0,0 +1,104 @@
+//! Random colours and styles with `rand`, available with the `rand`
+//! feature.
+
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+use style::{Colour, Style};
+
+
+/// Which kind of colour to pick at random, as a distribution to sample
+/// colours from.
+///
+/// This type is only available with the `rand` feature.
+///
+/// # Examples
+///
+/// ```
+/// extern crate rand;
+/// use ansi_term::{Colour, RandomColours};
+/// use rand::Rng;
+/// use rand::rngs::mock::StepRng;
+///
+/// let mut rng = StepRng::new(0, 1 << 61);
+/// let colour: Colour = rng.sample(RandomColours::Basic);
+/// assert!(Colour::basic_iter().any(|c| c == colour));
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum RandomColours {
+
+    /// One of the eight named colours, which every colour terminal can show.
+    Basic,
+
+    /// Any colour from the 256-colour palette.
+    #[cfg(feature = "fixed")]
+    Fixed,
+
+    /// Any 24-bit colour.
+    #[cfg(feature = "rgb")]
+    RGB,
+}
+
+impl Distribution<Colour> for RandomColours {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Colour {
+        match *self {
+            RandomColours::Basic  => Colour::basic_iter().nth(rng.gen_range(0 .. 8)).unwrap(),
+            #[cfg(feature = "fixed")]
+            RandomColours::Fixed  => Colour::Fixed(rng.gen()),
+            #[cfg(feature = "rgb")]
+            RandomColours::RGB    => Colour::RGB(rng.gen(), rng.gen(), rng.gen()),
+        }
+    }
+}
+
+/// Picks one of the kinds of colour the crate was built with, and then a
+/// colour of that kind.
+impl Distribution<Colour> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Colour {
+        let kinds = [
+            RandomColours::Basic,
+            #[cfg(feature = "fixed")]
+            RandomColours::Fixed,
+            #[cfg(feature = "rgb")]
+            RandomColours::RGB,
+        ];
+
+        kinds[rng.gen_range(0 .. kinds.len())].sample(rng)
+    }
+}
+
+/// Picks a style with a random foreground colour, which may also be bold,
+/// italic, or underlined.
+///
+/// The styles never have a background colour, or blink, or get reversed
+/// or hidden, so that text painted with any of them can still be read.
+impl Distribution<Style> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Style {
+        Style {
+            foreground:    Some(rng.gen()),
+            is_bold:       rng.gen(),
+            is_italic:     rng.gen(),
+            is_underline:  rng.gen(),
+            .. Style::default()
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::mock::StepRng;
+
+    #[test]
+    fn styles_stay_readable() {
+        let mut rng = StepRng::new(1, 0x9E37_79B9_7F4A_7C15);
+
+        for _ in 0 .. 100 {
+            let style: Style = rng.gen();
+            assert!(style.foreground.is_some());
+            assert_eq!(style.background, None);
+            assert!(!style.is_blink && !style.is_reverse && !style.is_hidden);
+        }
+    }
+}
@@ -0,0 +1,259 @@
+use std::io::{self, Write};
+use std::str;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use depth::ColourDepth;
+use sgr::{self, Token};
+use style::Style;
+
+
+/// A writer that records the styled text written through it, along with
+/// when it was written, while passing it on to another writer.
+///
+/// The [`Recording`] it makes can be replayed later, with the same timing
+/// or all at once, and at a different colour depth, which helps with
+/// debugging a program’s output. Its spans can also be compared in tests.
+/// To record without writing anything anywhere else, use `io::sink()`.
+///
+/// Escape sequences that aren’t colour codes get recorded as part of the
+/// text.
+///
+/// [`Recording`]: struct.Recording.html
+///
+/// # Examples
+///
+/// ```
+/// use std::io::{self, Write};
+/// use ansi_term::Recorder;
+/// use ansi_term::Colour::Red;
+///
+/// let mut recorder = Recorder::new(io::sink());
+/// write!(recorder, "{} disk full", Red.paint("error:")).unwrap();
+///
+/// let (_, recording) = recorder.finish();
+/// assert_eq!(recording.to_spans(), vec![
+///     (Red.normal(), "error:".to_string()),
+///     (Default::default(), " disk full".to_string()),
+/// ]);
+/// ```
+#[derive(Debug)]
+pub struct Recorder<W: Write> {
+    writer: W,
+    start: Instant,
+    recording: Recording,
+
+    /// The style the text written is in.
+    style: Style,
+
+    /// Bytes at the end of the last write that couldn’t be recorded yet,
+    /// because they’re part of a code or character that hasn’t been
+    /// finished.
+    pending: Vec<u8>,
+}
+
+/// Styled text that a [`Recorder`] recorded.
+///
+/// [`Recorder`]: struct.Recorder.html
+#[derive(PartialEq, Clone, Default, Debug)]
+pub struct Recording {
+
+    /// The spans of text, in the order they were written.
+    pub spans: Vec<RecordedSpan>,
+}
+
+/// A piece of text in one style, recorded from one write.
+#[derive(PartialEq, Clone, Debug)]
+pub struct RecordedSpan {
+
+    /// How long after recording started the text was written.
+    pub time: Duration,
+
+    /// The style of the text.
+    pub style: Style,
+
+    /// The text itself.
+    pub text: String,
+}
+
+impl<W: Write> Recorder<W> {
+
+    /// Returns a recorder that records the styled text written to the given
+    /// writer, starting the clock now.
+    pub fn new(writer: W) -> Recorder<W> {
+        Recorder {
+            writer,
+            start: Instant::now(),
+            recording: Recording::default(),
+            style: Style::default(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Returns what has been recorded so far.
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// Stops recording, returning the underlying writer and the recording.
+    pub fn finish(self) -> (W, Recording) {
+        (self.writer, self.recording)
+    }
+
+    /// Records as much of the pending bytes as can be.
+    fn record(&mut self, time: Duration) {
+        let mut offset = 0;
+
+        while let Some((token, len)) = sgr::next_token(&self.pending[offset ..]) {
+            match token {
+                Token::Sgr(params)  => sgr::apply_all(&mut self.style, params),
+                Token::Other(code)  => self.recording.push(time, self.style, &String::from_utf8_lossy(code)),
+                Token::Text(text)   => match str::from_utf8(text) {
+                    Ok(text)  => self.recording.push(time, self.style, text),
+                    Err(e) if e.error_len().is_none() && offset + len == self.pending.len() => {
+                        let valid = e.valid_up_to();
+                        let text = str::from_utf8(&text[.. valid]).expect("valid up to here");
+                        self.recording.push(time, self.style, text);
+                        offset += valid;
+                        break;
+                    },
+                    Err(_)    => self.recording.push(time, self.style, &String::from_utf8_lossy(text)),
+                },
+            }
+
+            offset += len;
+        }
+
+        self.pending.drain(.. offset);
+    }
+}
+
+impl<W: Write> Write for Recorder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.writer.write(buf)?;
+        self.pending.extend_from_slice(&buf[.. len]);
+        let time = self.start.elapsed();
+        self.record(time);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl Recording {
+
+    /// Returns the recorded text as a list of styles paired with text,
+    /// without the times, and with adjacent spans that have the same style
+    /// merged, for comparing against what a test expects.
+    pub fn to_spans(&self) -> Vec<(Style, String)> {
+        let mut spans: Vec<(Style, String)> = Vec::new();
+
+        for span in &self.spans {
+            match spans.last_mut() {
+                Some(last) if last.0 == span.style  => last.1.push_str(&span.text),
+                _                                   => spans.push((span.style, span.text.clone())),
+            }
+        }
+
+        spans
+    }
+
+    /// Writes the recorded text to the given writer all at once, with the
+    /// minimum of codes, and with its colours replaced by the closest ones a
+    /// terminal with the given colour depth can show.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "rgb")] {
+    /// use std::io::{self, Write};
+    /// use ansi_term::{ColourDepth, Recorder};
+    /// use ansi_term::Colour::RGB;
+    ///
+    /// let mut recorder = Recorder::new(io::sink());
+    /// write!(recorder, "{}", RGB(250, 5, 5).paint("hot")).unwrap();
+    /// let (_, recording) = recorder.finish();
+    ///
+    /// let mut output = Vec::new();
+    /// recording.replay(&mut output, ColourDepth::Ansi16).unwrap();
+    /// assert_eq!(output, b"\x1B[31mhot\x1B[0m");
+    /// # }
+    /// ```
+    pub fn replay<W: Write>(&self, writer: W, depth: ColourDepth) -> io::Result<()> {
+        self.play(writer, depth, None)
+    }
+
+    /// Writes the recorded text to the given writer as
+    /// [`replay`](#method.replay) does, but waits before writing each span
+    /// until as long has passed as had when it was recorded.
+    pub fn replay_in_real_time<W: Write>(&self, writer: W, depth: ColourDepth) -> io::Result<()> {
+        self.play(writer, depth, Some(Instant::now()))
+    }
+
+    fn play<W: Write>(&self, mut writer: W, depth: ColourDepth, start: Option<Instant>) -> io::Result<()> {
+        let mut written = Style::default();
+
+        for span in &self.spans {
+            if let Some(start) = start {
+                if let Some(wait) = span.time.checked_sub(start.elapsed()) {
+                    writer.flush()?;
+                    thread::sleep(wait);
+                }
+            }
+
+            let style = span.style.to_depth(depth);
+            write!(writer, "{}{}", written.infix(style), span.text)?;
+            written = style;
+        }
+
+        write!(writer, "{}", written.infix(Style::default()))?;
+        writer.flush()
+    }
+
+    /// Adds some text to the end, as part of the last span if it was
+    /// written at the same time in the same style.
+    fn push(&mut self, time: Duration, style: Style, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+
+        match self.spans.last_mut() {
+            Some(last) if last.time == time && last.style == style => last.text.push_str(text),
+            _ => self.spans.push(RecordedSpan { time, style, text: String::from(text) }),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use style::Colour::*;
+
+    #[test]
+    fn split_codes_and_characters() {
+        let mut recorder = Recorder::new(Vec::new());
+        for chunk in &[ &b"\x1B[3"[..], b"4ma\xC3", b"\xA9\x1B[0mb" ] {
+            recorder.write_all(chunk).unwrap();
+        }
+
+        let (output, recording) = recorder.finish();
+        assert_eq!(output, "\x1B[34maé\x1B[0mb".as_bytes());
+        assert_eq!(recording.to_spans(), vec![ (Blue.normal(), "aé".to_string()), (Style::default(), "b".to_string()) ]);
+    }
+
+    #[test]
+    fn replay_minimises_codes() {
+        let recording = Recording { spans: vec![
+            RecordedSpan { time: Duration::from_millis(0), style: Red.normal(), text: "a".into() },
+            RecordedSpan { time: Duration::from_millis(5), style: Red.normal(), text: "b".into() },
+            RecordedSpan { time: Duration::from_millis(9), style: Red.bold(), text: "c".into() },
+        ]};
+
+        let mut output = Vec::new();
+        recording.replay(&mut output, ColourDepth::Monochrome).unwrap();
+        assert_eq!(output, b"ab\x1B[1mc\x1B[0m");
+    }
+}
@@ -0,0 +1,149 @@
+use std::io::{self, IsTerminal, Write};
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Once;
+
+use ansi::RESET;
+
+
+/// The code that switches the terminal to its alternate screen buffer, as
+/// used by full-screen programs (private mode 1049).
+pub static ENTER_ALTERNATE_SCREEN: &str = "\x1B[?1049h";
+
+/// The code that switches the terminal back from its alternate screen buffer
+/// to the normal one.
+pub static LEAVE_ALTERNATE_SCREEN: &str = "\x1B[?1049l";
+
+/// How many `ResetGuard`s have entered the alternate screen and not left it
+/// yet, so the panic hook knows whether it has to leave it too.
+static ALTERNATE_SCREENS: AtomicUsize = AtomicUsize::new(0);
+
+
+/// A guard that resets the terminal back to its normal style when it gets
+/// dropped, so an early return or a panic in the middle of painting doesn’t
+/// leave the user’s terminal red and bold.
+///
+/// If the guard was created with [`alternate_screen`], it also leaves the
+/// alternate screen again.
+///
+/// [`alternate_screen`]: #method.alternate_screen
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+/// use ansi_term::{ResetGuard, Colour::Red};
+///
+/// let mut out = Vec::new();
+/// {
+///     let mut guard = ResetGuard::new(&mut out);
+///     write!(guard, "{}", Red.prefix()).unwrap();
+/// }
+///
+/// assert_eq!(out, b"\x1B[31m\x1B[0m");
+/// ```
+#[derive(Debug)]
+pub struct ResetGuard<W: Write> {
+    writer: W,
+    alternate_screen: bool,
+}
+
+impl<W: Write> ResetGuard<W> {
+
+    /// Returns a guard that writes the reset code to the given writer when it
+    /// gets dropped.
+    pub fn new(writer: W) -> ResetGuard<W> {
+        ResetGuard { writer, alternate_screen: false }
+    }
+
+    /// Switches the writer to the alternate screen, returning a guard that
+    /// switches it back (and resets the style) when it gets dropped.
+    ///
+    /// While this guard is alive, the hook set up by [`install_reset_hook`]
+    /// leaves the alternate screen too.
+    ///
+    /// [`install_reset_hook`]: fn.install_reset_hook.html
+    pub fn alternate_screen(mut writer: W) -> io::Result<ResetGuard<W>> {
+        writer.write_all(ENTER_ALTERNATE_SCREEN.as_bytes())?;
+        writer.flush()?;
+        ALTERNATE_SCREENS.fetch_add(1, Ordering::SeqCst);
+        Ok(ResetGuard { writer, alternate_screen: true })
+    }
+}
+
+impl<W: Write> Write for ResetGuard<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl<W: Write> Drop for ResetGuard<W> {
+    fn drop(&mut self) {
+        let _ = self.writer.write_all(RESET.as_bytes());
+
+        if self.alternate_screen {
+            ALTERNATE_SCREENS.fetch_sub(1, Ordering::SeqCst);
+            let _ = self.writer.write_all(LEAVE_ALTERNATE_SCREEN.as_bytes());
+        }
+
+        let _ = self.writer.flush();
+    }
+}
+
+
+/// Installs a panic hook that resets the terminal style before the panic
+/// message gets printed, leaving the alternate screen first if a
+/// [`ResetGuard`] has entered it.
+///
+/// The codes only get written to standard output and standard error when
+/// they are terminals, so redirected output doesn’t end up with stray codes
+/// in it. The previous panic hook gets run afterwards, and installing the
+/// hook more than once has no further effect.
+///
+/// [`ResetGuard`]: struct.ResetGuard.html
+pub fn install_reset_hook() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            reset_terminal(&mut io::stdout());
+            reset_terminal(&mut io::stderr());
+            previous(info);
+        }));
+    });
+}
+
+fn reset_terminal<W: Write + IsTerminal>(stream: &mut W) {
+    if !stream.is_terminal() {
+        return;
+    }
+
+    let _ = stream.write_all(RESET.as_bytes());
+    if ALTERNATE_SCREENS.load(Ordering::SeqCst) > 0 {
+        let _ = stream.write_all(LEAVE_ALTERNATE_SCREEN.as_bytes());
+    }
+    let _ = stream.flush();
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn alternate_screen() {
+        let mut out = Vec::new();
+        {
+            let mut guard = ResetGuard::alternate_screen(&mut out).unwrap();
+            guard.write_all(b"hi").unwrap();
+        }
+
+        assert_eq!(out, b"\x1B[?1049hhi\x1B[0m\x1B[?1049l");
+    }
+}
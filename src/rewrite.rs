@@ -0,0 +1,154 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+
+use sgr::{self, Token};
+use style::Style;
+
+
+/// Reads text with escape codes in it, such as the output of another
+/// program, and writes it back out with the style of each fragment changed
+/// by the given function, for recolouring, theming, or redacting it.
+///
+/// The function gets called with the style and the text of each fragment
+/// between two colour codes, without their newlines, and can change either
+/// of them. The output gets written with the minimum of codes between the
+/// new styles, and every line of it ends in the plain style, so it can be
+/// read one line at a time.
+///
+/// Escape sequences that aren’t colour codes, such as cursor movements, get
+/// written as they are. Colour codes for things a `Style` can’t represent
+/// get left out, and text that isn’t valid UTF-8 gets replaced with `�`.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::rewrite;
+/// use ansi_term::Colour::{Blue, Red};
+///
+/// let input = b"\x1B[31merror:\x1B[0m disk \x1B[1;31mfull\x1B[0m\n";
+/// let mut output = Vec::new();
+///
+/// rewrite(&input[..], &mut output, |style, _text| {
+///     if style.foreground == Some(Red) {
+///         style.foreground = Some(Blue);
+///     }
+/// }).unwrap();
+///
+/// assert_eq!(output, b"\x1B[34merror:\x1B[0m disk \x1B[1;34mfull\x1B[0m\n");
+/// ```
+pub fn rewrite<R, W, F>(input: R, output: W, f: F) -> io::Result<()>
+where R: Read, W: Write, F: FnMut(&mut Style, &mut String) {
+    let mut input = BufReader::new(input);
+    let mut rewriter = Rewriter { output, f, read: Style::default(), written: Style::default() };
+    let mut line = Vec::new();
+
+    while input.read_until(b'\n', &mut line)? > 0 {
+        rewriter.line(&line)?;
+        line.clear();
+    }
+
+    rewriter.reset()?;
+    rewriter.output.flush()
+}
+
+
+/// The state of the output as a rewrite goes on.
+struct Rewriter<W, F> {
+    output: W,
+    f: F,
+
+    /// The style the input is in.
+    read: Style,
+
+    /// The style the output is in.
+    written: Style,
+}
+
+impl<W: Write, F: FnMut(&mut Style, &mut String)> Rewriter<W, F> {
+
+    /// Rewrites one line of input, including its newline if it has one.
+    fn line(&mut self, mut line: &[u8]) -> io::Result<()> {
+        let newline = line.last() == Some(&b'\n');
+        if newline {
+            line = &line[.. line.len() - 1];
+        }
+
+        while let Some((token, len)) = sgr::next_token(line) {
+            match token {
+                Token::Text(text)    => self.text(text)?,
+                Token::Sgr(params)   => sgr::apply_all(&mut self.read, params),
+                Token::Other(code)   => self.output.write_all(code)?,
+            }
+
+            line = &line[len ..];
+        }
+
+        // A control sequence that hasn’t been finished is kept as text.
+        self.text(line)?;
+
+        if newline {
+            self.reset()?;
+            self.output.write_all(b"\n")?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes a fragment of text in the style the function gives it.
+    fn text(&mut self, text: &[u8]) -> io::Result<()> {
+        if text.is_empty() {
+            return Ok(());
+        }
+
+        let mut style = self.read;
+        let mut text = String::from_utf8_lossy(text).into_owned();
+        (self.f)(&mut style, &mut text);
+
+        if !text.is_empty() {
+            write!(self.output, "{}{}", self.written.infix(style), text)?;
+            self.written = style;
+        }
+
+        Ok(())
+    }
+
+    /// Puts the output back in the plain style.
+    fn reset(&mut self) -> io::Result<()> {
+        write!(self.output, "{}", self.written.infix(Style::default()))?;
+        self.written = Style::default();
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn run<F: FnMut(&mut Style, &mut String)>(input: &str, f: F) -> String {
+        let mut output = Vec::new();
+        rewrite(input.as_bytes(), &mut output, f).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn minimises_codes() {
+        assert_eq!(run("\x1B[31ma\x1B[0m\x1B[31mb\x1B[0m", |_, _| {}), "\x1B[31mab\x1B[0m");
+    }
+
+    #[test]
+    fn redacts_text() {
+        assert_eq!(run("key: \x1B[1msecret\x1B[0m\n", |style, text| if style.is_bold { *text = "***".into() }),
+                   "key: \x1B[1m***\x1B[0m\n");
+    }
+
+    #[test]
+    fn style_carries_across_lines() {
+        assert_eq!(run("\x1B[32ma\nb\x1B[2Kc", |_, _| {}), "\x1B[32ma\x1B[0m\n\x1B[32mb\x1B[2Kc\x1B[0m");
+    }
+
+    #[test]
+    fn removed_styles() {
+        assert_eq!(run("\x1B[4;34ma", |style, _| *style = Style::default()), "a");
+        assert_eq!(run("a\x1B[1;34mb", |style, _| style.is_bold = false), "a\x1B[34mb\x1B[0m");
+    }
+}
@@ -0,0 +1,246 @@
+use std::io;
+
+use ansi::RESET;
+use display::ANSIStrings;
+use style::Style;
+use styled_text::StyledText;
+
+
+/// A renderer that remembers the last frame it drew, and draws each new
+/// frame by only writing the characters that changed.
+///
+/// This is enough for status dashboards and progress displays that redraw
+/// themselves many times a second, without pulling in a full terminal UI
+/// framework. A frame is a list of lines of [`StyledText`]; row *n* of the
+/// frame gets drawn on row *n* of the screen, counting from zero.
+///
+/// For every run of characters that differ from the previous frame, the
+/// renderer moves the cursor to the start of the run (unless it’s already
+/// there) and writes the run, changing the style only when it needs to.
+/// Lines that got shorter have the rest of their row erased, and rows
+/// beyond the end of a shorter frame get erased completely.
+///
+/// Each character counts as one column.
+///
+/// [`StyledText`]: struct.StyledText.html
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{Screen, StyledText, Style};
+/// use ansi_term::Colour::Green;
+///
+/// let mut line = StyledText::new();
+/// line.push(Style::default(), "done: 10%");
+///
+/// let mut screen = Screen::new();
+/// let mut out = Vec::new();
+/// screen.render(&[ line.clone() ], &mut out).unwrap();
+///
+/// line.delete(6 .. 8);
+/// line.insert(6, Green.normal(), "50");
+///
+/// out.clear();
+/// screen.render(&[ line ], &mut out).unwrap();
+/// assert_eq!(out, b"\x1B[1;7H\x1B[32m50\x1B[0m");
+/// ```
+#[derive(PartialEq, Clone, Default, Debug)]
+pub struct Screen {
+    previous: Vec<Vec<(Style, char)>>,
+}
+
+impl Screen {
+
+    /// Creates a renderer that assumes the screen is blank, so the first
+    /// frame gets drawn in full.
+    pub fn new() -> Screen {
+        Screen::default()
+    }
+
+    /// Forgets the previous frame, so the next one gets drawn in full, such
+    /// as after the screen has been cleared by something else.
+    pub fn invalidate(&mut self) {
+        self.previous.clear();
+    }
+
+    /// Draws the given frame, writing only the changes since the previous
+    /// one, and remembers it for next time.
+    pub fn render<W: io::Write>(&mut self, frame: &[StyledText], w: &mut W) -> io::Result<()> {
+        let frame: Vec<Vec<(Style, char)>> = frame.iter().map(cells).collect();
+        let mut cursor = None;
+        let mut current = Style::default();
+
+        for row in 0 .. frame.len().max(self.previous.len()) {
+            let new = frame.get(row).map(|r| &r[..]).unwrap_or(&[]);
+            let old = self.previous.get(row).map(|r| &r[..]).unwrap_or(&[]);
+
+            for (column, &(style, c)) in new.iter().enumerate() {
+                if old.get(column) == Some(&(style, c)) {
+                    continue;
+                }
+
+                move_to(w, &mut cursor, row, column)?;
+                write!(w, "{}{}", current.infix(style), c)?;
+                current = style;
+                cursor = Some((row, column + 1));
+            }
+
+            if old.len() > new.len() {
+                move_to(w, &mut cursor, row, new.len())?;
+
+                // Erasing fills with the current background colour, so the
+                // style has to be reset first.
+                if !current.is_plain() {
+                    w.write_all(RESET.as_bytes())?;
+                    current = Style::default();
+                }
+
+                w.write_all(b"\x1B[K")?;
+            }
+        }
+
+        if !current.is_plain() {
+            w.write_all(RESET.as_bytes())?;
+        }
+
+        self.previous = frame;
+        Ok(())
+    }
+}
+
+/// Changes a line that has already been written into a new one, writing
+/// only the part from the first character that changed to the end, for
+/// progress and status lines that get redrawn every tick.
+///
+/// The cursor has to be at the end of the previous line, where writing it
+/// left it, and is left at the end of the new one. The cursor gets moved
+/// back to the first character that changed, the rest of the new line gets
+/// written, and if the new line is shorter, the rest of the old one gets
+/// erased. Nothing gets written if the lines are the same.
+///
+/// Each character counts as one column, and both lines have to fit in the
+/// width of the terminal.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{redraw_line, ANSIStrings};
+/// use ansi_term::Colour::{Green, Red};
+///
+/// let before = [ Green.paint("ok: "), "10 files".into() ];
+/// let after = [ Green.paint("ok: "), "12 files".into(), Red.paint(" (1 error)") ];
+///
+/// let mut out = Vec::new();
+/// redraw_line(&ANSIStrings(&before), &ANSIStrings(&after), &mut out).unwrap();
+/// assert_eq!(out, b"\x1B[7D2 files\x1B[31m (1 error)\x1B[0m");
+/// ```
+pub fn redraw_line<W: io::Write>(previous: &ANSIStrings, next: &ANSIStrings, w: &mut W) -> io::Result<()> {
+    let old: Vec<(char, Style)> = previous.chars().collect();
+    let new: Vec<(char, Style)> = next.chars().collect();
+    let start = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+
+    if start == old.len() && start == new.len() {
+        return Ok(());
+    }
+
+    // A movement of zero columns moves one column in most terminals, so
+    // it has to be left out.
+    if old.len() > start {
+        write!(w, "\x1B[{}D", old.len() - start)?;
+    }
+
+    let mut current = Style::default();
+    for &(c, style) in &new[start ..] {
+        write!(w, "{}{}", current.infix(style), c)?;
+        current = style;
+    }
+
+    write!(w, "{}", current.infix(Style::default()))?;
+
+    if old.len() > new.len() {
+        w.write_all(b"\x1B[K")?;
+    }
+
+    Ok(())
+}
+
+fn cells(line: &StyledText) -> Vec<(Style, char)> {
+    line.fragments().iter()
+        .flat_map(|s| { let style = *s.style_ref(); s.chars().map(move |c| (style, c)) })
+        .collect()
+}
+
+fn move_to<W: io::Write>(w: &mut W, cursor: &mut Option<(usize, usize)>, row: usize, column: usize) -> io::Result<()> {
+    if *cursor != Some((row, column)) {
+        write!(w, "\x1B[{};{}H", row + 1, column + 1)?;
+        *cursor = Some((row, column));
+    }
+
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use display::ANSIString;
+    use style::Colour::*;
+
+    fn line(style: Style, text: &str) -> StyledText {
+        let mut line = StyledText::new();
+        line.push(style, text);
+        line
+    }
+
+    fn render(screen: &mut Screen, frame: &[StyledText]) -> String {
+        let mut out = Vec::new();
+        screen.render(frame, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn first_frame_in_full() {
+        let mut screen = Screen::new();
+        assert_eq!(render(&mut screen, &[ line(Red.normal(), "ab"), line(Style::default(), "c") ]),
+                   "\x1B[1;1H\x1B[31mab\x1B[2;1H\x1B[0mc");
+    }
+
+    #[test]
+    fn same_frame_writes_nothing() {
+        let mut screen = Screen::new();
+        let frame = [ line(Red.normal(), "ab") ];
+        render(&mut screen, &frame);
+        assert_eq!(render(&mut screen, &frame), "");
+    }
+
+    #[test]
+    fn restyle_only() {
+        let mut screen = Screen::new();
+        render(&mut screen, &[ line(Red.normal(), "abc") ]);
+        assert_eq!(render(&mut screen, &[ line(Red.bold(), "abc") ]), "\x1B[1;1H\x1B[1;31mabc\x1B[0m");
+    }
+
+    #[test]
+    fn shorter_frame() {
+        let mut screen = Screen::new();
+        render(&mut screen, &[ line(Blue.normal(), "abc"), line(Blue.normal(), "def") ]);
+        assert_eq!(render(&mut screen, &[ line(Blue.normal(), "a") ]), "\x1B[1;2H\x1B[K\x1B[2;1H\x1B[K");
+    }
+
+    fn redraw(previous: &[ANSIString], next: &[ANSIString]) -> String {
+        let mut out = Vec::new();
+        redraw_line(&ANSIStrings(previous), &ANSIStrings(next), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn redraw_shorter_line() {
+        assert_eq!(redraw(&[ Red.paint("abc"), Blue.paint("def") ], &[ Red.paint("ab") ]), "\x1B[4D\x1B[K");
+        assert_eq!(redraw(&[ Red.paint("abc") ], &[ Red.paint("abc") ]), "");
+    }
+
+    #[test]
+    fn redraw_restyled_tail() {
+        assert_eq!(redraw(&[ Red.paint("abc") ], &[ Red.paint("a"), Red.bold().paint("bcd") ]), "\x1B[2D\x1B[1;31mbcd\x1B[0m");
+    }
+}
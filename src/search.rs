@@ -0,0 +1,298 @@
+use std::ops::Range;
+
+use display::{ANSIString, ANSIStrings};
+use style::Style;
+use util::unstyle;
+
+
+/// Returns a copy of `strs` with every occurrence of `pattern` in its text
+/// painted with the `overlay` style on top of its existing style.
+///
+/// Matches are found in the text without any of its styles, so they can
+/// span more than one fragment, and each part of the match keeps whatever
+/// its own style doesn’t get overridden by the overlay: the overlay’s
+/// colours replace the existing ones if it has any, and its properties
+/// (such as bold or underline) are added to them.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{highlight_matches, ANSIStrings, Style};
+/// use ansi_term::Colour::{Red, Blue};
+///
+/// let line = [ Red.paint("error: "), Blue.paint("no such file") ];
+/// let hits = highlight_matches(&ANSIStrings(&line), "r: no", Style::new().reverse());
+///
+/// assert_eq!(hits, vec![
+///     Red.paint("erro"), Red.reverse().paint("r: "),
+///     Blue.reverse().paint("no"), Blue.paint(" such file"),
+/// ]);
+/// ```
+pub fn highlight_matches(strs: &ANSIStrings, pattern: &str, overlay: Style) -> Vec<ANSIString<'static>> {
+    highlight_ranges(strs, match_ranges(strs, pattern), overlay)
+}
+
+/// Returns a copy of `strs` with every match of the regular expression
+/// painted with the `overlay` style on top of its existing style, in the
+/// same way as [`highlight_matches`].
+///
+/// This function is only available with the `regex` feature.
+///
+/// [`highlight_matches`]: fn.highlight_matches.html
+///
+/// # Examples
+///
+/// ```
+/// extern crate regex;
+/// extern crate ansi_term;
+/// use regex::Regex;
+/// use ansi_term::{highlight_regex_matches, ANSIStrings, Style};
+/// use ansi_term::Colour::Yellow;
+///
+/// let line = [ Style::default().paint("took 15ms, then 3ms") ];
+/// let numbers = Regex::new("[0-9]+").unwrap();
+/// let hits = highlight_regex_matches(&ANSIStrings(&line), &numbers, Yellow.bold());
+///
+/// assert_eq!(ANSIStrings(&hits).to_string(),
+///            "took \x1B[1;33m15\x1B[0mms, then \x1B[1;33m3\x1B[0mms");
+/// ```
+#[cfg(feature = "regex")]
+pub fn highlight_regex_matches(strs: &ANSIStrings, regex: &::regex::Regex, overlay: Style) -> Vec<ANSIString<'static>> {
+    let text = unstyle(strs);
+    let ranges = regex.find_iter(&text).map(|m| m.range()).collect();
+    highlight_ranges(strs, ranges, overlay)
+}
+
+impl<'a> ANSIStrings<'a> {
+
+    /// Returns a copy of these strings with every occurrence of `pattern` in
+    /// their text replaced by the `replacement` fragments.
+    ///
+    /// Matches are found in the text without any of its styles, so they can
+    /// span more than one fragment. The text around each match keeps its
+    /// style, and the replacement keeps its own. An empty pattern doesn’t
+    /// match anything.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::ANSIStrings;
+    /// use ansi_term::Colour::{Red, Black};
+    ///
+    /// let line = [ Red.paint("password: hunter2") ];
+    /// let redacted = ANSIStrings(&line).replace("hunter2", &[ Black.bold().paint("*******") ]);
+    ///
+    /// assert_eq!(redacted, vec![ Red.paint("password: "), Black.bold().paint("*******") ]);
+    /// ```
+    pub fn replace(&self, pattern: &str, replacement: &[ANSIString]) -> Vec<ANSIString<'static>> {
+        let mut vec = Vec::new();
+        let mut replaced = None;
+
+        for (style, text, hit) in split_ranges(self, match_ranges(self, pattern)) {
+            match hit {
+                None => {
+                    vec.push(style.paint(String::from(text)));
+                },
+                Some(index) if replaced != Some(index) => {
+                    vec.extend(replacement.iter().map(|r| r.style_ref().paint(String::from(&r[..]))));
+                    replaced = Some(index);
+                },
+                Some(_) => {},
+            }
+        }
+
+        vec
+    }
+
+    /// Returns the first occurrence of `pattern` in the text of these
+    /// strings, along with the styles it’s painted in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::ANSIStrings;
+    /// use ansi_term::Colour::{Red, Blue};
+    ///
+    /// let line = [ Red.paint("src/"), Blue.bold().paint("main.rs") ];
+    /// let hit = ANSIStrings(&line).find("main").unwrap();
+    ///
+    /// assert_eq!(hit.range, 4 .. 8);
+    /// assert_eq!(hit.style(), Some(Blue.bold()));
+    /// ```
+    pub fn find(&self, pattern: &str) -> Option<StyledMatch> {
+        let mut ranges = match_ranges(self, pattern);
+        ranges.truncate(1);
+        styled_matches(self, ranges).pop()
+    }
+
+    /// Returns every occurrence of `pattern` in the text of these strings,
+    /// in order, along with the styles they’re painted in. An empty pattern
+    /// doesn’t match anything.
+    pub fn find_all(&self, pattern: &str) -> Vec<StyledMatch> {
+        styled_matches(self, match_ranges(self, pattern))
+    }
+}
+
+/// Pairs each of the given ranges with the styles of the text in it.
+fn styled_matches(strs: &ANSIStrings, ranges: Vec<Range<usize>>) -> Vec<StyledMatch> {
+    let mut matches: Vec<StyledMatch> = ranges.iter()
+        .map(|r| StyledMatch { range: r.clone(), spans: Vec::new() })
+        .collect();
+    let mut offset = 0;
+
+    for (style, text, hit) in split_ranges(strs, ranges) {
+        if let Some(index) = hit {
+            matches[index].spans.push((offset .. offset + text.len(), style));
+        }
+
+        offset += text.len();
+    }
+
+    matches
+}
+
+/// A match found by [`ANSIStrings::find`] or [`ANSIStrings::find_all`],
+/// with the styles its text is painted in.
+///
+/// [`ANSIStrings::find`]: struct.ANSIGenericStrings.html#method.find
+/// [`ANSIStrings::find_all`]: struct.ANSIGenericStrings.html#method.find_all
+#[derive(PartialEq, Clone, Debug)]
+pub struct StyledMatch {
+
+    /// The byte range of the match in the text without any of its styles.
+    pub range: Range<usize>,
+
+    /// The parts of the match that are painted in each style, in order, as
+    /// byte ranges of the same text. There’s more than one part when the
+    /// match spans fragments with different styles.
+    pub spans: Vec<(Range<usize>, Style)>,
+}
+
+impl StyledMatch {
+
+    /// Returns the style of the match, if all of it is painted in the same
+    /// style.
+    pub fn style(&self) -> Option<Style> {
+        let first = self.spans.first()?.1;
+        if self.spans.iter().all(|s| s.1 == first) { Some(first) } else { None }
+    }
+}
+
+/// Finds the byte ranges of the occurrences of `pattern` in the text of
+/// `strs`.
+fn match_ranges(strs: &ANSIStrings, pattern: &str) -> Vec<Range<usize>> {
+    if pattern.is_empty() {
+        return Vec::new();
+    }
+
+    unstyle(strs).match_indices(pattern).map(|(start, m)| start .. start + m.len()).collect()
+}
+
+/// Paints the given byte ranges of the text of `strs` with the overlay. The
+/// ranges have to be in order, and mustn’t overlap.
+fn highlight_ranges(strs: &ANSIStrings, ranges: Vec<Range<usize>>, overlay: Style) -> Vec<ANSIString<'static>> {
+    split_ranges(strs, ranges).into_iter()
+        .map(|(style, text, hit)| if hit.is_some() { overlay.apply_over(&style) } else { style }.paint(String::from(text)))
+        .collect()
+}
+
+/// Splits the fragments of `strs` at the edges of the given byte ranges of
+/// its text, returning each piece along with the index of the range it’s in,
+/// if any. Empty ranges are ignored; the rest have to be in order, and
+/// mustn’t overlap.
+fn split_ranges<'s>(strs: &'s ANSIStrings, ranges: Vec<Range<usize>>) -> Vec<(Style, &'s str, Option<usize>)> {
+    let mut ranges = ranges.into_iter().enumerate().filter(|r| !r.1.is_empty()).peekable();
+    let mut pieces = Vec::new();
+    let mut offset = 0;
+
+    for fragment in strs.0.iter() {
+        let style = *fragment.style_ref();
+        let end = offset + fragment.len();
+        let mut pos = offset;
+
+        while pos < end {
+            while ranges.peek().is_some_and(|r| r.1.end <= pos) {
+                ranges.next();
+            }
+
+            let (next, hit) = match ranges.peek() {
+                Some((index, r)) if r.start <= pos       => (r.end.min(end), Some(*index)),
+                Some((_, r)) if r.start < end            => (r.start, None),
+                _                                        => (end, None),
+            };
+
+            pieces.push((style, &fragment[pos - offset .. next - offset], hit));
+            pos = next;
+        }
+
+        offset = end;
+    }
+
+    pieces
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use style::Colour::*;
+
+    #[test]
+    fn no_matches() {
+        let line = [ Red.paint("abc"), Blue.paint("") ];
+        assert_eq!(highlight_matches(&ANSIStrings(&line), "x", Green.normal()), vec![ Red.paint("abc") ]);
+    }
+
+    #[test]
+    fn several_matches_in_one_fragment() {
+        let line = [ Red.paint("a-a-a") ];
+        assert_eq!(highlight_matches(&ANSIStrings(&line), "a", Style::new().on(Yellow)), vec![
+            Red.on(Yellow).paint("a"), Red.paint("-"), Red.on(Yellow).paint("a"),
+            Red.paint("-"), Red.on(Yellow).paint("a"),
+        ]);
+    }
+
+    #[test]
+    fn overlay_colour_wins() {
+        let line = [ Red.bold().paint("ab") ];
+        assert_eq!(highlight_matches(&ANSIStrings(&line), "b", Green.normal()), vec![
+            Red.bold().paint("a"), Green.bold().paint("b"),
+        ]);
+    }
+
+    #[test]
+    fn find_across_fragments() {
+        let line = [ Red.paint("ab"), Blue.paint("cd") ];
+        let hit = ANSIStrings(&line).find("bc").unwrap();
+        assert_eq!(hit.spans, vec![ (1 .. 2, Red.normal()), (2 .. 3, Blue.normal()) ]);
+        assert_eq!(hit.style(), None);
+    }
+
+    #[test]
+    fn find_all() {
+        let line = [ Red.paint("a-"), Blue.paint("a") ];
+        let hits = ANSIStrings(&line).find_all("a");
+        assert_eq!(hits.iter().map(|h| h.range.clone()).collect::<Vec<_>>(), vec![ 0 .. 1, 2 .. 3 ]);
+        assert_eq!(hits[1].style(), Some(Blue.normal()));
+        assert!(ANSIStrings(&line).find("b").is_none());
+    }
+
+    #[test]
+    fn replace_across_fragments() {
+        let line = [ Red.paint("ab"), Blue.paint("cd") ];
+        assert_eq!(ANSIStrings(&line).replace("bc", &[ Green.paint("-") ]), vec![
+            Red.paint("a"), Green.paint("-"), Blue.paint("d"),
+        ]);
+    }
+
+    #[test]
+    fn replace_with_nothing() {
+        let line = [ Red.paint("a, b, c") ];
+        assert_eq!(ANSIStrings(&line).replace(", ", &[]), vec![ Red.paint("a"), Red.paint("b"), Red.paint("c") ]);
+    }
+
+    #[test]
+    fn replace_adjacent_matches() {
+        let line = [ Red.paint("xx") ];
+        assert_eq!(ANSIStrings(&line).replace("x", &[ Blue.paint("y") ]), vec![ Blue.paint("y"), Blue.paint("y") ]);
+    }
+}
@@ -0,0 +1,200 @@
+//! Reading styles back out of Select Graphic Rendition parameters, the
+//! numbers in an `ESC [ … m` code, for the parts of the crate that read
+//! escape codes or style lists written by other programs.
+
+use std::str;
+
+use style::{Colour, Style};
+
+
+/// Applies one parameter to a style, taking the rest of a `38` or `48`
+/// code from the parameters that follow it: `5;n` for a colour from the
+/// 256-colour palette, `2;r;g;b` for an RGB colour, or `3;c;m;y` or
+/// `4;c;m;y;k` for a CMY or CMYK colour.
+///
+/// The bright colours, `90` to `97` and `100` to `107`, become `Fixed(8)` to
+/// `Fixed(15)`, or without the `fixed` feature, their normal colours, made
+/// bold for a foreground.
+///
+/// Returns `false`, leaving the style as it was, if the parameter isn’t one
+/// that a `Style` can represent, or is a colour the crate was built
+/// without.
+pub(crate) fn apply<I: Iterator<Item = usize>>(style: &mut Style, param: usize, params: &mut I) -> bool {
+    let s = style;
+    match param {
+        0          => *s = Style::default(),
+        1          => s.is_bold = true,
+        2          => s.is_dimmed = true,
+        3          => s.is_italic = true,
+        4          => s.is_underline = true,
+        5          => s.is_blink = true,
+        7          => s.is_reverse = true,
+        8          => s.is_hidden = true,
+        9          => s.is_strikethrough = true,
+        22         => { s.is_bold = false; s.is_dimmed = false },
+        23         => s.is_italic = false,
+        24         => s.is_underline = false,
+        25         => s.is_blink = false,
+        27         => s.is_reverse = false,
+        28         => s.is_hidden = false,
+        29         => s.is_strikethrough = false,
+        30 ..= 37  => s.foreground = Some(basic(param - 30)),
+        38         => match extended(params) {
+            Some(colour)  => s.foreground = Some(colour),
+            None          => return false,
+        },
+        39         => s.foreground = None,
+        40 ..= 47  => s.background = Some(basic(param - 40)),
+        48         => match extended(params) {
+            Some(colour)  => s.background = Some(colour),
+            None          => return false,
+        },
+        49         => s.background = None,
+        90 ..= 97  => {
+            s.foreground = Some(bright(param - 90));
+            if cfg!(not(feature = "fixed")) {
+                s.is_bold = true;
+            }
+        },
+        100 ..= 107 => s.background = Some(bright(param - 100)),
+        _          => return false,
+    }
+
+    true
+}
+
+/// Applies all the parameters of an SGR code, such as the `1;31` in
+/// `ESC [ 1 ; 3 1 m`, to a style. Parameters that can’t be read count as
+/// `0`, and ones that a `Style` can’t represent get skipped.
+///
+/// Colours can be in the colon form from ITU T.416 as well, such as
+/// `38:2::255:0:0`, with or without the empty colour space.
+pub(crate) fn apply_all(style: &mut Style, params: &[u8]) {
+    let number = |p: &[u8]| str::from_utf8(p).ok().and_then(|p| p.parse().ok()).unwrap_or(0);
+    let mut params = params.split(|&b| b == b';');
+
+    while let Some(param) = params.next() {
+        if param.contains(&b':') {
+            let mut subparams: Vec<usize> = param.split(|&b| b == b':').map(number).collect();
+            if subparams.get(1).and_then(|&kind| colour_len(kind)) == Some(subparams.len() - 1) {
+                subparams.remove(2);
+            }
+
+            let mut subparams = subparams.into_iter();
+            if let Some(first) = subparams.next() {
+                apply(style, first, &mut subparams);
+            }
+        }
+        else {
+            apply(style, number(param), &mut params.by_ref().map(number));
+        }
+    }
+}
+
+
+/// A piece of some text that has escape codes in it.
+#[derive(PartialEq, Debug)]
+pub(crate) enum Token<'a> {
+
+    /// Text up to the next escape character.
+    Text(&'a [u8]),
+
+    /// An SGR code, as its parameters between the `ESC [` and the `m`.
+    Sgr(&'a [u8]),
+
+    /// Any other control sequence, as a whole.
+    Other(&'a [u8]),
+}
+
+/// Splits the first token off the start of some bytes, returning it with
+/// its length, or `None` if the bytes are empty or start with a control
+/// sequence that hasn’t been finished.
+///
+/// An escape character that doesn’t start a control sequence counts as
+/// text.
+pub(crate) fn next_token<'a>(bytes: &'a [u8]) -> Option<(Token<'a>, usize)> {
+    match bytes {
+        []                   => None,
+        [ 0x1B ]             => None,
+        [ 0x1B, b'[', .. ]   => {
+            let end = 2 + bytes[2 ..].iter().position(|b| (0x40 ..= 0x7E).contains(b))?;
+            let token = if bytes[end] == b'm' { Token::Sgr(&bytes[2 .. end]) }
+                                         else { Token::Other(&bytes[..= end]) };
+            Some((token, end + 1))
+        },
+        _                    => {
+            let end = bytes[1 ..].iter().position(|&b| b == 0x1B).map_or(bytes.len(), |p| p + 1);
+            Some((Token::Text(&bytes[.. end]), end))
+        },
+    }
+}
+
+/// Returns how many numbers a `38` or `48` colour of the given kind has,
+/// counting the `38` and the kind but not the colour space, for the kinds
+/// that can have a colour space.
+pub(crate) fn colour_len(kind: usize) -> Option<usize> {
+    match kind {
+        2 | 3  => Some(5),
+        4      => Some(6),
+        _      => None,
+    }
+}
+
+fn basic(n: usize) -> Colour {
+    Colour::basic_iter().nth(n).unwrap()
+}
+
+#[cfg(feature = "fixed")]
+fn bright(n: usize) -> Colour {
+    Colour::Fixed(8 + n as u8)
+}
+
+#[cfg(not(feature = "fixed"))]
+fn bright(n: usize) -> Colour {
+    basic(n)
+}
+
+#[cfg(any(feature = "fixed", feature = "rgb"))]
+fn byte(n: Option<usize>) -> Option<u8> {
+    n.filter(|&n| n <= 255).map(|n| n as u8)
+}
+
+fn extended<I: Iterator<Item = usize>>(params: &mut I) -> Option<Colour> {
+    match params.next() {
+        #[cfg(feature = "fixed")]
+        Some(5)  => Some(Colour::Fixed(byte(params.next())?)),
+        #[cfg(feature = "rgb")]
+        Some(2)  => Some(Colour::RGB(byte(params.next())?, byte(params.next())?, byte(params.next())?)),
+        #[cfg(feature = "cmyk")]
+        Some(3)  => Some(Colour::CMY(byte(params.next())?, byte(params.next())?, byte(params.next())?)),
+        #[cfg(feature = "cmyk")]
+        Some(4)  => Some(Colour::CMYK(byte(params.next())?, byte(params.next())?, byte(params.next())?, byte(params.next())?)),
+        _        => None,
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tokens() {
+        assert_eq!(next_token(b"ab\x1B[1m"), Some((Token::Text(b"ab"), 2)));
+        assert_eq!(next_token(b"\x1B[1;31mab"), Some((Token::Sgr(b"1;31"), 7)));
+        assert_eq!(next_token(b"\x1B[2Kab"), Some((Token::Other(b"\x1B[2K"), 4)));
+        assert_eq!(next_token(b"\x1B(B"), Some((Token::Text(b"\x1B(B"), 3)));
+        assert_eq!(next_token(b"\x1B[1;3"), None);
+    }
+
+    #[test]
+    #[cfg(all(feature = "rgb", feature = "fixed"))]
+    fn colon_colours() {
+        let mut style = Style::default();
+        apply_all(&mut style, b"1;38:2::255:0:0;48:5:17");
+        assert_eq!(style, Colour::RGB(255, 0, 0).on(Colour::Fixed(17)).bold());
+
+        apply_all(&mut style, b"38:2:1:2:3;4:3");
+        assert_eq!(style, Colour::RGB(1, 2, 3).on(Colour::Fixed(17)).bold().underline());
+    }
+}
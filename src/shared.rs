@@ -0,0 +1,151 @@
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use display::ANSIString;
+use style::{Colour, Style};
+
+
+/// A styled string whose text is shared through an `Arc`, so cloning it
+/// never copies the text, and it can be sent between threads.
+///
+/// This is for renderers and caches that hand the same large pieces of
+/// styled text to many consumers. An `ANSIString` either borrows its text or
+/// owns a copy of it, so sharing one means copying it for each consumer or
+/// keeping a lifetime around.
+///
+/// It gets displayed in the same way as an `ANSIString`, and
+/// [`as_ansi_string`] borrows it as one, for painting several of them
+/// together as `ANSIStrings`.
+///
+/// [`as_ansi_string`]: #method.as_ansi_string
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+/// use ansi_term::Colour::Green;
+///
+/// let banner = Green.paint_shared("a long banner that many threads print");
+/// let handles: Vec<_> = (0 .. 4).map(|_| {
+///     let banner = banner.clone();
+///     thread::spawn(move || banner.to_string())
+/// }).collect();
+///
+/// for handle in handles {
+///     assert_eq!(handle.join().unwrap(), "\x1B[32ma long banner that many threads print\x1B[0m");
+/// }
+/// ```
+#[derive(PartialEq, Clone, Debug)]
+pub struct SharedANSIString {
+    style: Style,
+    string: Arc<str>,
+}
+
+impl SharedANSIString {
+
+    /// Creates a string with the given style and shared text.
+    pub fn new<S: Into<Arc<str>>>(style: Style, string: S) -> SharedANSIString {
+        SharedANSIString { style, string: string.into() }
+    }
+
+    /// Directly access the style.
+    pub fn style_ref(&self) -> &Style {
+        &self.style
+    }
+
+    /// Directly access the style mutably.
+    pub fn style_ref_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
+
+    /// Returns the shared text, which can be cloned to share it further.
+    pub fn as_arc(&self) -> &Arc<str> {
+        &self.string
+    }
+
+    /// Borrows this string as an `ANSIString`, without copying the text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::ANSIStrings;
+    /// use ansi_term::Colour::{Red, Blue};
+    ///
+    /// let shared = [ Red.paint_shared("a"), Blue.paint_shared("b") ];
+    /// let strings: Vec<_> = shared.iter().map(|s| s.as_ansi_string()).collect();
+    /// assert_eq!(ANSIStrings(&strings).to_string(), "\x1B[31ma\x1B[34mb\x1B[0m");
+    /// ```
+    pub fn as_ansi_string(&self) -> ANSIString<'_> {
+        self.style.paint(&*self.string)
+    }
+}
+
+impl Deref for SharedANSIString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.string
+    }
+}
+
+/// Formatting a `SharedANSIString` pads it by the width of its text on the
+/// screen, in the same way as an `ANSIString`.
+impl fmt::Display for SharedANSIString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.as_ansi_string(), f)
+    }
+}
+
+impl<'a> From<ANSIString<'a>> for SharedANSIString {
+
+    /// Converts an `ANSIString` into one with shared text, copying the text
+    /// once.
+    fn from(string: ANSIString<'a>) -> SharedANSIString {
+        SharedANSIString::new(*string.style_ref(), &*string)
+    }
+}
+
+impl Style {
+
+    /// Paints the given text with this style, returning a string whose text
+    /// is shared through an `Arc`. The text can be anything that converts
+    /// into an `Arc<str>`, including an existing `Arc<str>`, which doesn’t
+    /// get copied.
+    #[must_use]
+    pub fn paint_shared<S: Into<Arc<str>>>(self, input: S) -> SharedANSIString {
+        SharedANSIString::new(self, input)
+    }
+}
+
+impl Colour {
+
+    /// Paints the given text with this colour, returning a string whose text
+    /// is shared through an `Arc`.
+    #[must_use]
+    pub fn paint_shared<S: Into<Arc<str>>>(self, input: S) -> SharedANSIString {
+        SharedANSIString::new(self.normal(), input)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use style::Colour::*;
+
+    #[test]
+    fn clones_share_text() {
+        let text: Arc<str> = Arc::from("shared");
+        let string = Red.bold().paint_shared(Arc::clone(&text));
+        let clone = string.clone();
+        assert!(Arc::ptr_eq(clone.as_arc(), &text));
+        assert_eq!(Arc::strong_count(&text), 3);
+    }
+
+    #[test]
+    fn displays_like_ansi_string() {
+        let string = SharedANSIString::from(Blue.paint("ab"));
+        assert_eq!(format!("[{:>4}]", string), format!("[{:>4}]", Blue.paint("ab")));
+    }
+}
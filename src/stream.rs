@@ -0,0 +1,95 @@
+use std::fmt;
+use std::io;
+
+use display::{ANSIByteStrings, ANSIStrings};
+
+
+/// One of the two standard output streams.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub(crate) enum Stream {
+    Stdout,
+    Stderr,
+}
+
+impl Stream {
+
+    /// Locks the stream, and runs the function with the lock held.
+    pub(crate) fn locked<T, F>(self, f: F) -> T
+    where F: FnOnce(&mut dyn io::Write) -> T {
+        match self {
+            Stream::Stdout => f(&mut io::stdout().lock()),
+            Stream::Stderr => f(&mut io::stderr().lock()),
+        }
+    }
+}
+
+
+/// A handle to standard output or standard error that writes a whole set of
+/// styled strings while holding the stream’s lock.
+///
+/// Writing an `ANSIStrings` value involves several writes: one for each
+/// string and each code in between. With `print!`, another thread can print
+/// in the middle of those and be coloured by them; a `StyledStream` prevents
+/// this by keeping the stream locked until every write is done.
+///
+/// Get one with the [`stdout`] or [`stderr`] functions.
+///
+/// [`stdout`]: fn.stdout.html
+/// [`stderr`]: fn.stderr.html
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct StyledStream {
+    stream: Stream,
+}
+
+/// Returns a handle to standard output for writing styled strings.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{stdout, ANSIStrings};
+/// use ansi_term::Colour::{Green, Red};
+///
+/// stdout().println(&ANSIStrings(&[
+///     Green.paint("12 passed"),
+///     Red.paint(", 1 failed"),
+/// ])).unwrap();
+/// ```
+pub fn stdout() -> StyledStream {
+    StyledStream { stream: Stream::Stdout }
+}
+
+/// Returns a handle to standard error for writing styled strings.
+pub fn stderr() -> StyledStream {
+    StyledStream { stream: Stream::Stderr }
+}
+
+impl StyledStream {
+
+    /// Writes the strings to the stream, with the minimum of control
+    /// characters, and flushes it.
+    pub fn print(&self, strings: &ANSIStrings) -> io::Result<()> {
+        self.write_fmt(format_args!("{}", strings))
+    }
+
+    /// Writes the strings to the stream followed by a newline, and flushes
+    /// it. The newline is written after the final reset code.
+    pub fn println(&self, strings: &ANSIStrings) -> io::Result<()> {
+        self.write_fmt(format_args!("{}\n", strings))
+    }
+
+    /// Writes the byte strings to the stream, with the minimum of control
+    /// characters, and flushes it.
+    pub fn print_bytes(&self, strings: &ANSIByteStrings) -> io::Result<()> {
+        self.stream.locked(|w| {
+            strings.write_to(&mut &mut *w)?;
+            w.flush()
+        })
+    }
+
+    fn write_fmt(&self, args: fmt::Arguments) -> io::Result<()> {
+        self.stream.locked(|w| {
+            w.write_fmt(args)?;
+            w.flush()
+        })
+    }
+}
@@ -0,0 +1,249 @@
+use std::io::{self, IsTerminal, Write};
+
+
+/// The longest SGR code that gets kept for a pager. A longer sequence gets
+/// removed instead, so one that never gets finished can’t use up memory.
+const MAX_SEQUENCE: usize = 64;
+
+/// A writer that removes the escape sequences from everything written
+/// through it when it isn’t writing to a terminal, so that output piped into
+/// another program or redirected to a file comes out as plain text.
+///
+/// Sequences get recognised even when they’re split across several writes.
+/// As well as the colour and style codes, this removes cursor movement, the
+/// sequences for titles and hyperlinks, and any other control sequence that
+/// starts with an escape character.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+/// use ansi_term::StripWriter;
+/// use ansi_term::Colour::Red;
+///
+/// let mut out = StripWriter::always(Vec::new());
+/// write!(out, "{}: disk full", Red.bold().paint("error")).unwrap();
+///
+/// assert_eq!(out.into_inner(), b"error: disk full");
+/// ```
+#[derive(Debug)]
+pub struct StripWriter<W: Write> {
+    writer: W,
+    strip: bool,
+    state: State,
+
+    /// Whether SGR codes get kept, for a pager.
+    keep_sgr: bool,
+
+    /// The control sequence that’s being read, so an SGR code can be kept
+    /// once it has been finished.
+    sequence: Vec<u8>,
+}
+
+/// How far through an escape sequence the bytes written so far have got.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum State {
+
+    /// Not in a sequence.
+    Text,
+
+    /// After the escape character.
+    Escape,
+
+    /// In a control sequence, which ends with a byte from `@` to `~`.
+    ControlSequence,
+
+    /// In a string, such as a title or hyperlink, which ends with BEL or
+    /// the string terminator.
+    String,
+
+    /// After an escape character in a string.
+    StringEscape,
+}
+
+impl<W: Write + IsTerminal> StripWriter<W> {
+
+    /// Returns a writer that strips escape sequences only if the given
+    /// writer is not a terminal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Write;
+    /// use ansi_term::StripWriter;
+    /// use ansi_term::Colour::Green;
+    ///
+    /// let mut out = StripWriter::new(std::io::stdout());
+    /// writeln!(out, "{}", Green.paint("done")).unwrap();
+    /// ```
+    pub fn new(writer: W) -> StripWriter<W> {
+        let strip = !writer.is_terminal();
+        StripWriter { writer, strip, state: State::Text, keep_sgr: false, sequence: Vec::new() }
+    }
+}
+
+impl<W: Write> StripWriter<W> {
+
+    /// Returns a writer that always strips escape sequences, wherever it
+    /// writes to.
+    pub fn always(writer: W) -> StripWriter<W> {
+        StripWriter { writer, strip: true, state: State::Text, keep_sgr: false, sequence: Vec::new() }
+    }
+
+    /// Returns a writer that keeps the SGR codes that set colours and
+    /// styles, and removes every other escape sequence, such as cursor
+    /// movements, titles, and hyperlinks. This makes the output safe to be
+    /// shown by a pager such as `less -R`, which passes colour codes through
+    /// to the terminal but shows the other sequences as garbage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Write;
+    /// use ansi_term::{set_title, StripWriter};
+    /// use ansi_term::Colour::Red;
+    ///
+    /// let mut out = StripWriter::pager_safe(Vec::new());
+    /// write!(out, "{}{}", set_title("log"), Red.paint("error")).unwrap();
+    ///
+    /// assert_eq!(out.into_inner(), b"\x1B[31merror\x1B[0m");
+    /// ```
+    pub fn pager_safe(writer: W) -> StripWriter<W> {
+        StripWriter { writer, strip: true, state: State::Text, keep_sgr: true, sequence: Vec::new() }
+    }
+
+    /// Returns whether escape sequences are being removed.
+    pub fn is_stripping(&self) -> bool {
+        self.strip
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> Write for StripWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !self.strip {
+            return self.writer.write(buf);
+        }
+
+        let mut start = 0;
+
+        for (index, &byte) in buf.iter().enumerate() {
+            let state = self.state;
+            self.state = next_state(state, byte);
+
+            if state == State::Text && self.state != State::Text {
+                self.writer.write_all(&buf[start .. index])?;
+            }
+            else if state != State::Text && self.state == State::Text {
+                start = index + 1;
+
+                if self.keep_sgr && state == State::ControlSequence && byte == b'm' && self.sequence.len() < MAX_SEQUENCE {
+                    self.sequence.push(byte);
+                    self.writer.write_all(&self.sequence)?;
+                }
+
+                self.sequence.clear();
+            }
+
+            // Strings never get kept, so only control sequences need to be
+            // remembered.
+            let in_control = self.state == State::Escape || self.state == State::ControlSequence;
+            if self.keep_sgr && in_control && self.sequence.len() < MAX_SEQUENCE {
+                self.sequence.push(byte);
+            }
+        }
+
+        if self.state == State::Text {
+            self.writer.write_all(&buf[start ..])?;
+        }
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Returns the state after the given byte, where the byte is part of a
+/// sequence unless both states are `Text`.
+fn next_state(state: State, byte: u8) -> State {
+    match (state, byte) {
+        (State::Text,             0x1B)          => State::Escape,
+        (State::Text,             _)             => State::Text,
+        (State::Escape,           b'[')          => State::ControlSequence,
+        (State::Escape,           b']')          |
+        (State::Escape,           b'P')          |
+        (State::Escape,           b'X')          |
+        (State::Escape,           b'^')          |
+        (State::Escape,           b'_')          => State::String,
+        (State::Escape,           0x20 ..= 0x2F) => State::Escape,
+        (State::Escape,           _)             => State::Text,
+        (State::ControlSequence,  0x40 ..= 0x7E) => State::Text,
+        (State::ControlSequence,  _)             => State::ControlSequence,
+        (State::String,           0x07)          => State::Text,
+        (State::String,           0x1B)          => State::StringEscape,
+        (State::String,           _)             => State::String,
+        (State::StringEscape,     b'\\')         => State::Text,
+        (State::StringEscape,     _)             => State::String,
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn strip(chunks: &[&str]) -> String {
+        let mut out = StripWriter::always(Vec::new());
+        for chunk in chunks {
+            out.write_all(chunk.as_bytes()).unwrap();
+        }
+        String::from_utf8(out.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn split_sequences() {
+        assert_eq!(strip(&[ "a\x1B", "[1;3", "1mb\x1B[0", "m" ]), "ab");
+    }
+
+    #[test]
+    fn hyperlinks_and_titles() {
+        assert_eq!(strip(&[ "\x1B]8;;http://a\x1B\\link\x1B]8;;\x1B\\", "\x1B]0;title\x07!" ]), "link!");
+    }
+
+    #[test]
+    fn pager_keeps_colours() {
+        let mut out = StripWriter::pager_safe(Vec::new());
+        out.write_all(b"\x1B[1;3").unwrap();
+        out.write_all(b"1ma\x1B[2K\x1B]8;;http://a\x07b\x1B]8;;\x07\x1B[0m").unwrap();
+        assert_eq!(out.into_inner(), b"\x1B[1;31mab\x1B[0m");
+    }
+
+    #[test]
+    fn pager_unfinished_strings() {
+        let mut out = StripWriter::pager_safe(Vec::new());
+        out.write_all(b"a\x1B]0;").unwrap();
+        for _ in 0 .. 1000 {
+            out.write_all(b"a very long title that never ends").unwrap();
+        }
+        assert!(out.sequence.len() < MAX_SEQUENCE);
+
+        out.write_all(format!("\x07\x1B[{}mb\x1B[1mc", "1;".repeat(100)).as_bytes()).unwrap();
+        assert_eq!(out.into_inner(), b"ab\x1B[1mc");
+    }
+
+    #[test]
+    fn other_escapes() {
+        assert_eq!(strip(&[ "\x1B(Ba\x1B7b\x1B8" ]), "ab");
+    }
+}
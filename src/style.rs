@@ -1,3 +1,10 @@
+#[cfg(feature = "rgb")]
+use std::array::TryFromSliceError;
+#[cfg(feature = "rgb")]
+use std::convert::TryFrom;
+use std::ops::{BitOr, BitOrAssign};
+
+
 /// A style is a collection of properties that can format a string
 /// using ANSI escape codes.
 ///
@@ -199,6 +206,156 @@ impl Style {
         Style { background: Some(background), .. *self }
     }
 
+    /// Returns a `Style` with the foreground colour property set, if there
+    /// is a colour, and this style’s foreground colour otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::{Style, Colour};
+    ///
+    /// let configured: Option<Colour> = None;
+    /// let style = Style::new().fg(Colour::Red).fg_opt(configured).bold();
+    /// assert_eq!(style, Colour::Red.bold());
+    /// ```
+    pub fn fg_opt(&self, foreground: Option<Colour>) -> Style {
+        Style { foreground: foreground.or(self.foreground), .. *self }
+    }
+
+    /// Returns a `Style` with the background colour property set, if there
+    /// is a colour, and this style’s background colour otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::{Style, Colour};
+    ///
+    /// let style = Style::new().on_opt(Some(Colour::Blue));
+    /// assert_eq!(style, Style::new().on(Colour::Blue));
+    /// ```
+    pub fn on_opt(&self, background: Option<Colour>) -> Style {
+        Style { background: background.or(self.background), .. *self }
+    }
+
+    /// Returns a `Style` with the bold property set if the condition is
+    /// true, and this style as it is otherwise.
+    ///
+    /// There is one of these for each property, so that a style that
+    /// depends on something can still be built in one expression.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::Colour;
+    ///
+    /// let selected = true;
+    /// let style = Colour::Blue.normal().bold_if(selected).underline_if(!selected);
+    /// assert_eq!(style, Colour::Blue.bold());
+    /// ```
+    pub fn bold_if(&self, condition: bool) -> Style {
+        if condition { self.bold() } else { *self }
+    }
+
+    /// Returns a `Style` with the dimmed property set if the condition is
+    /// true, and this style as it is otherwise.
+    pub fn dimmed_if(&self, condition: bool) -> Style {
+        if condition { self.dimmed() } else { *self }
+    }
+
+    /// Returns a `Style` with the italic property set if the condition is
+    /// true, and this style as it is otherwise.
+    pub fn italic_if(&self, condition: bool) -> Style {
+        if condition { self.italic() } else { *self }
+    }
+
+    /// Returns a `Style` with the underline property set if the condition is
+    /// true, and this style as it is otherwise.
+    pub fn underline_if(&self, condition: bool) -> Style {
+        if condition { self.underline() } else { *self }
+    }
+
+    /// Returns a `Style` with the blink property set if the condition is
+    /// true, and this style as it is otherwise.
+    pub fn blink_if(&self, condition: bool) -> Style {
+        if condition { self.blink() } else { *self }
+    }
+
+    /// Returns a `Style` with the reverse property set if the condition is
+    /// true, and this style as it is otherwise.
+    pub fn reverse_if(&self, condition: bool) -> Style {
+        if condition { self.reverse() } else { *self }
+    }
+
+    /// Returns a `Style` with the hidden property set if the condition is
+    /// true, and this style as it is otherwise.
+    pub fn hidden_if(&self, condition: bool) -> Style {
+        if condition { self.hidden() } else { *self }
+    }
+
+    /// Returns a `Style` with the strikethrough property set if the condition is
+    /// true, and this style as it is otherwise.
+    pub fn strikethrough_if(&self, condition: bool) -> Style {
+        if condition { self.strikethrough() } else { *self }
+    }
+
+    /// Returns a `Style` with the foreground colour property set if the
+    /// condition is true, and this style as it is otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::Style;
+    /// use ansi_term::Colour::Red;
+    ///
+    /// let failed = false;
+    /// assert_eq!(Style::new().fg_if(failed, Red), Style::new());
+    /// ```
+    pub fn fg_if(&self, condition: bool, foreground: Colour) -> Style {
+        if condition { self.fg(foreground) } else { *self }
+    }
+
+    /// Returns a `Style` with the background colour property set if the
+    /// condition is true, and this style as it is otherwise.
+    pub fn on_if(&self, condition: bool, background: Colour) -> Style {
+        if condition { self.on(background) } else { *self }
+    }
+
+    /// Returns a `Style` with this style’s properties applied over those of
+    /// `parent`, so that whatever this style leaves unset gets inherited.
+    ///
+    /// This style’s colours are used if it has them, and the parent’s if it
+    /// doesn’t. Properties such as bold or underline are set if either style
+    /// sets them. This makes it possible to build up themes in layers, such
+    /// as a base style, a style for each section, and a style for each kind
+    /// of element within it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::{Style, Colour};
+    ///
+    /// let section = Colour::Blue.on(Colour::Black);
+    /// let heading = Style::new().bold().apply_over(&section);
+    /// assert_eq!(heading, Colour::Blue.on(Colour::Black).bold());
+    ///
+    /// let warning = Colour::Yellow.normal().apply_over(&heading);
+    /// assert_eq!(warning, Colour::Yellow.on(Colour::Black).bold());
+    /// ```
+    pub fn apply_over(&self, parent: &Style) -> Style {
+        Style {
+            foreground:        self.foreground.or(parent.foreground),
+            background:        self.background.or(parent.background),
+            is_bold:           self.is_bold || parent.is_bold,
+            is_dimmed:         self.is_dimmed || parent.is_dimmed,
+            is_italic:         self.is_italic || parent.is_italic,
+            is_underline:      self.is_underline || parent.is_underline,
+            is_blink:          self.is_blink || parent.is_blink,
+            is_reverse:        self.is_reverse || parent.is_reverse,
+            is_hidden:         self.is_hidden || parent.is_hidden,
+            is_strikethrough:  self.is_strikethrough || parent.is_strikethrough,
+        }
+    }
+
     /// Return true if this `Style` has no actual styles, and can be written
     /// without any control characters.
     ///
@@ -213,6 +370,227 @@ impl Style {
     pub fn is_plain(self) -> bool {
         self == Style::default()
     }
+
+    /// Returns this style with both of its colours in their canonical form,
+    /// as described by [`Colour::normalize`], so that styles that look the
+    /// same compare as equal.
+    ///
+    /// [`Colour::normalize`]: enum.Colour.html#method.normalize
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "fixed")] {
+    /// use ansi_term::Colour::{Fixed, Red, Blue};
+    ///
+    /// assert_ne!(Fixed(1).on(Fixed(4)), Red.on(Blue));
+    /// assert_eq!(Fixed(1).on(Fixed(4)).normalize(), Red.on(Blue));
+    /// # }
+    /// ```
+    pub fn normalize(&self) -> Style {
+        Style {
+            foreground: self.foreground.map(Colour::normalize),
+            background: self.background.map(Colour::normalize),
+            .. *self
+        }
+    }
+}
+
+/// One of the properties of a `Style` that is either set or not, such as
+/// bold or underline.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Attribute {
+
+    /// The `is_bold` property.
+    Bold,
+
+    /// The `is_dimmed` property.
+    Dimmed,
+
+    /// The `is_italic` property.
+    Italic,
+
+    /// The `is_underline` property.
+    Underline,
+
+    /// The `is_blink` property.
+    Blink,
+
+    /// The `is_reverse` property.
+    Reverse,
+
+    /// The `is_hidden` property.
+    Hidden,
+
+    /// The `is_strikethrough` property.
+    Strikethrough,
+}
+
+impl Attribute {
+
+    /// Every attribute, in the order of the fields of `Style`.
+    pub const ALL: [Attribute; 8] = [
+        Attribute::Bold, Attribute::Dimmed, Attribute::Italic, Attribute::Underline,
+        Attribute::Blink, Attribute::Reverse, Attribute::Hidden, Attribute::Strikethrough,
+    ];
+}
+
+impl Style {
+
+    /// Returns whether this style has the given attribute set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::{Attribute, Style};
+    ///
+    /// assert!(Style::new().italic().has(Attribute::Italic));
+    /// assert!(!Style::new().italic().has(Attribute::Bold));
+    /// ```
+    pub fn has(&self, attribute: Attribute) -> bool {
+        match attribute {
+            Attribute::Bold           => self.is_bold,
+            Attribute::Dimmed         => self.is_dimmed,
+            Attribute::Italic         => self.is_italic,
+            Attribute::Underline      => self.is_underline,
+            Attribute::Blink          => self.is_blink,
+            Attribute::Reverse        => self.is_reverse,
+            Attribute::Hidden         => self.is_hidden,
+            Attribute::Strikethrough  => self.is_strikethrough,
+        }
+    }
+
+    /// Returns a `Style` with the given attribute set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::{Attribute, Style};
+    ///
+    /// assert_eq!(Style::new().with(Attribute::Blink), Style::new().blink());
+    /// ```
+    pub fn with(&self, attribute: Attribute) -> Style {
+        let mut style = *self;
+        match attribute {
+            Attribute::Bold           => style.is_bold = true,
+            Attribute::Dimmed         => style.is_dimmed = true,
+            Attribute::Italic         => style.is_italic = true,
+            Attribute::Underline      => style.is_underline = true,
+            Attribute::Blink          => style.is_blink = true,
+            Attribute::Reverse        => style.is_reverse = true,
+            Attribute::Hidden         => style.is_hidden = true,
+            Attribute::Strikethrough  => style.is_strikethrough = true,
+        }
+        style
+    }
+
+    /// Returns an iterator over the attributes that this style has set, in
+    /// the order of its fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::{Attribute, Style};
+    ///
+    /// let style = Style::new().underline().bold();
+    /// assert_eq!(style.attributes().collect::<Vec<_>>(),
+    ///            vec![ Attribute::Bold, Attribute::Underline ]);
+    /// ```
+    pub fn attributes(&self) -> impl Iterator<Item=Attribute> {
+        let style = *self;
+        Attribute::ALL.iter().copied().filter(move |a| style.has(*a))
+    }
+}
+
+/// The plain style, for building the constants below.
+const PLAIN: Style = Style {
+    foreground: None,
+    background: None,
+    is_bold: false,
+    is_dimmed: false,
+    is_italic: false,
+    is_underline: false,
+    is_blink: false,
+    is_reverse: false,
+    is_hidden: false,
+    is_strikethrough: false,
+};
+
+/// Styles with a single property set, which can be combined with `|`.
+impl Style {
+
+    /// A style that is only bold.
+    pub const BOLD: Style = Style { is_bold: true, .. PLAIN };
+
+    /// A style that is only dimmed.
+    pub const DIMMED: Style = Style { is_dimmed: true, .. PLAIN };
+
+    /// A style that is only italic.
+    pub const ITALIC: Style = Style { is_italic: true, .. PLAIN };
+
+    /// A style that is only underlined.
+    pub const UNDERLINE: Style = Style { is_underline: true, .. PLAIN };
+
+    /// A style that is only blinking.
+    pub const BLINK: Style = Style { is_blink: true, .. PLAIN };
+
+    /// A style that only has reverse colours.
+    pub const REVERSE: Style = Style { is_reverse: true, .. PLAIN };
+
+    /// A style that is only hidden.
+    pub const HIDDEN: Style = Style { is_hidden: true, .. PLAIN };
+
+    /// A style that is only struckthrough.
+    pub const STRIKETHROUGH: Style = Style { is_strikethrough: true, .. PLAIN };
+}
+
+/// Combines two styles with `|`, giving a style with the properties of
+/// both. If both have a colour, the one on the right wins, the same as
+/// with [`apply_over`](struct.Style.html#method.apply_over).
+///
+/// ```
+/// use ansi_term::Style;
+/// use ansi_term::Colour::Red;
+///
+/// assert_eq!(Style::BOLD | Style::UNDERLINE | Red, Red.bold().underline());
+/// ```
+impl BitOr for Style {
+    type Output = Style;
+
+    fn bitor(self, rhs: Style) -> Style {
+        rhs.apply_over(&self)
+    }
+}
+
+/// Sets the foreground colour of a style with `|`.
+impl BitOr<Colour> for Style {
+    type Output = Style;
+
+    fn bitor(self, rhs: Colour) -> Style {
+        self.fg(rhs)
+    }
+}
+
+/// Combines a colour with a style using `|`, giving a style with that
+/// foreground colour unless the style has one of its own.
+impl BitOr<Style> for Colour {
+    type Output = Style;
+
+    fn bitor(self, rhs: Style) -> Style {
+        rhs.apply_over(&self.normal())
+    }
+}
+
+impl BitOrAssign for Style {
+    fn bitor_assign(&mut self, rhs: Style) {
+        *self = *self | rhs;
+    }
+}
+
+impl BitOrAssign<Colour> for Style {
+    fn bitor_assign(&mut self, rhs: Colour) {
+        *self = *self | rhs;
+    }
 }
 
 impl Default for Style {
@@ -301,15 +679,64 @@ pub enum Colour {
     /// It might make more sense to look at a [colour chart][cc].
     ///
     /// [cc]: https://upload.wikimedia.org/wikipedia/commons/1/15/Xterm_256color_chart.svg
+    ///
+    /// This variant is only available with the `fixed` feature, which is on
+    /// by default.
+    #[cfg(feature = "fixed")]
     Fixed(u8),
 
     /// A 24-bit RGB color, as specified by ISO-8613-3.
+    ///
+    /// This variant is only available with the `rgb` feature, which is on by
+    /// default.
+    #[cfg(feature = "rgb")]
     RGB(u8, u8, u8),
+
+    /// A colour given by how much cyan, magenta, and yellow it has, as
+    /// specified by ISO-8613-6 (foreground code `38;3`, background code
+    /// `48;3`).
+    ///
+    /// Hardly any terminals understand these codes, so this is for
+    /// programs working with colours from print, which should convert them
+    /// with [`to_rgb`](#method.to_rgb) or
+    /// [`to_depth`](#method.to_depth) before showing them.
+    ///
+    /// This variant is only available with the `cmyk` feature.
+    #[cfg(feature = "cmyk")]
+    CMY(u8, u8, u8),
+
+    /// A colour given by how much cyan, magenta, yellow, and black it has,
+    /// as specified by ISO-8613-6 (foreground code `38;4`, background code
+    /// `48;4`). As with `CMY`, hardly any terminals understand these codes.
+    ///
+    /// This variant is only available with the `cmyk` feature.
+    #[cfg(feature = "cmyk")]
+    CMYK(u8, u8, u8, u8),
 }
 
 
 impl Colour {
 
+    /// Returns an iterator over the eight named colours, from `Black` to
+    /// `White`, in the order of their codes.
+    ///
+    /// The bright versions of these colours don’t have names, but they are
+    /// `Fixed(8)` to `Fixed(15)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::Colour;
+    ///
+    /// let colours: Vec<Colour> = Colour::basic_iter().collect();
+    /// assert_eq!(colours.len(), 8);
+    /// assert_eq!(colours[1], Colour::Red);
+    /// ```
+    pub fn basic_iter() -> impl Iterator<Item=Colour> {
+        use self::Colour::*;
+        [ Black, Red, Green, Yellow, Blue, Purple, Cyan, White ].iter().copied()
+    }
+
     /// Returns a `Style` with the foreground colour set to this colour.
     ///
     /// # Examples
@@ -435,10 +862,12 @@ impl Colour {
     /// # Examples
     ///
     /// ```
+    /// # #[cfg(feature = "fixed")] {
     /// use ansi_term::Colour;
     ///
     /// let style = Colour::Fixed(244).strikethrough();
     /// println!("{}", style.paint("yo"));
+    /// # }
     /// ```
     pub fn strikethrough(self) -> Style {
         Style { foreground: Some(self), is_strikethrough: true, .. Style::default() }
@@ -450,14 +879,144 @@ impl Colour {
     /// # Examples
     ///
     /// ```
+    /// # #[cfg(feature = "rgb")] {
     /// use ansi_term::Colour;
     ///
     /// let style = Colour::RGB(31, 31, 31).on(Colour::White);
     /// println!("{}", style.paint("eyyyy"));
+    /// # }
     /// ```
     pub fn on(self, background: Colour) -> Style {
         Style { foreground: Some(self), background: Some(background), .. Style::default() }
     }
+
+    /// Returns the canonical form of this colour: the first eight colours of
+    /// the 256-colour palette, `Fixed(0)` to `Fixed(7)`, are the same as the
+    /// eight named colours, so they get turned into them. Every other colour
+    /// is returned as it is.
+    ///
+    /// The named colours have shorter codes, and can be shown on terminals
+    /// without the 256-colour palette. There are no named versions of the
+    /// bright colours, `Fixed(8)` to `Fixed(15)`, so those stay the same.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::Colour;
+    ///
+    /// assert_eq!(Colour::Fixed(1).normalize(), Colour::Red);
+    /// assert_eq!(Colour::Fixed(9).normalize(), Colour::Fixed(9));
+    /// assert_eq!(Colour::Fixed(1).normalize(), Colour::Red.normalize());
+    /// ```
+    #[cfg(feature = "fixed")]
+    pub fn normalize(self) -> Colour {
+        match self {
+            Colour::Fixed(0)  => Colour::Black,
+            Colour::Fixed(1)  => Colour::Red,
+            Colour::Fixed(2)  => Colour::Green,
+            Colour::Fixed(3)  => Colour::Yellow,
+            Colour::Fixed(4)  => Colour::Blue,
+            Colour::Fixed(5)  => Colour::Purple,
+            Colour::Fixed(6)  => Colour::Cyan,
+            Colour::Fixed(7)  => Colour::White,
+            other             => other,
+        }
+    }
+
+    /// Returns the canonical form of this colour. Without the `fixed`
+    /// feature, every colour is already in its canonical form.
+    #[cfg(not(feature = "fixed"))]
+    pub fn normalize(self) -> Colour {
+        self
+    }
+
+    /// Returns this colour as a colour from the 256-colour palette, which is
+    /// the reverse of [`normalize`]: the named colours get turned into
+    /// `Fixed(0)` to `Fixed(7)`. Every other colour is returned as it is.
+    ///
+    /// [`normalize`]: #method.normalize
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::Colour;
+    ///
+    /// assert_eq!(Colour::Cyan.to_fixed(), Colour::Fixed(6));
+    /// # #[cfg(feature = "rgb")]
+    /// assert_eq!(Colour::RGB(1, 2, 3).to_fixed(), Colour::RGB(1, 2, 3));
+    /// ```
+    ///
+    /// This method is only available with the `fixed` feature.
+    #[cfg(feature = "fixed")]
+    pub fn to_fixed(self) -> Colour {
+        match self {
+            Colour::Black   => Colour::Fixed(0),
+            Colour::Red     => Colour::Fixed(1),
+            Colour::Green   => Colour::Fixed(2),
+            Colour::Yellow  => Colour::Fixed(3),
+            Colour::Blue    => Colour::Fixed(4),
+            Colour::Purple  => Colour::Fixed(5),
+            Colour::Cyan    => Colour::Fixed(6),
+            Colour::White   => Colour::Fixed(7),
+            other           => other,
+        }
+    }
+
+    /// Returns this colour’s number in the 256-colour palette: `0` for
+    /// `Black` up to `7` for `White`, the number of a `Fixed` colour, and
+    /// `None` for an `RGB` colour, which isn’t in the palette.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::Colour;
+    ///
+    /// assert_eq!(Colour::Yellow.to_index(), Some(3));
+    /// # #[cfg(feature = "fixed")]
+    /// assert_eq!(Colour::Fixed(11).to_index(), Some(11));
+    /// # #[cfg(feature = "rgb")]
+    /// assert_eq!(Colour::RGB(1, 2, 3).to_index(), None);
+    /// ```
+    pub fn to_index(self) -> Option<u8> {
+        match self {
+            Colour::Black         => Some(0),
+            Colour::Red           => Some(1),
+            Colour::Green         => Some(2),
+            Colour::Yellow        => Some(3),
+            Colour::Blue          => Some(4),
+            Colour::Purple        => Some(5),
+            Colour::Cyan          => Some(6),
+            Colour::White         => Some(7),
+            #[cfg(feature = "fixed")]
+            Colour::Fixed(n)      => Some(n),
+            #[cfg(feature = "rgb")]
+            Colour::RGB(_, _, _)  => None,
+            #[cfg(feature = "cmyk")]
+            Colour::CMY(..) | Colour::CMYK(..)  => None,
+        }
+    }
+
+    /// Returns the colour with the given number in the 256-colour palette,
+    /// as one of the named colours if it has a name, so that this is the
+    /// reverse of [`to_index`]. The bright colours, `8` to `15`, don’t have
+    /// names, so they stay as `Fixed` colours.
+    ///
+    /// [`to_index`]: #method.to_index
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::Colour;
+    ///
+    /// assert_eq!(Colour::from_index(3), Colour::Yellow);
+    /// assert_eq!(Colour::from_index(11), Colour::Fixed(11));
+    /// ```
+    ///
+    /// This method is only available with the `fixed` feature.
+    #[cfg(feature = "fixed")]
+    pub fn from_index(index: u8) -> Colour {
+        Colour::Fixed(index).normalize()
+    }
 }
 
 impl From<Colour> for Style {
@@ -477,8 +1036,58 @@ impl From<Colour> for Style {
     }
 }
 
+/// A tuple of red, green, and blue values can be turned into an `RGB`
+/// colour with the `From` trait.
+///
+/// ```
+/// use ansi_term::Colour;
+/// let colour: Colour = (70, 130, 180).into();
+/// assert_eq!(colour, Colour::RGB(70, 130, 180));
+/// ```
+#[cfg(feature = "rgb")]
+impl From<(u8, u8, u8)> for Colour {
+    fn from((r, g, b): (u8, u8, u8)) -> Colour {
+        Colour::RGB(r, g, b)
+    }
+}
+
+/// An array of red, green, and blue values can be turned into an `RGB`
+/// colour with the `From` trait.
+///
+/// ```
+/// use ansi_term::Colour;
+/// assert_eq!(Colour::from([ 70, 130, 180 ]), Colour::RGB(70, 130, 180));
+/// ```
+#[cfg(feature = "rgb")]
+impl From<[u8; 3]> for Colour {
+    fn from([r, g, b]: [u8; 3]) -> Colour {
+        Colour::RGB(r, g, b)
+    }
+}
+
+/// A slice of exactly three bytes, such as a pixel from an image, can be
+/// turned into an `RGB` colour with the `TryFrom` trait. Slices of any
+/// other length are an error.
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use ansi_term::Colour;
+///
+/// let pixels = [ 255, 0, 0, 0, 255, 0 ];
+/// assert_eq!(Colour::try_from(&pixels[3 .. 6]).unwrap(), Colour::RGB(0, 255, 0));
+/// assert!(Colour::try_from(&pixels[..]).is_err());
+/// ```
+#[cfg(feature = "rgb")]
+impl<'a> TryFrom<&'a [u8]> for Colour {
+    type Error = TryFromSliceError;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Colour, TryFromSliceError> {
+        <[u8; 3]>::try_from(bytes).map(Colour::from)
+    }
+}
+
 #[cfg(test)]
-#[cfg(feature = "derive_serde_style")]
+#[cfg(all(feature = "derive_serde_style", feature = "rgb", feature = "fixed"))]
 mod serde_json_tests {
     use super::{Style, Colour};
 
@@ -504,7 +1113,7 @@ mod serde_json_tests {
             Colour::Fixed(255),
         ];
 
-        for colour in colours.into_iter() {
+        for colour in colours.iter() {
             let serialized = serde_json::to_string(&colour).unwrap();
             let deserialized: Colour = serde_json::from_str(&serialized).unwrap();
 
@@ -519,3 +1128,35 @@ mod serde_json_tests {
         assert_eq!(serde_json::to_string(&style).unwrap(), "{\"foreground\":null,\"background\":null,\"is_bold\":false,\"is_dimmed\":false,\"is_italic\":false,\"is_underline\":false,\"is_blink\":false,\"is_reverse\":false,\"is_hidden\":false,\"is_strikethrough\":false}".to_string());
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "fixed")]
+    fn every_index_round_trips() {
+        for index in 0 ..= 255 {
+            assert_eq!(Colour::from_index(index).to_index(), Some(index));
+        }
+    }
+
+    #[test]
+    fn or_combines_styles() {
+        use self::Colour::*;
+
+        let mut style = Style::ITALIC;
+        style |= Blue;
+        style |= Style::BOLD | Red;
+        assert_eq!(style, Red.bold().italic());
+        assert_eq!(Green | Style::BLINK, Green.blink());
+    }
+
+    #[test]
+    fn every_attribute_round_trips() {
+        for attribute in &Attribute::ALL {
+            let style = Style::new().with(*attribute);
+            assert_eq!(style.attributes().collect::<Vec<_>>(), vec![ *attribute ]);
+        }
+    }
+}
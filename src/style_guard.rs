@@ -0,0 +1,267 @@
+use std::io::{self, Write};
+use std::ops::{Deref, DerefMut};
+
+use style::Style;
+
+
+/// A writer that keeps track of the style its output is currently in, so
+/// that blocks of code can change the style for a while with [`style`], and
+/// have it changed back for them afterwards.
+///
+/// The codes only get written just before some text is, so changing the
+/// style and then changing it again without writing anything in between
+/// writes nothing, and only the codes that differ get written otherwise.
+/// Once the last style that was changed has been changed back, the output
+/// gets put back in the plain style straight away.
+///
+/// [`style`]: #method.style
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+/// use ansi_term::StyledWriter;
+/// use ansi_term::Colour::Red;
+/// use ansi_term::Style;
+///
+/// let mut out = StyledWriter::new(Vec::new());
+/// {
+///     let mut red = out.style(Red.bold());
+///     write!(red, "error: ").unwrap();
+///     {
+///         let mut underlined = red.style(Style::new().underline());
+///         write!(underlined, "a.txt").unwrap();
+///     }
+///     write!(red, " is missing").unwrap();
+/// }
+/// write!(out, "!").unwrap();
+///
+/// assert_eq!(out.into_inner(),
+///            b"\x1B[1;31merror: \x1B[4ma.txt\x1B[0m\x1B[1;31m is missing\x1B[0m!");
+/// ```
+#[derive(Debug)]
+pub struct StyledWriter<W: Write> {
+    writer: W,
+
+    /// The style that text written now should be in.
+    current: Style,
+
+    /// The style that the output is actually in.
+    written: Style,
+
+    /// How many styles have been changed and not changed back yet.
+    depth: usize,
+}
+
+impl<W: Write> StyledWriter<W> {
+
+    /// Returns a writer that writes to the given writer, starting in the
+    /// plain style.
+    pub fn new(writer: W) -> StyledWriter<W> {
+        StyledWriter { writer, current: Style::default(), written: Style::default(), depth: 0 }
+    }
+
+    /// Returns the style that text written now would be in.
+    pub fn current_style(&self) -> Style {
+        self.current
+    }
+
+    /// Changes the style of the text written until the returned guard gets
+    /// dropped, which changes it back.
+    ///
+    /// The new style gets applied over the current one, as with
+    /// [`Style::apply_over`], so a guard made inside another one only has
+    /// to give the properties it adds. The guard can be written to, and can
+    /// make guards of its own.
+    ///
+    /// [`Style::apply_over`]: struct.Style.html#method.apply_over
+    pub fn style<'a>(&'a mut self, style: Style) -> StyleGuard<'a, W> {
+        let previous = self.enter(style);
+        StyleGuard { writer: self, previous }
+    }
+
+    /// Runs the function with the style of this writer changed, and changes
+    /// it back afterwards, returning what the function returns.
+    ///
+    /// This is the same as [`style`], but as a closure, which makes the
+    /// nesting of the styles follow the nesting of the code. The style gets
+    /// applied over the current one, and each change between levels only
+    /// writes the codes that differ. The style gets changed back even if the
+    /// function returns an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns the function’s error, or an error from writing the codes that
+    /// put the output back in the plain style at the end.
+    ///
+    /// [`style`]: #method.style
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Write;
+    /// use ansi_term::{Style, StyledWriter};
+    /// use ansi_term::Colour::Red;
+    ///
+    /// let mut out = StyledWriter::new(Vec::new());
+    /// out.with_style(Red.bold(), |out| {
+    ///     write!(out, "error: ")?;
+    ///     out.with_style(Style::new().underline(), |out| write!(out, "a.txt"))
+    /// }).unwrap();
+    ///
+    /// assert_eq!(out.into_inner(), b"\x1B[1;31merror: \x1B[4ma.txt\x1B[0m");
+    /// ```
+    pub fn with_style<T, F>(&mut self, style: Style, f: F) -> io::Result<T>
+    where F: FnOnce(&mut StyledWriter<W>) -> io::Result<T> {
+        let previous = self.enter(style);
+        let result = f(self);
+        let restored = self.leave(previous);
+
+        let value = result?;
+        restored.map(|()| value)
+    }
+
+    /// Returns the writer that this writes to. Any style that it was in
+    /// doesn’t get reset.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Applies a style over the current one, returning the style to go
+    /// back to.
+    fn enter(&mut self, style: Style) -> Style {
+        let previous = self.current;
+        self.current = style.apply_over(&previous);
+        self.depth += 1;
+        previous
+    }
+
+    /// Goes back to the given style, writing the codes for it if it’s the
+    /// last one to be changed back.
+    fn leave(&mut self, previous: Style) -> io::Result<()> {
+        self.current = previous;
+        self.depth -= 1;
+
+        if self.depth == 0 { self.write_style() } else { Ok(()) }
+    }
+
+    /// Writes the codes that put the output in the current style, if it
+    /// isn’t already.
+    fn write_style(&mut self) -> io::Result<()> {
+        if self.written != self.current {
+            write!(self.writer, "{}", self.written.infix(self.current))?;
+            self.written = self.current;
+        }
+
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for StyledWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !buf.is_empty() {
+            self.write_style()?;
+        }
+
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.write_style()?;
+        self.writer.flush()
+    }
+}
+
+
+/// A guard that changes a [`StyledWriter`] back to the style it was in
+/// before when it gets dropped, as returned by [`StyledWriter::style`].
+///
+/// It dereferences to the writer, so it can be written to, and more
+/// guards can be made from it.
+///
+/// [`StyledWriter`]: struct.StyledWriter.html
+/// [`StyledWriter::style`]: struct.StyledWriter.html#method.style
+#[derive(Debug)]
+pub struct StyleGuard<'a, W: Write + 'a> {
+    writer: &'a mut StyledWriter<W>,
+    previous: Style,
+}
+
+impl<'a, W: Write> Deref for StyleGuard<'a, W> {
+    type Target = StyledWriter<W>;
+
+    fn deref(&self) -> &StyledWriter<W> {
+        self.writer
+    }
+}
+
+impl<'a, W: Write> DerefMut for StyleGuard<'a, W> {
+    fn deref_mut(&mut self) -> &mut StyledWriter<W> {
+        self.writer
+    }
+}
+
+impl<'a, W: Write> Write for StyleGuard<'a, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl<'a, W: Write> Drop for StyleGuard<'a, W> {
+    fn drop(&mut self) {
+        let _ = self.writer.leave(self.previous);
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use style::Colour::*;
+
+    #[test]
+    fn styles_without_text_write_nothing() {
+        let mut out = StyledWriter::new(Vec::new());
+        {
+            let mut red = out.style(Red.normal());
+            let _blue = red.style(Blue.underline());
+        }
+        assert_eq!(out.into_inner(), b"");
+    }
+
+    #[test]
+    fn siblings_change_directly() {
+        let mut out = StyledWriter::new(Vec::new());
+        out.with_style(Style::new().bold(), |out| {
+            out.with_style(Red.normal(), |out| write!(out, "a"))?;
+            out.with_style(Blue.normal(), |out| write!(out, "b"))
+        }).unwrap();
+
+        assert_eq!(out.into_inner(), b"\x1B[1;31ma\x1B[34mb\x1B[0m");
+    }
+
+    #[test]
+    fn style_restored_after_error() {
+        let mut out = StyledWriter::new(Vec::new());
+        let result: io::Result<()> = out.with_style(Green.normal(), |out| {
+            write!(out, "x")?;
+            Err(io::Error::other("oops"))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(out.current_style(), Style::default());
+        assert_eq!(out.into_inner(), b"\x1B[32mx\x1B[0m");
+    }
+
+    #[test]
+    fn inner_colour_wins() {
+        let mut out = StyledWriter::new(Vec::new());
+        let mut red = out.style(Red.normal());
+        let blue = red.style(Blue.normal());
+        assert_eq!(blue.current_style(), Blue.normal());
+    }
+}
@@ -0,0 +1,192 @@
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::mem;
+
+use sgr;
+use style::Style;
+use theme::Theme;
+
+
+/// A reason that a style list couldn’t be parsed, as returned by
+/// [`parse_style_list`].
+///
+/// [`parse_style_list`]: fn.parse_style_list.html
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum StyleListError {
+
+    /// An entry doesn’t have an `=` between its name and its style.
+    MissingEquals(String),
+
+    /// An entry has nothing before its `=`.
+    EmptyName(String),
+
+    /// An entry’s style isn’t a list of codes that this crate understands.
+    /// This has the entry’s name, and its style.
+    InvalidStyle(String, String),
+}
+
+impl fmt::Display for StyleListError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            StyleListError::MissingEquals(ref entry)          => write!(f, "style list entry {:?} has no ‘=’", entry),
+            StyleListError::EmptyName(ref entry)              => write!(f, "style list entry {:?} has no name", entry),
+            StyleListError::InvalidStyle(ref name, ref style) => write!(f, "invalid style {:?} for {:?}", style, name),
+        }
+    }
+}
+
+impl Error for StyleListError {}
+
+
+/// Parses a list of named styles in the format of `LS_COLORS`, `GREP_COLORS`
+/// and `GCC_COLORS`, returning them as a `Theme`.
+///
+/// The entries are separated by colons, and each one is a name, an `=`,
+/// and the numbers of the codes that make up the style, separated by
+/// semicolons, as they would appear in an escape code: `error=01;31` is bold
+/// red. Empty entries get skipped, and a name that appears twice gets the
+/// later style.
+///
+/// # Errors
+///
+/// Returns the first entry that can’t be parsed, so that a program can tell
+/// the user which part of their list is wrong.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{parse_style_list, StyleListError};
+/// use ansi_term::Colour::{Red, Blue};
+///
+/// let theme = parse_style_list("error=01;31:path=4;34").unwrap();
+/// assert_eq!(theme.get("error"), Red.bold());
+/// assert_eq!(theme.get("path"), Blue.underline());
+///
+/// assert_eq!(parse_style_list("error=01;31:path"),
+///            Err(StyleListError::MissingEquals(String::from("path"))));
+/// ```
+pub fn parse_style_list(list: &str) -> Result<Theme, StyleListError> {
+    let mut theme = Theme::new();
+
+    for entry in list.split(':').filter(|e| !e.is_empty()) {
+        let equals = entry.find('=').ok_or_else(|| StyleListError::MissingEquals(String::from(entry)))?;
+        let (name, codes) = (&entry[.. equals], &entry[equals + 1 ..]);
+
+        if name.is_empty() {
+            return Err(StyleListError::EmptyName(String::from(entry)));
+        }
+
+        let style = parse_codes(codes)
+            .ok_or_else(|| StyleListError::InvalidStyle(String::from(name), String::from(codes)))?;
+        theme = theme.style(name, style);
+    }
+
+    Ok(theme)
+}
+
+/// Parses the codes of one style, such as `01;38;5;208`.
+fn parse_codes(codes: &str) -> Option<Style> {
+    let mut params = Vec::new();
+    for code in codes.split(';') {
+        params.push(if code.is_empty() { 0 } else { code.parse().ok()? });
+    }
+
+    let mut style = Style::default();
+    let mut params = params.into_iter();
+    while let Some(param) = params.next() {
+        if !sgr::apply(&mut style, param, &mut params) {
+            return None;
+        }
+    }
+
+    Some(style)
+}
+
+impl Theme {
+
+    /// Replaces this theme’s styles with the ones in the style list in the
+    /// environment variable with the given name, so that users can change
+    /// a program’s colours with a variable such as `MYAPP_COLORS`.
+    ///
+    /// Nothing changes if the variable isn’t set. See [`parse_style_list`]
+    /// for the format of the list.
+    ///
+    /// [`parse_style_list`]: fn.parse_style_list.html
+    ///
+    /// # Errors
+    ///
+    /// Returns the first entry that can’t be parsed, leaving the theme as it
+    /// was, so the program can warn the user and carry on with its own
+    /// colours.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::Theme;
+    /// use ansi_term::Colour::{Red, Yellow};
+    ///
+    /// std::env::set_var("EXAMPLE_COLORS", "warning=33");
+    ///
+    /// let mut theme = Theme::new().style("error", Red.bold()).style("warning", Red.normal());
+    /// theme.override_from_env("EXAMPLE_COLORS").unwrap();
+    ///
+    /// assert_eq!(theme.get("error"), Red.bold());
+    /// assert_eq!(theme.get("warning"), Yellow.normal());
+    /// ```
+    pub fn override_from_env(&mut self, variable: &str) -> Result<(), StyleListError> {
+        let list = match env::var(variable) {
+            Ok(list)  => list,
+            Err(_)    => return Ok(()),
+        };
+
+        let overrides = parse_style_list(&list)?;
+        let theme = mem::take(self);
+        *self = overrides.iter().fold(theme, |theme, (name, style)| theme.style(name, style));
+
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn empty_codes_reset() {
+        let theme = parse_style_list("a=:b=;1::").unwrap();
+        assert_eq!(theme.get("a"), Style::default());
+        assert_eq!(theme.get("b"), Style::new().bold());
+    }
+
+    #[test]
+    #[cfg(feature = "fixed")]
+    fn extended_colours() {
+        use style::Colour::Fixed;
+
+        let theme = parse_style_list("a=38;5;208;48;5;17").unwrap();
+        assert_eq!(theme.get("a"), Fixed(208).on(Fixed(17)));
+    }
+
+    #[test]
+    fn bright_colours() {
+        use style::Colour::*;
+
+        let theme = parse_style_list("a=91;104").unwrap();
+
+        #[cfg(feature = "fixed")]
+        assert_eq!(theme.get("a"), Fixed(9).on(Fixed(12)));
+
+        #[cfg(not(feature = "fixed"))]
+        assert_eq!(theme.get("a"), Red.bold().on(Blue));
+    }
+
+    #[test]
+    fn invalid_styles() {
+        assert_eq!(parse_style_list("a=1;x"), Err(StyleListError::InvalidStyle("a".into(), "1;x".into())));
+        assert_eq!(parse_style_list("a=99"), Err(StyleListError::InvalidStyle("a".into(), "99".into())));
+        assert_eq!(parse_style_list("a=38;5"), Err(StyleListError::InvalidStyle("a".into(), "38;5".into())));
+        assert_eq!(parse_style_list("=1"), Err(StyleListError::EmptyName("=1".into())));
+    }
+}
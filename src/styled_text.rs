@@ -0,0 +1,291 @@
+use std::fmt;
+use std::ops::Range;
+use std::slice;
+
+use display::{ANSIString, ANSIStrings, write_strings_to_fmt};
+use style::Style;
+
+
+/// A mutable piece of styled text, for programs such as editors and REPLs
+/// that need to change text after it has been painted.
+///
+/// The text is stored as a list of spans, each with its own `Style`. It can
+/// be edited with [`insert`], [`delete`], and [`restyle`], which are all
+/// addressed by the *visible* position of the characters: escape codes never
+/// count, and positions count characters rather than bytes.
+///
+/// A `StyledText` implements `Display`, writing the minimum of control
+/// characters like `ANSIStrings` does, and [`fragments`] returns its spans as
+/// `ANSIString`s for anything else.
+///
+/// [`insert`]: #method.insert
+/// [`delete`]: #method.delete
+/// [`restyle`]: #method.restyle
+/// [`fragments`]: #method.fragments
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::StyledText;
+/// use ansi_term::Colour::{Red, Blue};
+/// use ansi_term::Style;
+///
+/// let mut text = StyledText::new();
+/// text.push(Style::default(), "let x = 1;");
+/// text.restyle(0 .. 3, Blue.bold());
+/// text.insert(8, Red.normal(), "-");
+/// text.delete(4 .. 6);
+///
+/// assert_eq!(text.to_plain_string(), "let = -1;");
+/// assert_eq!(text.to_string(), "\x1B[1;34mlet\x1B[0m = \x1B[31m-\x1B[0m1;");
+/// ```
+#[derive(PartialEq, Clone, Default, Debug)]
+pub struct StyledText {
+    spans: Vec<(Style, String)>,
+}
+
+impl StyledText {
+
+    /// Creates a new, empty piece of text.
+    pub fn new() -> StyledText {
+        StyledText::default()
+    }
+
+    /// Returns the number of visible characters in the text.
+    pub fn len(&self) -> usize {
+        self.spans.iter().map(|s| s.1.chars().count()).sum()
+    }
+
+    /// Returns whether there is no text at all.
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Returns the text without any of its styles.
+    pub fn to_plain_string(&self) -> String {
+        self.spans.iter().map(|s| &*s.1).collect()
+    }
+
+    /// Adds some text with the given style to the end.
+    pub fn push(&mut self, style: Style, text: &str) {
+        let len = self.len();
+        self.insert(len, style, text);
+    }
+
+    /// Inserts some text with the given style before the character at the
+    /// given position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is past the end of the text.
+    pub fn insert(&mut self, position: usize, style: Style, text: &str) {
+        let index = self.split_at(position);
+        self.spans.insert(index, (style, text.to_owned()));
+        self.tidy();
+    }
+
+    /// Removes the characters in the given range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range goes past the end of the text.
+    pub fn delete(&mut self, range: Range<usize>) {
+        let (start, end) = self.split_range(range);
+        self.spans.drain(start .. end);
+        self.tidy();
+    }
+
+    /// Changes the style of the characters in the given range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range goes past the end of the text.
+    pub fn restyle(&mut self, range: Range<usize>, style: Style) {
+        self.restyle_with(range, |_| style);
+    }
+
+    /// Changes the style of the characters in the given range, by running
+    /// each of their existing styles through the given function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range goes past the end of the text.
+    pub fn restyle_with<F: FnMut(Style) -> Style>(&mut self, range: Range<usize>, mut f: F) {
+        let (start, end) = self.split_range(range);
+        for span in &mut self.spans[start .. end] {
+            span.0 = f(span.0);
+        }
+        self.tidy();
+    }
+
+    /// Returns the spans of the text as `ANSIString`s that borrow from it,
+    /// for use with `ANSIStrings` or the functions in this crate that take
+    /// them.
+    pub fn fragments<'a>(&'a self) -> Vec<ANSIString<'a>> {
+        self.iter().collect()
+    }
+
+    /// Returns an iterator over the spans of the text as `ANSIString`s that
+    /// borrow from it, without collecting them like [`fragments`] does.
+    ///
+    /// [`fragments`]: #method.fragments
+    pub fn iter<'a>(&'a self) -> Fragments<'a> {
+        Fragments { spans: self.spans.iter() }
+    }
+
+    fn split_range(&mut self, range: Range<usize>) -> (usize, usize) {
+        assert!(range.start <= range.end, "range starts after it ends");
+        let start = self.split_at(range.start);
+        let end = self.split_at(range.end);
+        (start, end)
+    }
+
+    /// Makes sure a span starts at the given character position, splitting
+    /// the span containing it in two if necessary, and returns its index.
+    fn split_at(&mut self, position: usize) -> usize {
+        let mut remaining = position;
+
+        for index in 0 .. self.spans.len() {
+            let byte = match self.spans[index].1.char_indices().nth(remaining) {
+                Some((byte, _)) => byte,
+                None => {
+                    remaining -= self.spans[index].1.chars().count();
+                    continue;
+                }
+            };
+
+            if byte == 0 {
+                return index;
+            }
+
+            let style = self.spans[index].0;
+            let rest = self.spans[index].1.split_off(byte);
+            self.spans.insert(index + 1, (style, rest));
+            return index + 1;
+        }
+
+        assert!(remaining == 0, "position {} is past the end of the text", position);
+        self.spans.len()
+    }
+
+    /// Removes empty spans and merges adjacent spans with the same style.
+    fn tidy(&mut self) {
+        self.spans.retain(|s| !s.1.is_empty());
+
+        let mut index = 1;
+        while index < self.spans.len() {
+            if self.spans[index - 1].0 == self.spans[index].0 {
+                let (_, text) = self.spans.remove(index);
+                self.spans[index - 1].1.push_str(&text);
+            }
+            else {
+                index += 1;
+            }
+        }
+    }
+}
+
+impl<'a> From<ANSIStrings<'a>> for StyledText {
+    fn from(strings: ANSIStrings<'a>) -> StyledText {
+        let mut text = StyledText::new();
+        text.spans = strings.0.iter().map(|s| (*s.style_ref(), String::from(&s[..]))).collect();
+        text.tidy();
+        text
+    }
+}
+
+impl<'a> IntoIterator for &'a StyledText {
+    type Item = ANSIString<'a>;
+    type IntoIter = Fragments<'a>;
+
+    fn into_iter(self) -> Fragments<'a> {
+        self.iter()
+    }
+}
+
+/// An iterator over the spans of a `StyledText`, as returned by its
+/// [`iter`] method.
+///
+/// [`iter`]: struct.StyledText.html#method.iter
+#[derive(Clone, Debug)]
+pub struct Fragments<'a> {
+    spans: slice::Iter<'a, (Style, String)>,
+}
+
+impl<'a> Iterator for Fragments<'a> {
+    type Item = ANSIString<'a>;
+
+    fn next(&mut self) -> Option<ANSIString<'a>> {
+        self.spans.next().map(|s| s.0.paint(&*s.1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.spans.size_hint()
+    }
+}
+
+impl fmt::Display for StyledText {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_strings_to_fmt(self.fragments(), f)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use style::Colour::*;
+
+    fn text() -> StyledText {
+        StyledText::from(ANSIStrings(&[ Red.paint("héllo"), Blue.paint(" wörld") ]))
+    }
+
+    #[test]
+    fn insert_at_boundary() {
+        let mut t = text();
+        t.insert(5, Green.normal(), "!");
+        assert_eq!(t.fragments(), vec![ Red.paint("héllo"), Green.paint("!"), Blue.paint(" wörld") ]);
+    }
+
+    #[test]
+    fn delete_across_spans() {
+        let mut t = text();
+        t.delete(2 .. 8);
+        assert_eq!(t.fragments(), vec![ Red.paint("hé"), Blue.paint("rld") ]);
+    }
+
+    #[test]
+    fn delete_everything() {
+        let mut t = text();
+        t.delete(0 .. 11);
+        assert!(t.is_empty());
+    }
+
+    #[test]
+    fn restyle_merges() {
+        let mut t = text();
+        t.restyle(3 .. 11, Red.normal());
+        assert_eq!(t.fragments(), vec![ Red.paint("héllo wörld") ]);
+    }
+
+    #[test]
+    fn restyle_with_keeps_colours() {
+        let mut t = text();
+        t.restyle_with(4 .. 7, |s| s.underline());
+        assert_eq!(t.fragments(), vec![
+            Red.paint("héll"), Red.underline().paint("o"), Blue.underline().paint(" w"), Blue.paint("örld"),
+        ]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn past_the_end() {
+        text().insert(12, Red.normal(), "x");
+    }
+
+    #[test]
+    fn iterating_matches_fragments() {
+        let t = text();
+        assert_eq!((&t).into_iter().collect::<Vec<_>>(), t.fragments());
+    }
+}
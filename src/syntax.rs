@@ -0,0 +1,217 @@
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use downgrade::SgrRewriter;
+use sgr::colour_len;
+
+
+/// How the numbers in the codes for 256-colour palette and RGB colours get
+/// separated.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Debug)]
+pub enum ColourSyntax {
+
+    /// Semicolons, such as `38;2;255;0;0`, which is what nearly every
+    /// terminal expects.
+    #[default]
+    Semicolon,
+
+    /// Colons, in the form from ITU T.416, such as `38:2::255:0:0`. Some
+    /// terminals and multiplexers handle this more reliably, as it can’t be
+    /// confused with a list of separate parameters.
+    Colon,
+}
+
+/// Whether colour codes get written with colons.
+static COLON: AtomicBool = AtomicBool::new(false);
+
+
+/// Sets how the codes for palette and RGB colours get written by the whole
+/// program, from every thread.
+///
+/// A [`CompiledStyle`] renders its prefix when it gets compiled, so it uses
+/// the syntax that was set at the time. To change the syntax of only some
+/// output, write it through a [`ColourSyntaxWriter`] instead.
+///
+/// [`CompiledStyle`]: struct.CompiledStyle.html
+/// [`ColourSyntaxWriter`]: struct.ColourSyntaxWriter.html
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(all(feature = "rgb", feature = "fixed"))] {
+/// use ansi_term::{set_colour_syntax, ColourSyntax};
+/// use ansi_term::Colour::{RGB, Fixed};
+///
+/// set_colour_syntax(ColourSyntax::Colon);
+/// assert_eq!(RGB(255, 0, 0).on(Fixed(17)).paint("!").to_string(),
+///            "\x1B[48:5:17;38:2::255:0:0m!\x1B[0m");
+///
+/// set_colour_syntax(ColourSyntax::Semicolon);
+/// # }
+/// ```
+pub fn set_colour_syntax(syntax: ColourSyntax) {
+    COLON.store(syntax == ColourSyntax::Colon, Ordering::SeqCst);
+}
+
+/// Returns how the codes for palette and RGB colours get written, as set
+/// by [`set_colour_syntax`].
+///
+/// [`set_colour_syntax`]: fn.set_colour_syntax.html
+pub fn colour_syntax() -> ColourSyntax {
+    if COLON.load(Ordering::Relaxed) { ColourSyntax::Colon }
+                                else { ColourSyntax::Semicolon }
+}
+
+
+/// A writer that rewrites the palette and RGB colours in the codes written
+/// through it to use the given syntax, whichever one they were written in.
+///
+/// This is for sending output to one terminal in a different syntax from
+/// the rest of the program, or for passing another program’s output through
+/// to a terminal that only understands one of them. Codes split across
+/// several writes get rewritten too, and everything else gets written as it
+/// is.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "rgb")] {
+/// use std::io::Write;
+/// use ansi_term::{ColourSyntax, ColourSyntaxWriter};
+/// use ansi_term::Colour::RGB;
+///
+/// let mut out = ColourSyntaxWriter::new(Vec::new(), ColourSyntax::Colon);
+/// write!(out, "{}", RGB(255, 0, 0).bold().paint("hot")).unwrap();
+///
+/// assert_eq!(out.into_inner(), b"\x1B[1;38:2::255:0:0mhot\x1B[0m");
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ColourSyntaxWriter<W: Write> {
+    inner: SgrRewriter<W>,
+    syntax: ColourSyntax,
+}
+
+impl<W: Write> ColourSyntaxWriter<W> {
+
+    /// Returns a writer that rewrites colours to the given syntax on their
+    /// way to the given writer.
+    pub fn new(writer: W, syntax: ColourSyntax) -> ColourSyntaxWriter<W> {
+        ColourSyntaxWriter { inner: SgrRewriter::new(writer), syntax }
+    }
+
+    /// Returns the syntax that colours get rewritten to.
+    pub fn syntax(&self) -> ColourSyntax {
+        self.syntax
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner.writer
+    }
+
+    /// Returns the underlying writer. The start of a sequence that hasn’t
+    /// been finished gets thrown away.
+    pub fn into_inner(self) -> W {
+        self.inner.writer
+    }
+}
+
+impl<W: Write> Write for ColourSyntaxWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let syntax = self.syntax;
+        self.inner.write(buf, |params| rewrite(params, syntax))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.writer.flush()
+    }
+}
+
+/// Rewrites the parameters of an SGR code so its palette and RGB colours
+/// use the given syntax. Parameters that can’t be read get left as they are.
+fn rewrite(params: &[u8], syntax: ColourSyntax) -> String {
+    let params = String::from_utf8_lossy(params);
+    let params: Vec<&str> = params.split(';').collect();
+    let mut output = Vec::with_capacity(params.len());
+    let mut index = 0;
+
+    while index < params.len() {
+        let param = params[index];
+
+        let len = match (param, params.get(index + 1).and_then(|k| k.parse().ok())) {
+            ("38" | "48", Some(5))     => 3,
+            ("38" | "48", Some(kind))  => colour_len(kind).unwrap_or(0),
+            _                          => 0,
+        };
+
+        if len > 0 && index + len <= params.len() {
+            output.push(format_colour(&params[index .. index + len], syntax));
+            index += len;
+            continue;
+        }
+
+        // A colour that’s already in the colon form, with or without its
+        // colour space.
+        let mut subparams: Vec<&str> = param.split(':').collect();
+        let len = match subparams.get(1).and_then(|k| k.parse().ok()) {
+            Some(5)     => Some(3),
+            Some(kind)  => colour_len(kind),
+            None        => None,
+        };
+
+        if len == Some(subparams.len() - 1) {
+            subparams.remove(2);
+        }
+
+        match subparams[0] {
+            "38" | "48" if len == Some(subparams.len())  => output.push(format_colour(&subparams, syntax)),
+            _                                            => output.push(param.to_string()),
+        }
+
+        index += 1;
+    }
+
+    output.join(";")
+}
+
+/// Writes a colour, given as its type and numbers without a colour space,
+/// in the given syntax.
+fn format_colour(numbers: &[&str], syntax: ColourSyntax) -> String {
+    match syntax {
+        ColourSyntax::Semicolon                  => numbers.join(";"),
+        ColourSyntax::Colon if numbers[1] == "5"  => numbers.join(":"),
+        ColourSyntax::Colon                      => format!("{}:{}::{}", numbers[0], numbers[1], numbers[2 ..].join(":")),
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn convert(chunks: &[&str], syntax: ColourSyntax) -> String {
+        let mut out = ColourSyntaxWriter::new(Vec::new(), syntax);
+        for chunk in chunks {
+            out.write_all(chunk.as_bytes()).unwrap();
+        }
+        String::from_utf8(out.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn semicolons_to_colons() {
+        assert_eq!(convert(&[ "\x1B[1;38;2;1", ";2;3;48;5;9m" ], ColourSyntax::Colon), "\x1B[1;38:2::1:2:3;48:5:9m");
+    }
+
+    #[test]
+    fn colons_to_semicolons() {
+        assert_eq!(convert(&[ "\x1B[38:2::1:2:3;48:2:4:5:6;4:3;38:5:9m" ], ColourSyntax::Semicolon),
+                   "\x1B[38;2;1;2;3;48;2;4;5;6;4:3;38;5;9m");
+    }
+
+    #[test]
+    fn cmyk_colours() {
+        assert_eq!(convert(&[ "\x1B[38;4;1;2;3;4;48:3:0:5:6:7m" ], ColourSyntax::Colon),
+                   "\x1B[38:4::1:2:3:4;48:3::5:6:7m");
+    }
+}
@@ -0,0 +1,217 @@
+use std::fmt;
+
+use display::ANSIStrings;
+use style::Style;
+use util::visible_width;
+
+
+/// How the text in a table column is lined up within it.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Debug)]
+pub enum Alignment {
+
+    /// Line the text up against the left edge of the column.
+    #[default]
+    Left,
+
+    /// Line the text up against the right edge of the column, as is usual
+    /// for numbers.
+    Right,
+
+    /// Put the text in the middle of the column, with any odd column of
+    /// padding going on the right.
+    Centre,
+}
+
+/// A table of styled cells, laid out in columns as wide as their widest
+/// cell.
+///
+/// Column widths are worked out from the *visible* width of each cell, so
+/// escape codes never throw the columns out of line. (Enable the
+/// `unicode-width` feature to have wide characters measured correctly too.)
+///
+/// Without a border, columns are separated by two spaces, and the last
+/// column isn’t padded if it’s aligned to the left. With a border, the table
+/// gets drawn inside a box of line-drawing characters in the border’s style.
+///
+/// A `Table` implements `Display`, writing one line per row, each of which
+/// ends with a newline.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{Table, Alignment, ANSIStrings};
+/// use ansi_term::Colour::{Red, Green};
+///
+/// let names = [ [ Green.paint("ok") ], [ Red.paint("failed") ] ];
+/// let counts = [ [ Green.paint("12") ], [ Red.paint("3") ] ];
+///
+/// let mut table = Table::new().align(1, Alignment::Right);
+/// table.add_row(vec![ ANSIStrings(&names[0]), ANSIStrings(&counts[0]) ]);
+/// table.add_row(vec![ ANSIStrings(&names[1]), ANSIStrings(&counts[1]) ]);
+///
+/// assert_eq!(table.to_string(),
+///            "\x1B[32mok\x1B[0m      \x1B[32m12\x1B[0m\n\
+///             \x1B[31mfailed\x1B[0m   \x1B[31m3\x1B[0m\n");
+/// ```
+#[derive(Default, Debug)]
+pub struct Table<'a> {
+    rows: Vec<Vec<ANSIStrings<'a>>>,
+    alignments: Vec<Alignment>,
+    border: Option<Style>,
+}
+
+impl<'a> Table<'a> {
+
+    /// Creates a new table with no rows, no border, and every column aligned
+    /// to the left.
+    pub fn new() -> Table<'a> {
+        Table::default()
+    }
+
+    /// Returns a table that aligns the given column (counting from zero)
+    /// with the given alignment.
+    pub fn align(mut self, column: usize, alignment: Alignment) -> Table<'a> {
+        if self.alignments.len() <= column {
+            self.alignments.resize(column + 1, Alignment::default());
+        }
+
+        self.alignments[column] = alignment;
+        self
+    }
+
+    /// Returns a table that gets drawn inside a border with the given style.
+    pub fn border(self, style: Style) -> Table<'a> {
+        Table { border: Some(style), .. self }
+    }
+
+    /// Adds a row of cells to the bottom of the table. Rows don’t need to
+    /// have the same number of cells: missing cells are left blank.
+    pub fn add_row(&mut self, cells: Vec<ANSIStrings<'a>>) {
+        self.rows.push(cells);
+    }
+
+    /// Returns the width of each column, in order.
+    pub fn column_widths(&self) -> Vec<usize> {
+        let mut widths = Vec::new();
+
+        for row in &self.rows {
+            if widths.len() < row.len() {
+                widths.resize(row.len(), 0);
+            }
+
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(visible_width(cell));
+            }
+        }
+
+        widths
+    }
+
+    fn alignment(&self, column: usize) -> Alignment {
+        self.alignments.get(column).cloned().unwrap_or_default()
+    }
+
+    fn write_rule(&self, f: &mut fmt::Formatter, widths: &[usize], style: Style, ends: (char, char, char)) -> fmt::Result {
+        let (left, middle, right) = ends;
+        let mut rule = String::new();
+
+        rule.push(left);
+        for (index, width) in widths.iter().enumerate() {
+            if index > 0 {
+                rule.push(middle);
+            }
+            rule.extend((0 .. width + 2).map(|_| '─'));
+        }
+        rule.push(right);
+
+        writeln!(f, "{}", style.paint(rule))
+    }
+}
+
+impl<'a> fmt::Display for Table<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let widths = self.column_widths();
+        let empty = ANSIStrings(&[]);
+
+        if let Some(style) = self.border {
+            self.write_rule(f, &widths, style, ('┌', '┬', '┐'))?;
+        }
+
+        for row in &self.rows {
+            // Without a border, there’s nothing to line up after the last
+            // cell in the row.
+            let columns = if self.border.is_some() { widths.len() } else { row.len() };
+
+            for (column, &width) in widths[.. columns].iter().enumerate() {
+                let cell = row.get(column).unwrap_or(&empty);
+                let extra = width - visible_width(cell);
+                let (before, after) = match self.alignment(column) {
+                    Alignment::Left    => (0, extra),
+                    Alignment::Right   => (extra, 0),
+                    Alignment::Centre  => (extra / 2, extra - extra / 2),
+                };
+
+                match self.border {
+                    Some(style) if column == 0  => write!(f, "{} ", style.paint("│"))?,
+                    Some(style)                 => write!(f, " {} ", style.paint("│"))?,
+                    None if column == 0         => {},
+                    None                        => f.write_str("  ")?,
+                }
+
+                let last = column + 1 == columns;
+                let after = if last && self.border.is_none() { 0 } else { after };
+                write!(f, "{:before$}{}{:after$}", "", cell, "", before = before, after = after)?;
+            }
+
+            match self.border {
+                Some(style)  => writeln!(f, " {}", style.paint("│"))?,
+                None         => writeln!(f)?,
+            }
+        }
+
+        if let Some(style) = self.border {
+            self.write_rule(f, &widths, style, ('└', '┴', '┘'))?;
+        }
+
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use display::ANSIString;
+    use style::Colour::*;
+
+    #[test]
+    fn widths_ignore_codes() {
+        let cells = [ Red.bold().paint("abc"), Blue.paint("de") ];
+        let mut table = Table::new();
+        table.add_row(vec![ ANSIStrings(&cells[..1]), ANSIStrings(&cells[1..]) ]);
+        table.add_row(vec![ ANSIStrings(&cells[1..]) ]);
+        assert_eq!(table.column_widths(), vec![ 3, 2 ]);
+    }
+
+    #[test]
+    fn centred_with_border() {
+        let cells: [ANSIString; 2] = [ "a".into(), "abcd".into() ];
+        let mut table = Table::new().align(0, Alignment::Centre).border(Style::default());
+        table.add_row(vec![ ANSIStrings(&cells[..1]) ]);
+        table.add_row(vec![ ANSIStrings(&cells[1..]) ]);
+
+        assert_eq!(table.to_string(), "┌──────┐\n\
+                                       │  a   │\n\
+                                       │ abcd │\n\
+                                       └──────┘\n");
+    }
+
+    #[test]
+    fn missing_cells() {
+        let cells: [ANSIString; 2] = [ "a".into(), "b".into() ];
+        let mut table = Table::new();
+        table.add_row(vec![ ANSIStrings(&cells[..1]), ANSIStrings(&cells[1..]) ]);
+        table.add_row(vec![ ANSIStrings(&cells[1..]) ]);
+        assert_eq!(table.to_string(), "a  b\nb\n");
+    }
+}
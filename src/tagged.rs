@@ -0,0 +1,112 @@
+use std::fmt;
+
+use display::{ANSIString, write_strings_to_fmt};
+use util::sub_string_ranges;
+
+
+/// An `ANSIString` with a value of any type attached to it, such as the kind
+/// of token it is or where in a source file it came from.
+///
+/// The tag doesn’t affect how the string gets displayed. It just gets
+/// carried along with the text, so that it’s still there after the text has
+/// been cut up by functions such as [`tagged_sub_string`].
+///
+/// [`tagged_sub_string`]: fn.tagged_sub_string.html
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::Colour::Blue;
+///
+/// #[derive(PartialEq, Clone, Debug)]
+/// enum Token { Keyword, Name }
+///
+/// let keyword = Blue.bold().paint("fn").tagged(Token::Keyword);
+/// assert_eq!(keyword.tag, Token::Keyword);
+/// assert_eq!(keyword.to_string(), "\x1B[1;34mfn\x1B[0m");
+/// ```
+#[derive(PartialEq, Clone, Debug)]
+pub struct TaggedString<'a, T> {
+
+    /// The styled text.
+    pub string: ANSIString<'a>,
+
+    /// The value attached to the text.
+    pub tag: T,
+}
+
+impl<'a> ANSIString<'a> {
+
+    /// Attaches the given tag to this string.
+    pub fn tagged<T>(self, tag: T) -> TaggedString<'a, T> {
+        TaggedString { string: self, tag }
+    }
+}
+
+impl<'a, T> fmt::Display for TaggedString<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.string, f)
+    }
+}
+
+
+/// A set of `TaggedString`s collected together, in order to be written with
+/// a minimum of control characters, like `ANSIStrings`.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::TaggedStrings;
+/// use ansi_term::Colour::Red;
+///
+/// let strings = [ Red.paint("a").tagged(1), Red.bold().paint("b").tagged(2) ];
+/// assert_eq!(TaggedStrings(&strings).to_string(), "\x1B[31ma\x1B[1mb\x1B[0m");
+/// ```
+#[derive(PartialEq, Debug)]
+pub struct TaggedStrings<'b, 'a: 'b, T: 'b>(pub &'b [TaggedString<'a, T>]);
+
+impl<'b, 'a, T> fmt::Display for TaggedStrings<'b, 'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_strings_to_fmt(self.0.iter().map(|t| &t.string), f)
+    }
+}
+
+
+/// Return a substring of the given tagged strings, in the same way as
+/// `sub_string`, keeping both the formatting and the tag of every fragment
+/// that ends up in it.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::tagged_sub_string;
+/// use ansi_term::Colour::{Red, Blue};
+///
+/// let strings = [ Red.paint("hello").tagged("greeting"), Blue.paint(" world").tagged("name") ];
+/// let sub = tagged_sub_string(3, 5, &strings);
+///
+/// assert_eq!(sub, vec![ Red.paint("lo").tagged("greeting"), Blue.paint(" wo").tagged("name") ]);
+/// ```
+pub fn tagged_sub_string<'a, T: Clone>(start: usize, len: usize, strs: &[TaggedString<'a, T>]) -> Vec<TaggedString<'static, T>> {
+    sub_string_ranges(start, len, strs.iter().map(|t| &*t.string))
+        .into_iter()
+        .map(|(index, range)| {
+            let t = &strs[index];
+            t.string.style_ref().paint(String::from(&t.string[range])).tagged(t.tag.clone())
+        })
+        .collect()
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use style::Colour::*;
+
+    #[test]
+    fn sub_string_past_the_end() {
+        let strings = [ Red.paint("ab").tagged(()) ];
+        assert_eq!(tagged_sub_string(1, 10, &strings), vec![ Red.paint("b").tagged(()) ]);
+        assert!(tagged_sub_string(5, 1, &strings).is_empty());
+    }
+}
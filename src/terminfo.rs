@@ -0,0 +1,692 @@
+//! Reading the escape codes for a terminal from its entry in the terminfo
+//! database, rather than assuming that it understands the usual ones.
+
+use std::convert::TryFrom;
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use backend::Backend;
+use depth::ColourDepth;
+use difference::Difference;
+use display::{ANSIString, ANSIStrings};
+use style::{Colour, Style};
+
+
+/// The magic numbers at the start of a compiled terminfo entry, with 16-bit
+/// and 32-bit numbers.
+const MAGIC_16: i16 = 0o432;
+const MAGIC_32: i16 = 0o1036;
+
+/// The indexes of the capabilities this uses, in the order that ncurses
+/// stores them in.
+const MAX_COLORS: usize = 13;
+const ENTER_BLINK_MODE: usize = 26;
+const ENTER_BOLD_MODE: usize = 27;
+const ENTER_DIM_MODE: usize = 30;
+const ENTER_SECURE_MODE: usize = 32;
+const ENTER_REVERSE_MODE: usize = 34;
+const ENTER_UNDERLINE_MODE: usize = 36;
+const EXIT_ATTRIBUTE_MODE: usize = 39;
+const ENTER_ITALICS_MODE: usize = 311;
+const SET_A_FOREGROUND: usize = 359;
+const SET_A_BACKGROUND: usize = 360;
+
+
+/// The description of a terminal from the terminfo database, used to
+/// render styles with the codes that the terminal says it understands.
+///
+/// Almost every terminal in use understands the codes that the rest of this
+/// crate writes, but some unusual ones don’t, or use different codes to
+/// reset the style. Rendering with a `Terminfo` uses the terminal’s own
+/// `bold`, `smul`, `setaf`, `sgr0`, and other capabilities instead, leaving
+/// out any properties it has no capability for, and turning colours into
+/// the closest ones it has.
+///
+/// This type is only available with the `terminfo` feature.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+/// use ansi_term::{ANSIStrings, Terminfo};
+/// use ansi_term::Colour::Red;
+///
+/// let terminfo = Terminfo::from_env().unwrap();
+/// terminfo.write_to(&ANSIStrings(&[ Red.bold().paint("error") ]), &mut io::stdout()).unwrap();
+/// ```
+#[derive(PartialEq, Clone, Debug)]
+pub struct Terminfo {
+    names: Vec<String>,
+    numbers: Vec<Option<i32>>,
+    strings: Vec<Option<Vec<u8>>>,
+
+    /// The names of the extended capabilities that the terminal has, such
+    /// as `RGB` and `Tc`.
+    extended: Vec<String>,
+}
+
+/// A reason that a terminfo entry couldn’t be loaded.
+#[derive(Debug)]
+pub enum TerminfoError {
+
+    /// The `TERM` environment variable isn’t set.
+    NoTerm,
+
+    /// There is no entry for the terminal with the given name.
+    NotFound(String),
+
+    /// The entry isn’t a compiled terminfo entry.
+    Invalid,
+
+    /// The entry couldn’t be read.
+    Io(io::Error),
+}
+
+impl fmt::Display for TerminfoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TerminfoError::NoTerm           => f.write_str("the TERM environment variable is not set"),
+            TerminfoError::NotFound(ref n)  => write!(f, "no terminfo entry for {:?}", n),
+            TerminfoError::Invalid          => f.write_str("invalid terminfo entry"),
+            TerminfoError::Io(ref e)        => write!(f, "error reading terminfo entry: {}", e),
+        }
+    }
+}
+
+impl Error for TerminfoError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            TerminfoError::Io(ref e)  => Some(e),
+            _                         => None,
+        }
+    }
+}
+
+impl From<io::Error> for TerminfoError {
+    fn from(error: io::Error) -> TerminfoError {
+        TerminfoError::Io(error)
+    }
+}
+
+impl Terminfo {
+
+    /// Loads the entry for the terminal named by the `TERM` environment
+    /// variable.
+    pub fn from_env() -> Result<Terminfo, TerminfoError> {
+        match env::var("TERM") {
+            Ok(ref term) if !term.is_empty()  => Terminfo::from_name(term),
+            _                                 => Err(TerminfoError::NoTerm),
+        }
+    }
+
+    /// Loads the entry for the terminal with the given name, looking in the
+    /// same directories as ncurses: `$TERMINFO`, `~/.terminfo`, the
+    /// directories in `$TERMINFO_DIRS`, and then the system ones.
+    pub fn from_name(name: &str) -> Result<Terminfo, TerminfoError> {
+        let first = match name.chars().next() {
+            Some(c) if !name.contains('/')  => c,
+            _                               => return Err(TerminfoError::NotFound(String::from(name))),
+        };
+
+        for directory in search_directories() {
+            // Most systems put the entries in directories named after their
+            // first letter, but macOS uses its number in hexadecimal.
+            for subdirectory in &[ first.to_string(), format!("{:x}", first as u32) ] {
+                let path = directory.join(subdirectory).join(name);
+                if path.is_file() {
+                    return Terminfo::parse(&fs::read(path)?);
+                }
+            }
+        }
+
+        Err(TerminfoError::NotFound(String::from(name)))
+    }
+
+    /// Reads a compiled terminfo entry, in the format written by `tic`.
+    pub fn parse(bytes: &[u8]) -> Result<Terminfo, TerminfoError> {
+        let short = |index: usize| -> Result<i16, TerminfoError> {
+            match bytes.get(index .. index + 2) {
+                Some(b)  => Ok(i16::from_le_bytes([ b[0], b[1] ])),
+                None     => Err(TerminfoError::Invalid),
+            }
+        };
+
+        let number_size = match short(0)? {
+            MAGIC_16  => 2,
+            MAGIC_32  => 4,
+            _         => return Err(TerminfoError::Invalid),
+        };
+
+        let mut sizes = [0_usize; 5];
+        for (index, size) in sizes.iter_mut().enumerate() {
+            *size = usize::try_from(short(2 + index * 2)?).map_err(|_| TerminfoError::Invalid)?;
+        }
+
+        let [ names_size, bools_count, numbers_count, strings_count, table_size ] = sizes;
+        let names_start = 12;
+        let numbers_start = names_start + names_size + bools_count;
+        let numbers_start = numbers_start + numbers_start % 2;
+        let strings_start = numbers_start + numbers_count * number_size;
+        let table_start = strings_start + strings_count * 2;
+        let table = bytes.get(table_start .. table_start + table_size).ok_or(TerminfoError::Invalid)?;
+
+        let names = bytes.get(names_start .. names_start + names_size).ok_or(TerminfoError::Invalid)?.split(|&b| b == b'|' || b == 0)
+            .filter(|n| !n.is_empty())
+            .map(|n| String::from_utf8_lossy(n).into_owned())
+            .collect();
+
+        let mut numbers = Vec::with_capacity(numbers_count);
+        for index in 0 .. numbers_count {
+            let start = numbers_start + index * number_size;
+            let number = match number_size {
+                2  => i32::from(short(start)?),
+                _  => match bytes.get(start .. start + 4) {
+                    Some(b)  => i32::from_le_bytes([ b[0], b[1], b[2], b[3] ]),
+                    None     => return Err(TerminfoError::Invalid),
+                },
+            };
+            numbers.push(if number >= 0 { Some(number) } else { None });
+        }
+
+        let mut strings = Vec::with_capacity(strings_count);
+        for index in 0 .. strings_count {
+            let offset = short(strings_start + index * 2)?;
+            let string = usize::try_from(offset).ok().and_then(|offset| {
+                let rest = table.get(offset ..)?;
+                let end = rest.iter().position(|&b| b == 0)?;
+                Some(rest[.. end].to_vec())
+            });
+            strings.push(string);
+        }
+
+        // The extended capabilities are optional, so an entry that has a
+        // broken section of them still gets read.
+        let extended_start = table_start + table_size;
+        let extended = parse_extended(bytes, extended_start + extended_start % 2, number_size).unwrap_or_default();
+
+        Ok(Terminfo { names, numbers, strings, extended })
+    }
+
+    /// Returns the names of the terminal, the first of which is the one its
+    /// entry is filed under, and the last of which is usually a longer
+    /// description.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Returns the number of colours the terminal can show, from its
+    /// `colors` capability.
+    pub fn max_colours(&self) -> Option<i32> {
+        self.numbers.get(MAX_COLORS).cloned().flatten()
+    }
+
+    /// Returns whether the terminal has the extended capability with the
+    /// given name, such as `RGB` or `Tc`, which aren’t part of the standard
+    /// set.
+    pub fn has_extended(&self, name: &str) -> bool {
+        self.extended.iter().any(|e| e == name)
+    }
+
+    /// Returns the colour depth that the terminal’s number of colours gives
+    /// it, or 24-bit colour if it has the `RGB` or `Tc` capabilities that
+    /// say it can show any colour. Colours that need a higher depth get
+    /// turned into the closest ones the terminal has when they’re rendered.
+    pub fn colour_depth(&self) -> ColourDepth {
+        if self.has_extended("RGB") || self.has_extended("Tc") {
+            return ColourDepth::TrueColour;
+        }
+
+        match self.max_colours() {
+            Some(n) if n >= 1 << 24  => ColourDepth::TrueColour,
+            Some(n) if n >= 256      => ColourDepth::Ansi256,
+            Some(n) if n >= 8        => ColourDepth::Ansi16,
+            _                        => ColourDepth::Monochrome,
+        }
+    }
+
+    /// Returns the codes that put the terminal in the given style, from a
+    /// plain style.
+    pub fn prefix(&self, style: Style) -> Vec<u8> {
+        let mut code = Vec::new();
+        let attributes = [
+            (style.is_bold, ENTER_BOLD_MODE),
+            (style.is_dimmed, ENTER_DIM_MODE),
+            (style.is_italic, ENTER_ITALICS_MODE),
+            (style.is_underline, ENTER_UNDERLINE_MODE),
+            (style.is_blink, ENTER_BLINK_MODE),
+            (style.is_reverse, ENTER_REVERSE_MODE),
+            (style.is_hidden, ENTER_SECURE_MODE),
+        ];
+
+        for &(set, capability) in &attributes {
+            if let (true, Some(string)) = (set, self.string(capability)) {
+                code.extend_from_slice(string);
+            }
+        }
+
+        let depth = self.colour_depth();
+        let colours = [ (style.background, SET_A_BACKGROUND), (style.foreground, SET_A_FOREGROUND) ];
+        for &(colour, capability) in &colours {
+            let index = colour.and_then(|c| c.to_depth(depth)).and_then(Colour::to_index);
+            if let (Some(index), Some(string)) = (index, self.string(capability)) {
+                code.extend(expand(string, &[ i32::from(index) ]));
+            }
+        }
+
+        code
+    }
+
+    /// Returns the codes that put the terminal back in the plain style after
+    /// the given style, which are nothing if the style is already plain.
+    pub fn suffix(&self, style: Style) -> Vec<u8> {
+        match self.string(EXIT_ATTRIBUTE_MODE) {
+            Some(reset) if !style.is_plain()  => reset.to_vec(),
+            _                                 => Vec::new(),
+        }
+    }
+
+    /// Returns the codes that change the terminal from one style to the
+    /// next.
+    pub fn infix(&self, style: Style, next: Style) -> Vec<u8> {
+        match Difference::between(&style, &next) {
+            Difference::ExtraStyles(extra)  => self.prefix(extra),
+            Difference::Reset               => {
+                let mut code = self.suffix(style);
+                code.extend(self.prefix(next));
+                code
+            },
+            Difference::NoDifference        => Vec::new(),
+        }
+    }
+
+    /// Renders the given strings with this terminal’s codes, with the
+    /// minimum of codes between them, returning the bytes to send to it.
+    pub fn paint(&self, strings: &ANSIStrings) -> Vec<u8> {
+        let mut output = Vec::new();
+        self.write_to(strings, &mut output).expect("writing to a Vec");
+        output
+    }
+
+    /// Writes the given strings to a writer with this terminal’s codes, with
+    /// the minimum of codes between them.
+    pub fn write_to<W: Write>(&self, strings: &ANSIStrings, w: &mut W) -> io::Result<()> {
+        let mut previous = Style::default();
+
+        for string in strings.0.iter() {
+            w.write_all(&self.infix(previous, *string.style_ref()))?;
+            w.write_all(string.as_bytes())?;
+            previous = *string.style_ref();
+        }
+
+        w.write_all(&self.suffix(previous))
+    }
+
+    fn string(&self, capability: usize) -> Option<&[u8]> {
+        self.strings.get(capability).and_then(|s| s.as_deref())
+    }
+}
+
+impl Backend for Terminfo {
+    fn transition(&mut self, w: &mut dyn Write, from: Style, to: Style) -> io::Result<()> {
+        w.write_all(&self.infix(from, to))
+    }
+}
+
+impl<'a> ANSIString<'a> {
+
+    /// Renders this string with the codes from the given terminfo entry.
+    ///
+    /// This method is only available with the `terminfo` feature.
+    pub fn paint_terminfo(&self, terminfo: &Terminfo) -> Vec<u8> {
+        terminfo.paint(&ANSIStrings(std::slice::from_ref(self)))
+    }
+}
+
+/// Reads the names of the extended capabilities that an entry has from the
+/// section after its standard ones, returning `None` if there isn’t one.
+fn parse_extended(bytes: &[u8], start: usize, number_size: usize) -> Option<Vec<String>> {
+    let short = |index: usize| bytes.get(index .. index + 2).map(|b| i16::from_le_bytes([ b[0], b[1] ]));
+    let count = |index: usize| short(start + index * 2).and_then(|n| usize::try_from(n).ok());
+
+    let (bools_count, numbers_count, strings_count, table_size) = (count(0)?, count(1)?, count(2)?, count(4)?);
+    let numbers_start = start + 10 + bools_count;
+    let numbers_start = numbers_start + numbers_start % 2;
+    let strings_start = numbers_start + numbers_count * number_size;
+    let names_start = strings_start + strings_count * 2;
+    let names_count = bools_count + numbers_count + strings_count;
+    let table_start = names_start + names_count * 2;
+    let table = bytes.get(table_start .. table_start + table_size)?;
+
+    let mut present = Vec::with_capacity(names_count);
+    present.extend(bytes.get(start + 10 .. start + 10 + bools_count)?.iter().map(|&b| b == 1));
+
+    for index in 0 .. numbers_count {
+        let number = bytes.get(numbers_start + index * number_size .. numbers_start + (index + 1) * number_size)?;
+        present.push(number.last().is_some_and(|&b| b & 0x80 == 0));
+    }
+
+    // The values of the strings come first in the table, followed by the
+    // names, whose offsets start from the end of the last value.
+    let mut values_end = 0;
+    for index in 0 .. strings_count {
+        let offset = short(strings_start + index * 2)?;
+        present.push(offset >= 0);
+
+        if let Ok(offset) = usize::try_from(offset) {
+            let length = table.get(offset ..)?.iter().position(|&b| b == 0)?;
+            values_end = values_end.max(offset + length + 1);
+        }
+    }
+
+    let mut names = Vec::new();
+    for (index, &present) in present.iter().enumerate() {
+        let offset = usize::try_from(short(names_start + index * 2)?).ok()?;
+        let name = table.get(values_end + offset ..)?;
+        let name = &name[.. name.iter().position(|&b| b == 0)?];
+
+        if present {
+            names.push(String::from_utf8_lossy(name).into_owned());
+        }
+    }
+
+    Some(names)
+}
+
+/// Returns the directories to look for terminfo entries in, in order.
+fn search_directories() -> Vec<PathBuf> {
+    let mut directories = Vec::new();
+
+    if let Some(directory) = env::var_os("TERMINFO") {
+        directories.push(PathBuf::from(directory));
+    }
+
+    if let Some(home) = env::var_os("HOME") {
+        directories.push(PathBuf::from(home).join(".terminfo"));
+    }
+
+    if let Ok(dirs) = env::var("TERMINFO_DIRS") {
+        for directory in dirs.split(':') {
+            directories.push(PathBuf::from(if directory.is_empty() { "/usr/share/terminfo" } else { directory }));
+        }
+    }
+
+    for directory in &[ "/etc/terminfo", "/lib/terminfo", "/usr/share/terminfo", "/usr/lib/terminfo" ] {
+        directories.push(PathBuf::from(directory));
+    }
+
+    directories
+}
+
+
+/// Expands the parameters in a capability string, such as the `%p1%d` in
+/// `\E[3%p1%dm`, as `tparm` does, with the given numbers as the parameters.
+fn expand(capability: &[u8], parameters: &[i32]) -> Vec<u8> {
+    let mut params = [0_i32; 9];
+    params[.. parameters.len()].copy_from_slice(parameters);
+
+    let mut output = Vec::new();
+    let mut stack: Vec<i32> = Vec::new();
+    let mut variables = [0_i32; 52];
+    let mut index = 0;
+
+    while index < capability.len() {
+        let byte = capability[index];
+        index += 1;
+
+        // Padding, such as the `$<2>` in vt100’s codes, is for terminals
+        // that need time to act on a code, which none do these days.
+        if byte == b'$' && capability.get(index) == Some(&b'<') {
+            if let Some(end) = capability[index ..].iter().position(|&b| b == b'>') {
+                index += end + 1;
+                continue;
+            }
+        }
+
+        if byte != b'%' {
+            output.push(byte);
+            continue;
+        }
+
+        // Flags and a width can come between the ‘%’ and the conversion,
+        // with a ‘:’ first if the flags start with ‘-’ or ‘+’, so they don’t
+        // get read as subtraction or addition.
+        let start = index;
+        let flags: &[u8] = if capability.get(index) == Some(&b':') { index += 1; b"# +-" } else { b"# " };
+        while index < capability.len() && (flags.contains(&capability[index]) || b".0123456789".contains(&capability[index])) {
+            index += 1;
+        }
+        let width = String::from_utf8_lossy(&capability[start .. index]);
+        let width = width.trim_start_matches(':');
+
+        let code = match capability.get(index) {
+            Some(&code)  => code,
+            None         => break,
+        };
+        index += 1;
+
+        let mut pop = || stack.pop().unwrap_or(0);
+        match code {
+            b'%'         => output.push(b'%'),
+            b'c'         => output.push(pop() as u8),
+            b'd' | b's'  => output.extend(format_number(pop(), width, 'd')),
+            b'o'         => output.extend(format_number(pop(), width, 'o')),
+            b'x'         => output.extend(format_number(pop(), width, 'x')),
+            b'X'         => output.extend(format_number(pop(), width, 'X')),
+            b'p'         => {
+                let n = capability.get(index).map_or(0, |&d| usize::from(d.wrapping_sub(b'1')));
+                stack.push(params.get(n).cloned().unwrap_or(0));
+                index += 1;
+            },
+            b'P' | b'g'  => {
+                let variable = capability.get(index).and_then(|&v| match v {
+                    b'a' ..= b'z'  => Some(usize::from(v - b'a')),
+                    b'A' ..= b'Z'  => Some(usize::from(v - b'A') + 26),
+                    _              => None,
+                });
+                index += 1;
+
+                if let Some(variable) = variable {
+                    if code == b'P' { variables[variable] = pop() }
+                               else { stack.push(variables[variable]) }
+                }
+            },
+            b'\''        => {
+                stack.push(capability.get(index).map_or(0, |&c| i32::from(c)));
+                index += 2;
+            },
+            b'{'         => {
+                let end = capability[index ..].iter().position(|&b| b == b'}').map_or(capability.len(), |p| index + p);
+                stack.push(String::from_utf8_lossy(&capability[index .. end]).parse().unwrap_or(0));
+                index = end + 1;
+            },
+            b'l'         => { pop(); stack.push(0) },
+            b'i'         => { params[0] += 1; params[1] += 1 },
+            b'!'         => { let a = pop(); stack.push((a == 0) as i32) },
+            b'~'         => { let a = pop(); stack.push(!a) },
+            b'+' | b'-' | b'*' | b'/' | b'm' | b'&' | b'|' | b'^' | b'=' | b'>' | b'<' | b'A' | b'O' => {
+                let b = pop();
+                let a = pop();
+                stack.push(match code {
+                    b'+'  => a.wrapping_add(b),
+                    b'-'  => a.wrapping_sub(b),
+                    b'*'  => a.wrapping_mul(b),
+                    b'/'  => a.checked_div(b).unwrap_or(0),
+                    b'm'  => a.checked_rem(b).unwrap_or(0),
+                    b'&'  => a & b,
+                    b'|'  => a | b,
+                    b'^'  => a ^ b,
+                    b'='  => (a == b) as i32,
+                    b'>'  => (a > b) as i32,
+                    b'<'  => (a < b) as i32,
+                    b'A'  => (a != 0 && b != 0) as i32,
+                    _     => (a != 0 || b != 0) as i32,
+                });
+            },
+            b'?' | b';'  => {},
+            b't' if pop() == 0  => index = skip(capability, index, true),
+            b'e'         => index = skip(capability, index, false),
+            _            => {},
+        }
+    }
+
+    output
+}
+
+/// Skips past the part of a conditional that doesn’t get used, returning
+/// the index just after the `%e` that starts the next part (if `to_else` is
+/// set) or the `%;` that ends the conditional.
+fn skip(capability: &[u8], mut index: usize, to_else: bool) -> usize {
+    let mut depth = 0;
+
+    while index + 1 < capability.len() {
+        if capability[index] != b'%' {
+            index += 1;
+            continue;
+        }
+
+        let code = capability[index + 1];
+        index += 2;
+
+        match code {
+            b'?'                           => depth += 1,
+            b';' if depth == 0             => return index,
+            b';'                           => depth -= 1,
+            b'e' if depth == 0 && to_else  => return index,
+            _                              => {},
+        }
+    }
+
+    capability.len()
+}
+
+/// Formats a number with a `printf`-style width, such as the `02` in
+/// `%02d`.
+fn format_number(number: i32, width: &str, conversion: char) -> Vec<u8> {
+    let zero = width.starts_with('0');
+    let width: usize = width.trim_start_matches(|c: char| !c.is_ascii_digit() || c == '0').parse().unwrap_or(0);
+
+    let string = match (conversion, zero) {
+        ('o', true)   => format!("{:0width$o}", number, width = width),
+        ('o', false)  => format!("{:width$o}", number, width = width),
+        ('x', true)   => format!("{:0width$x}", number, width = width),
+        ('x', false)  => format!("{:width$x}", number, width = width),
+        ('X', true)   => format!("{:0width$X}", number, width = width),
+        ('X', false)  => format!("{:width$X}", number, width = width),
+        (_, true)     => format!("{:0width$}", number, width = width),
+        (_, false)    => format!("{:width$}", number, width = width),
+    };
+
+    string.into_bytes()
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use Colour::*;
+
+    static SETAF: &[u8] = b"\x1B[%?%p1%{8}%<%t3%p1%d%e%p1%{16}%<%t9%p1%{8}%-%d%e38;5;%p1%d%;m";
+
+    /// Builds a compiled entry with the given number of colours, the given
+    /// strings at the given indexes, and the given extended flags.
+    fn entry(colours: i16, strings: &[(usize, &[u8])], flags: &[&str]) -> Vec<u8> {
+        let count = strings.iter().map(|s| s.0 + 1).max().unwrap_or(0);
+        let mut offsets = vec![ -1_i16; count ];
+        let mut table = Vec::new();
+
+        for &(index, string) in strings {
+            offsets[index] = table.len() as i16;
+            table.extend_from_slice(string);
+            table.push(0);
+        }
+
+        let names = b"test|a test terminal\0";
+        let mut bytes = Vec::new();
+        for &n in &[ MAGIC_16, names.len() as i16, 0, 14, count as i16, table.len() as i16 ] {
+            bytes.extend_from_slice(&n.to_le_bytes());
+        }
+        bytes.extend_from_slice(names);
+        if names.len() % 2 == 1 { bytes.push(0) }
+        for n in 0 .. 14 {
+            bytes.extend_from_slice(&(if n == MAX_COLORS { colours } else { -1 }).to_le_bytes());
+        }
+        for offset in offsets {
+            bytes.extend_from_slice(&offset.to_le_bytes());
+        }
+        bytes.extend_from_slice(&table);
+
+        if !flags.is_empty() {
+            let names: Vec<u8> = flags.iter().flat_map(|f| f.bytes().chain(Some(0))).collect();
+            if bytes.len() % 2 == 1 { bytes.push(0) }
+            for &n in &[ flags.len() as i16, 0, 0, flags.len() as i16, names.len() as i16 ] {
+                bytes.extend_from_slice(&n.to_le_bytes());
+            }
+            bytes.extend(flags.iter().map(|_| 1));
+            if bytes.len() % 2 == 1 { bytes.push(0) }
+            let mut offset = 0;
+            for flag in flags {
+                bytes.extend_from_slice(&(offset as i16).to_le_bytes());
+                offset += flag.len() + 1;
+            }
+            bytes.extend_from_slice(&names);
+        }
+
+        bytes
+    }
+
+    #[test]
+    fn expand_xterm_colours() {
+        assert_eq!(expand(SETAF, &[ 1 ]), b"\x1B[31m");
+        assert_eq!(expand(SETAF, &[ 9 ]), b"\x1B[91m");
+        assert_eq!(expand(SETAF, &[ 100 ]), b"\x1B[38;5;100m");
+    }
+
+    #[test]
+    fn expand_arithmetic_and_widths() {
+        assert_eq!(expand(b"\x1B[%i%p1%d;%p2%dH", &[ 4, 9 ]), b"\x1B[5;10H");
+        assert_eq!(expand(b"%p1%p2%*%03d%%", &[ 3, 4 ]), b"012%");
+        assert_eq!(expand(b"%p1%Pa%ga%ga%+%c", &[ 33 ]), b"B");
+        assert_eq!(expand(b"\x1B[1m$<2>", &[]), b"\x1B[1m");
+    }
+
+    #[test]
+    fn parse_and_paint() {
+        let terminfo = Terminfo::parse(&entry(8, &[
+            (ENTER_BOLD_MODE, b"<b>"),
+            (EXIT_ATTRIBUTE_MODE, b"<0>"),
+            (SET_A_FOREGROUND, b"<f%p1%d>"),
+        ], &[])).unwrap();
+
+        assert_eq!(terminfo.names(), &[ "test".to_string(), "a test terminal".to_string() ]);
+        assert_eq!(terminfo.max_colours(), Some(8));
+
+        let strings = [ Red.bold().paint("a"), Blue.underline().paint("b") ];
+        assert_eq!(terminfo.paint(&ANSIStrings(&strings)), b"<b><f1>a<0><f4>b<0>");
+    }
+
+    #[cfg(feature = "fixed")]
+    #[test]
+    fn palette_colours_fit_the_terminal() {
+        let terminfo = Terminfo::parse(&entry(8, &[ (SET_A_FOREGROUND, b"<f%p1%d>") ], &[])).unwrap();
+        assert_eq!(terminfo.prefix(Fixed(12).normal()), b"<f4>");
+    }
+
+    #[test]
+    fn invalid_entries() {
+        assert!(Terminfo::parse(b"").is_err());
+        assert!(Terminfo::parse(&entry(8, &[], &[])[.. 20]).is_err());
+    }
+
+    #[test]
+    fn extended_flags() {
+        let terminfo = Terminfo::parse(&entry(256, &[ (ENTER_BOLD_MODE, b"<b>") ], &[ "AX", "Tc" ])).unwrap();
+        assert!(terminfo.has_extended("Tc"));
+        assert!(!terminfo.has_extended("RGB"));
+        assert_eq!(terminfo.colour_depth(), ColourDepth::TrueColour);
+        assert_eq!(Terminfo::parse(&entry(256, &[], &[])).unwrap().colour_depth(), ColourDepth::Ansi256);
+    }
+}
@@ -0,0 +1,301 @@
+//! Helpers for testing code that paints things.
+//!
+//! When two rendered strings differ, the standard `assert_eq!` prints them
+//! with their escape codes interpreted by the terminal, or as `\u{1b}` blobs
+//! in their `Debug` form, neither of which shows where they differ. The
+//! [`assert_styled_eq!`] macro prints the codes as `\x1B` instead, and lines
+//! up both sides code by code.
+//!
+//! [`assert_styled_eq!`]: ../macro.assert_styled_eq.html
+//!
+//! When the strings are built from styled fragments rather than rendered
+//! first, [`explain_differences`] says which fragments differ and how.
+//!
+//! [`explain_differences`]: fn.explain_differences.html
+
+use std::fmt::{self, Write};
+
+use display::ANSIStrings;
+use escaped::Escaped;
+use style::{Attribute, Colour, Style};
+use util::normalize;
+
+
+/// Asserts that two values render to the same string, printing the escape
+/// codes of both sides in a readable form if they don’t.
+///
+/// Either side can be anything that implements `Display`, such as an
+/// `ANSIString`, `ANSIStrings`, or a plain `&str`.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use] extern crate ansi_term;
+/// use ansi_term::Colour::Yellow;
+///
+/// # fn main() {
+/// assert_styled_eq!(Yellow.bold().paint("hi"), "\x1B[1;33mhi\x1B[0m");
+/// # }
+/// ```
+///
+/// A failure looks like this:
+///
+/// ```text
+/// styled strings are not equal
+///   left: "\x1B[1;33mhi\x1B[0m"
+///  right: "\x1B[33mhi\x1B[0m"
+///     0: \x1B[1;33m  |  \x1B[33m  <--
+///     1: hi          |  hi
+///     2: \x1B[0m     |  \x1B[0m
+/// ```
+#[macro_export]
+macro_rules! assert_styled_eq {
+    ($left:expr, $right:expr $(,)?) => {{
+        let left = $left.to_string();
+        let right = $right.to_string();
+        if left != right {
+            $crate::testing::styled_mismatch(&left, &right);
+        }
+    }};
+}
+
+
+/// Returns the string with its escape codes and other control characters
+/// written the way they would be in a Rust string literal, so `ESC` shows
+/// up as `\x1B`.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::testing::escape_codes;
+/// use ansi_term::Colour::Red;
+///
+/// assert_eq!(escape_codes(&Red.paint("a").to_string()), r"\x1B[31ma\x1B[0m");
+/// ```
+pub fn escape_codes(s: &str) -> String {
+    Escaped::new(s).to_string()
+}
+
+/// Splits a rendered string into its escape codes and the runs of text
+/// between them.
+fn fragments(s: &str) -> Vec<&str> {
+    let bytes = s.as_bytes();
+    let mut fragments = Vec::new();
+    let (mut start, mut index) = (0, 0);
+
+    while index < bytes.len() {
+        if bytes[index] != 0x1B || bytes.get(index + 1) != Some(&b'[') {
+            index += 1;
+            continue;
+        }
+
+        // A control sequence ends with a byte between ‘@’ and ‘~’.
+        let end = bytes[index + 2 ..].iter()
+                                     .position(|b| (0x40 ..= 0x7E).contains(b))
+                                     .map_or(bytes.len(), |p| index + 2 + p + 1);
+
+        if start < index { fragments.push(&s[start .. index]) }
+        fragments.push(&s[index .. end]);
+        start = end;
+        index = end;
+    }
+
+    if start < s.len() { fragments.push(&s[start ..]) }
+    fragments
+}
+
+/// Panics with both strings escaped and lined up fragment by fragment.
+/// This is what `assert_styled_eq!` calls when its sides differ.
+#[doc(hidden)]
+pub fn styled_mismatch(left: &str, right: &str) -> ! {
+    let left_fragments: Vec<String> = fragments(left).into_iter().map(escape_codes).collect();
+    let right_fragments: Vec<String> = fragments(right).into_iter().map(escape_codes).collect();
+    let width = left_fragments.iter().map(|f| f.chars().count()).max().unwrap_or(0);
+
+    let mut message = String::from("styled strings are not equal\n");
+    let _ = writeln!(message, "  left: \"{}\"", escape_codes(left));
+    let _ = writeln!(message, " right: \"{}\"", escape_codes(right));
+
+    for index in 0 .. left_fragments.len().max(right_fragments.len()) {
+        let l = left_fragments.get(index).map_or("", |f| f.as_str());
+        let r = right_fragments.get(index).map_or("", |f| f.as_str());
+        let marker = if l == r { "" } else { "  <--" };
+        let _ = writeln!(message, "{:>5}: {:width$}  |  {}{}", index, l, r, marker, width = width);
+    }
+
+    panic!("{}", message)
+}
+
+
+/// A fragment that differs between two sets of styled strings, as returned
+/// by [`explain_differences`].
+///
+/// Its `Display` implementation explains the difference in a sentence.
+///
+/// [`explain_differences`]: fn.explain_differences.html
+#[derive(PartialEq, Clone, Debug)]
+pub struct FragmentDifference {
+
+    /// The index of the fragment, after the strings have been normalised.
+    pub index: usize,
+
+    /// The style and text of the fragment on the left, or `None` if the
+    /// left side has fewer fragments.
+    pub left: Option<(Style, String)>,
+
+    /// The style and text of the fragment on the right, or `None` if the
+    /// right side has fewer fragments.
+    pub right: Option<(Style, String)>,
+}
+
+/// Compares two sets of styled strings fragment by fragment, returning the
+/// fragments that differ in their text or their style.
+///
+/// Both sides get normalised first, as with the [`normalize`] function, and
+/// colours get compared by what they look like, so two sides that display
+/// the same way have no differences even if they were built up
+/// differently.
+///
+/// [`normalize`]: ../fn.normalize.html
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::ANSIStrings;
+/// use ansi_term::testing::explain_differences;
+/// use ansi_term::Colour::{Red, Blue};
+///
+/// let left = [ Red.paint("error: "), Blue.paint("a.txt") ];
+/// let right = [ Red.bold().paint("error: "), Blue.paint("a.txt") ];
+///
+/// let differences = explain_differences(&ANSIStrings(&left), &ANSIStrings(&right));
+/// assert_eq!(differences.len(), 1);
+/// assert_eq!(differences[0].to_string(),
+///            "fragment 0 \"error: \" differs in style: bold is off on the left and on on the right");
+/// ```
+pub fn explain_differences(left: &ANSIStrings, right: &ANSIStrings) -> Vec<FragmentDifference> {
+    let spans = |strings: &ANSIStrings| -> Vec<(Style, String)> {
+        normalize(strings).iter().map(|s| (s.style_ref().normalize(), String::from(&s[..]))).collect()
+    };
+
+    let (left, right) = (spans(left), spans(right));
+    let mut differences = Vec::new();
+
+    for index in 0 .. left.len().max(right.len()) {
+        let (l, r) = (left.get(index), right.get(index));
+        if l != r {
+            differences.push(FragmentDifference { index, left: l.cloned(), right: r.cloned() });
+        }
+    }
+
+    differences
+}
+
+impl FragmentDifference {
+
+    /// Returns whether the text of the fragment differs.
+    pub fn text_differs(&self) -> bool {
+        self.left.as_ref().map(|l| &l.1) != self.right.as_ref().map(|r| &r.1)
+    }
+
+    /// Returns whether the style of the fragment differs.
+    pub fn style_differs(&self) -> bool {
+        self.left.as_ref().map(|l| l.0) != self.right.as_ref().map(|r| r.0)
+    }
+
+    /// Returns the attributes that are set on one side but not the other, if
+    /// both sides have the fragment.
+    pub fn attributes(&self) -> Vec<Attribute> {
+        match (&self.left, &self.right) {
+            (Some(l), Some(r))  => Attribute::ALL.iter().cloned().filter(|&a| l.0.has(a) != r.0.has(a)).collect(),
+            _                   => Vec::new(),
+        }
+    }
+}
+
+impl fmt::Display for FragmentDifference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (left, right) = match (&self.left, &self.right) {
+            (Some(l), Some(r))  => (l, r),
+            (Some(l), None)     => return write!(f, "fragment {} \"{}\" is only on the left", self.index, escape_codes(&l.1)),
+            (None, Some(r))     => return write!(f, "fragment {} \"{}\" is only on the right", self.index, escape_codes(&r.1)),
+            (None, None)        => return write!(f, "fragment {} is on neither side", self.index),
+        };
+
+        if self.text_differs() {
+            write!(f, "fragment {} differs in text: \"{}\" on the left and \"{}\" on the right",
+                   self.index, escape_codes(&left.1), escape_codes(&right.1))?;
+            if !self.style_differs() {
+                return Ok(());
+            }
+            f.write_str(", and in style: ")?;
+        }
+        else {
+            write!(f, "fragment {} \"{}\" differs in style: ", self.index, escape_codes(&left.1))?;
+        }
+
+        let mut changes = Vec::new();
+        for attribute in self.attributes() {
+            let state = |style: Style| if style.has(attribute) { "on" } else { "off" };
+            changes.push(format!("{} is {} on the left and {} on the right",
+                                 format!("{:?}", attribute).to_lowercase(), state(left.0), state(right.0)));
+        }
+
+        let colour = |colour: Option<Colour>| colour.map_or(String::from("none"), |c| format!("{:?}", c));
+        if left.0.foreground != right.0.foreground {
+            changes.push(format!("the foreground is {} on the left and {} on the right",
+                                 colour(left.0.foreground), colour(right.0.foreground)));
+        }
+        if left.0.background != right.0.background {
+            changes.push(format!("the background is {} on the left and {} on the right",
+                                 colour(left.0.background), colour(right.0.background)));
+        }
+
+        f.write_str(&changes.join(", "))
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use style::Style;
+    use style::Colour::*;
+
+    #[test]
+    fn splits_codes_from_text() {
+        let painted = Red.bold().paint("a;b").to_string();
+        assert_eq!(fragments(&painted), vec![ "\x1B[1;31m", "a;b", "\x1B[0m" ]);
+    }
+
+    #[test]
+    fn differences_ignore_fragmenting() {
+        let left = [ Red.paint("a"), Red.paint("b"), Blue.paint("") ];
+        let right = [ Red.paint("ab") ];
+        assert!(explain_differences(&ANSIStrings(&left), &ANSIStrings(&right)).is_empty());
+    }
+
+    #[test]
+    fn text_and_colour_differences() {
+        let left = [ Red.paint("ab"), Blue.paint("c") ];
+        let right = [ Red.on(Blue).paint("ax") ];
+        let differences = explain_differences(&ANSIStrings(&left), &ANSIStrings(&right));
+
+        assert_eq!(differences[0].to_string(),
+                   "fragment 0 differs in text: \"ab\" on the left and \"ax\" on the right, \
+                    and in style: the background is none on the left and Blue on the right");
+        assert_eq!(differences[1].to_string(), "fragment 1 \"c\" is only on the left");
+    }
+
+    #[test]
+    fn equal_sides() {
+        assert_styled_eq!(Red.paint("x"), "\x1B[31mx\x1B[0m");
+    }
+
+    #[test]
+    #[should_panic(expected = r#"1: \x1B[1;31m  |  \x1B[31m  <--"#)]
+    fn unequal_sides() {
+        assert_styled_eq!(Style::default().paint("-").to_string() + &Red.bold().paint("x").to_string(),
+                          "-\x1B[31mx\x1B[0m");
+    }
+}
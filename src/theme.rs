@@ -0,0 +1,149 @@
+use styled_text::StyledText;
+use style::Style;
+
+
+/// A set of styles with names, such as `"keyword"` or `"error"`, so that
+/// the types that print themselves in colour don’t have to hard-code their
+/// colours, and a program can change all of them in one place.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{Theme, Style};
+/// use ansi_term::Colour::{Red, Blue};
+///
+/// let theme = Theme::new().style("error", Red.bold()).style("path", Blue.underline());
+/// assert_eq!(theme.get("error"), Red.bold());
+/// assert_eq!(theme.get("unknown"), Style::default());
+/// ```
+#[derive(PartialEq, Clone, Default, Debug)]
+pub struct Theme {
+    styles: Vec<(String, Style)>,
+}
+
+impl Theme {
+
+    /// Creates a theme with no styles in it.
+    pub fn new() -> Theme {
+        Theme::default()
+    }
+
+    /// Returns a theme that has the given style under the given name,
+    /// replacing any style it had under that name before.
+    pub fn style(mut self, name: &str, style: Style) -> Theme {
+        self.styles.retain(|s| s.0 != name);
+        self.styles.push((String::from(name), style));
+        self
+    }
+
+    /// Returns the style with the given name, or the plain style if the
+    /// theme doesn’t have one with that name.
+    pub fn get(&self, name: &str) -> Style {
+        self.styles.iter()
+            .find(|s| s.0 == name)
+            .map(|s| s.1)
+            .unwrap_or_default()
+    }
+
+    /// Returns an iterator over the names and styles in this theme, in the
+    /// order they were added.
+    pub fn iter(&self) -> impl Iterator<Item=(&str, Style)> {
+        self.styles.iter().map(|s| (s.0.as_str(), s.1))
+    }
+
+    /// Renders a value in this theme, returning its text with the styles
+    /// it asked for.
+    ///
+    /// The result can be displayed, or turned into `ANSIStrings` with its
+    /// [`fragments`] method.
+    ///
+    /// [`fragments`]: struct.StyledText.html#method.fragments
+    pub fn render<T: StyledDisplay + ?Sized>(&self, value: &T) -> StyledText {
+        let mut text = StyledText::new();
+        value.fmt_styled(self, &mut text);
+        text
+    }
+}
+
+
+/// A type that knows how to print itself in colour, using the styles in a
+/// [`Theme`].
+///
+/// This is the coloured counterpart of `Display`: instead of writing plain
+/// text to a formatter, an implementation pushes text onto a `StyledText`,
+/// picking the style of each part out of the theme by name. The value can
+/// then be rendered with [`Theme::render`].
+///
+/// [`Theme`]: struct.Theme.html
+/// [`Theme::render`]: struct.Theme.html#method.render
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{StyledDisplay, StyledText, Theme};
+/// use ansi_term::Colour::{Red, Blue};
+///
+/// struct Failure { path: String, reason: String }
+///
+/// impl StyledDisplay for Failure {
+///     fn fmt_styled(&self, theme: &Theme, f: &mut StyledText) {
+///         f.push(theme.get("path"), &self.path);
+///         f.push(theme.get("plain"), ": ");
+///         f.push(theme.get("error"), &self.reason);
+///     }
+/// }
+///
+/// let theme = Theme::new().style("error", Red.normal()).style("path", Blue.normal());
+/// let failure = Failure { path: "a.txt".into(), reason: "not found".into() };
+///
+/// assert_eq!(theme.render(&failure).to_string(),
+///            "\x1B[34ma.txt\x1B[0m: \x1B[31mnot found\x1B[0m");
+/// ```
+pub trait StyledDisplay {
+
+    /// Pushes this value’s text onto the given `StyledText`, in styles
+    /// taken from the theme.
+    fn fmt_styled(&self, theme: &Theme, f: &mut StyledText);
+}
+
+impl<T: StyledDisplay + ?Sized> StyledDisplay for &T {
+    fn fmt_styled(&self, theme: &Theme, f: &mut StyledText) {
+        (**self).fmt_styled(theme, f)
+    }
+}
+
+impl<T: StyledDisplay> StyledDisplay for [T] {
+    fn fmt_styled(&self, theme: &Theme, f: &mut StyledText) {
+        for value in self {
+            value.fmt_styled(theme, f);
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use style::Colour::*;
+
+    struct Name(&'static str);
+
+    impl StyledDisplay for Name {
+        fn fmt_styled(&self, theme: &Theme, f: &mut StyledText) {
+            f.push(theme.get("name"), self.0);
+        }
+    }
+
+    #[test]
+    fn later_styles_replace_earlier_ones() {
+        let theme = Theme::new().style("name", Red.normal()).style("name", Blue.normal());
+        assert_eq!(theme.get("name"), Blue.normal());
+    }
+
+    #[test]
+    fn slices_render_every_value() {
+        let theme = Theme::new().style("name", Red.normal());
+        let names = [ Name("a"), Name("b") ];
+        assert_eq!(theme.render(&names[..]).to_string(), "\x1B[31mab\x1B[0m");
+    }
+}
@@ -1,27 +1,205 @@
 use display::*;
-use std::ops::Deref;
+use style::Style;
+use std::cmp::Ordering;
+use std::ops::{Deref, Range};
+use std::{fmt, slice, str};
 
 /// Return a substring of the given ANSIStrings sequence, while keeping the formatting.
 pub fn sub_string<'a>(start: usize, len: usize, strs: &ANSIStrings<'a>) -> Vec<ANSIString<'static>> {
+    sub_string_ranges(start, len, strs.0.iter().map(|i| i.deref()))
+        .into_iter()
+        .map(|(index, range)| {
+            let i = &strs.0[index];
+            i.style_ref().paint(String::from(&i[range]))
+        })
+        .collect()
+}
+
+/// Return a substring of the given ANSIStrings sequence, while keeping the
+/// formatting, where `start` and `len` count grapheme clusters instead of
+/// bytes.
+///
+/// A grapheme cluster is what a user would think of as one character, such
+/// as a letter with combining accents or an emoji made of several code
+/// points, so the substring never cuts one in half.
+///
+/// This function is only available with the `unicode-segmentation` feature.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{sub_string_graphemes, ANSIStrings};
+/// use ansi_term::Colour::Red;
+///
+/// let strings = [ Red.paint("e\u{301}t\u{e9}") ];
+/// assert_eq!(sub_string_graphemes(0, 1, &ANSIStrings(&strings)),
+///            vec![ Red.paint("e\u{301}") ]);
+/// ```
+#[cfg(feature = "unicode-segmentation")]
+pub fn sub_string_graphemes<'a>(start: usize, len: usize, strs: &ANSIStrings<'a>) -> Vec<ANSIString<'static>> {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    let text = unstyle(strs);
+    let mut boundaries = text.grapheme_indices(true).map(|g| g.0).chain(Some(text.len()));
+    let from = boundaries.nth(start).unwrap_or(text.len());
+    let to = if len == 0 { from } else { boundaries.nth(len - 1).unwrap_or(text.len()) };
+
+    sub_string(from, to - from, strs)
+}
+
+/// Return a substring of the given ANSIStrings sequence, while keeping the
+/// formatting, where `start` and `width` count columns on the screen
+/// instead of bytes.
+///
+/// Wide characters (such as CJK ideographs) that would only partly fit in
+/// the substring are left out, so it’s never wider than `width`, and
+/// zero-width characters stay with the character before them. This is how
+/// to truncate styled text to fit in a window.
+///
+/// This function is only available with the `unicode-width` feature.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{sub_string_columns, ANSIStrings};
+/// use ansi_term::Colour::Red;
+///
+/// let strings = [ Red.paint("日本語") ];
+/// assert_eq!(sub_string_columns(0, 5, &ANSIStrings(&strings)),
+///            vec![ Red.paint("日本") ]);
+/// ```
+#[cfg(feature = "unicode-width")]
+pub fn sub_string_columns<'a>(start: usize, width: usize, strs: &ANSIStrings<'a>) -> Vec<ANSIString<'static>> {
+    use unicode_width::UnicodeWidthChar;
+
+    let text = unstyle(strs);
+    let end = start.saturating_add(width);
+    let mut from = None;
+    let mut to = text.len();
+    let mut column = 0;
+
+    for (index, c) in text.char_indices() {
+        let w = c.width().unwrap_or(0);
+
+        if from.is_none() && column >= start && w > 0 {
+            from = Some(index);
+        }
+
+        if column + w > end {
+            to = index;
+            break;
+        }
+
+        column += w;
+    }
+
+    match from {
+        Some(from) if from < to  => sub_string(from, to - from, strs),
+        _                        => Vec::new(),
+    }
+}
+
+/// Turns a list of styles paired with owned text back into strings, one for
+/// each span, as the reverse of [`ANSIStrings::to_spans`].
+///
+/// [`ANSIStrings::to_spans`]: type.ANSIStrings.html#method.to_spans
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{from_spans, ANSIStrings};
+/// use ansi_term::Colour::Red;
+/// use ansi_term::Style;
+///
+/// let strings = from_spans(vec![ (Red.bold(), "error".to_string()), (Style::new(), ": oops".to_string()) ]);
+/// assert_eq!(ANSIStrings(&strings).to_string(), "\x1B[1;31merror\x1B[0m: oops");
+/// ```
+pub fn from_spans<I>(spans: I) -> Vec<ANSIString<'static>>
+where I: IntoIterator<Item=(Style, String)> {
+    spans.into_iter().map(|(style, text)| style.paint(text)).collect()
+}
+
+/// A wrapper that displays the given strings cut off after the given number
+/// of columns on the screen, without making a truncated copy of them first.
+///
+/// The codes get written with the minimum of control characters, and the
+/// style is reset after the last visible character. As with
+/// [`sub_string_columns`], a wide character that would only partly fit gets
+/// left out.
+///
+/// This type is only available with the `unicode-width` feature.
+///
+/// [`sub_string_columns`]: fn.sub_string_columns.html
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{ANSIStrings, Truncated};
+/// use ansi_term::Colour::{Red, Blue};
+///
+/// let strings = [ Red.paint("error: "), Blue.paint("a.txt is missing") ];
+/// assert_eq!(Truncated(&ANSIStrings(&strings), 12).to_string(),
+///            "\x1B[31merror: \x1B[34ma.txt\x1B[0m");
+/// ```
+#[cfg(feature = "unicode-width")]
+#[derive(Debug)]
+pub struct Truncated<'a>(pub &'a ANSIStrings<'a>, pub usize);
+
+#[cfg(feature = "unicode-width")]
+impl<'a> fmt::Display for Truncated<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use unicode_width::UnicodeWidthChar;
+
+        let mut remaining = self.1;
+        let fragments = self.0.0.iter().map_while(|fragment| {
+            if remaining == 0 {
+                return None;
+            }
+
+            let text: &'a str = fragment.deref();
+            let mut end = text.len();
+
+            for (index, c) in text.char_indices() {
+                let width = c.width().unwrap_or(0);
+                if width > remaining {
+                    end = index;
+                    remaining = 0;
+                    break;
+                }
+
+                remaining -= width;
+            }
+
+            Some(fragment.style_ref().paint(&text[.. end]))
+        });
+
+        write_strings_to_fmt(fragments, f)
+    }
+}
+
+/// Works out which parts of which fragments make up the substring of
+/// `len` bytes starting at byte `start`, returning the index of each
+/// fragment with the byte range within it.
+pub(crate) fn sub_string_ranges<'s, I>(start: usize, len: usize, fragments: I) -> Vec<(usize, Range<usize>)>
+where I: Iterator<Item=&'s str> {
     let mut vec = Vec::new();
     let mut pos = start;
     let mut len_rem = len;
 
-    for i in strs.0.iter() {
-        let fragment = i.deref();
+    for (index, fragment) in fragments.enumerate() {
         let frag_len = fragment.len();
         if pos >= frag_len {
             pos -= frag_len;
             continue;
         }
-        if len_rem <= 0 {
+        if len_rem == 0 {
             break;
         }
 
         let end = pos + len_rem;
         let pos_end = if end >= frag_len { frag_len } else { end };
 
-        vec.push(i.style_ref().paint(String::from(&fragment[pos..pos_end])));
+        vec.push((index, pos .. pos_end));
 
         if end <= frag_len {
             break;
@@ -34,12 +212,49 @@ pub fn sub_string<'a>(start: usize, len: usize, strs: &ANSIStrings<'a>) -> Vec<A
     vec
 }
 
+/// Return a copy of `strs` with the empty fragments removed, and adjacent
+/// fragments with the same style merged into one.
+///
+/// Collections built up programmatically often end up with fragments that
+/// have no text, but still have a style that would be written out. The result
+/// of this function displays the same text with the same styles, but without
+/// the codes that didn’t do anything.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{normalize, ANSIStrings};
+/// use ansi_term::Colour::{Red, Blue};
+///
+/// let strings = [ Red.paint("a"), Blue.bold().paint(""), Red.paint("b") ];
+/// let normal = normalize(&ANSIStrings(&strings));
+/// assert_eq!(normal, vec![ Red.paint("ab") ]);
+/// ```
+pub fn normalize<'a>(strs: &ANSIStrings<'a>) -> Vec<ANSIString<'a>> {
+    let mut vec: Vec<ANSIString<'a>> = Vec::with_capacity(strs.0.len());
+
+    for i in strs.0.iter().filter(|i| !i.is_empty()) {
+        if let Some(last) = vec.last_mut() {
+            if last.style_ref() == i.style_ref() {
+                let mut merged = String::from(&last[..]);
+                merged.push_str(i);
+                *last = i.style_ref().paint(merged);
+                continue;
+            }
+        }
+
+        vec.push(i.clone());
+    }
+
+    vec
+}
+
 /// Return a concatenated copy of `strs` without the formatting, as an allocated `String`.
 pub fn unstyle(strs: &ANSIStrings) -> String {
     let mut s = String::new();
 
     for i in strs.0.iter() {
-        s += &i.deref();
+        s += i.deref();
     }
 
     s
@@ -54,9 +269,348 @@ pub fn unstyled_len(strs: &ANSIStrings) -> usize {
     l
 }
 
+/// Return the number of columns the text of `strs` takes up on the screen.
+///
+/// With the `unicode-width` feature enabled, this counts wide characters
+/// (such as CJK ideographs and most emoji) as two columns and zero-width ones
+/// as none. Without it, every character counts as one column.
+///
+/// # Examples
+///
+/// ```
+/// use ansi_term::{visible_width, ANSIStrings};
+/// use ansi_term::Colour::{Red, Blue};
+///
+/// let strings = [ Red.paint("naïve"), Blue.paint(" café") ];
+/// assert_eq!(visible_width(&ANSIStrings(&strings)), 10);
+/// ```
+pub fn visible_width(strs: &ANSIStrings) -> usize {
+    strs.0.iter().map(|i| str_width(i)).sum()
+}
+
+/// The number of columns a string without any escape codes takes up.
+#[cfg(feature = "unicode-width")]
+pub(crate) fn str_width(s: &str) -> usize {
+    ::unicode_width::UnicodeWidthStr::width(s)
+}
+
+/// The number of columns a string without any escape codes takes up.
+#[cfg(not(feature = "unicode-width"))]
+pub(crate) fn str_width(s: &str) -> usize {
+    s.chars().count()
+}
+
+impl<'a> ANSIString<'a> {
+
+    /// Returns this string with leading and trailing whitespace removed,
+    /// keeping its style.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::Colour::Red;
+    ///
+    /// assert_eq!(Red.paint("  hi \n").trim(), Red.paint("hi"));
+    /// ```
+    pub fn trim<'s>(&'s self) -> ANSIString<'s> {
+        self.style_ref().paint(self.deref().trim())
+    }
+
+    /// Returns this string with leading whitespace removed, keeping its
+    /// style.
+    pub fn trim_start<'s>(&'s self) -> ANSIString<'s> {
+        self.style_ref().paint(self.deref().trim_start())
+    }
+
+    /// Returns this string with trailing whitespace removed, keeping its
+    /// style.
+    pub fn trim_end<'s>(&'s self) -> ANSIString<'s> {
+        self.style_ref().paint(self.deref().trim_end())
+    }
+
+    /// Returns a string with this one’s text repeated `n` times, in the same
+    /// style, so the codes only get written once around the whole lot.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::Colour::Black;
+    ///
+    /// let rule = Black.bold().paint("=-").repeat(3);
+    /// assert_eq!(rule.to_string(), "\x1B[1;30m=-=-=-\x1B[0m");
+    /// ```
+    pub fn repeat(&self, n: usize) -> ANSIString<'static> {
+        self.style_ref().paint(self.deref().repeat(n))
+    }
+
+    /// Compares the text of this string with the text of another, ignoring
+    /// both their styles.
+    pub fn cmp_unstyled(&self, other: &ANSIString) -> Ordering {
+        self.deref().cmp(other.deref())
+    }
+}
+
+impl<'a> ANSIStrings<'a> {
+
+    /// Returns the fragments of these strings with leading and trailing
+    /// whitespace removed from their text as a whole, keeping the style of
+    /// each fragment that’s left.
+    ///
+    /// Fragments at either end that are nothing but whitespace get left out
+    /// completely.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::ANSIStrings;
+    /// use ansi_term::Colour::{Red, Blue};
+    ///
+    /// let strings = [ Red.paint("  "), Blue.paint(" a b "), Red.paint("c\n") ];
+    /// assert_eq!(ANSIStrings(&strings).trim(),
+    ///            vec![ Blue.paint("a b "), Red.paint("c") ]);
+    /// ```
+    pub fn trim(&self) -> Vec<ANSIString<'a>> {
+        trim_fragments(self.0, true, true)
+    }
+
+    /// Returns the fragments of these strings with leading whitespace
+    /// removed from their text as a whole, keeping their styles.
+    pub fn trim_start(&self) -> Vec<ANSIString<'a>> {
+        trim_fragments(self.0, true, false)
+    }
+
+    /// Returns the fragments of these strings with trailing whitespace
+    /// removed from their text as a whole, keeping their styles.
+    pub fn trim_end(&self) -> Vec<ANSIString<'a>> {
+        trim_fragments(self.0, false, true)
+    }
+
+    /// Compares the text of these strings with the text of some others,
+    /// ignoring their styles and how the text is split into fragments, so
+    /// that styled rows can be sorted by what they say.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use ansi_term::ANSIStrings;
+    /// use ansi_term::Colour::{Red, Blue};
+    ///
+    /// let a = [ Red.paint("app"), Blue.paint("le") ];
+    /// let b = [ Blue.bold().paint("apple") ];
+    /// let c = [ Red.paint("banana") ];
+    ///
+    /// assert_eq!(ANSIStrings(&a).cmp_unstyled(&ANSIStrings(&b)), Ordering::Equal);
+    /// assert_eq!(ANSIStrings(&a).cmp_unstyled(&ANSIStrings(&c)), Ordering::Less);
+    /// ```
+    pub fn cmp_unstyled(&self, other: &ANSIStrings) -> Ordering {
+        let ours = self.0.iter().flat_map(|i| i.as_bytes());
+        let theirs = other.0.iter().flat_map(|i| i.as_bytes());
+        ours.cmp(theirs)
+    }
+
+    /// Returns the text of these strings without any of their styles, the
+    /// same as the [`unstyle`] function, for logging or writing a copy of
+    /// the output to a file.
+    ///
+    /// [`unstyle`]: fn.unstyle.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::ANSIStrings;
+    /// use ansi_term::Colour::{Red, Blue};
+    ///
+    /// let strings = [ Red.paint("error"), Blue.paint(": a.txt") ];
+    /// assert_eq!(ANSIStrings(&strings).to_plain_string(), "error: a.txt");
+    /// ```
+    pub fn to_plain_string(&self) -> String {
+        unstyle(self)
+    }
+
+    /// Returns the character at the given visible position, counting
+    /// characters rather than bytes and ignoring escape codes, along with
+    /// the style it gets displayed in, or `None` if the position is past the
+    /// end of the text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::ANSIStrings;
+    /// use ansi_term::Colour::{Red, Blue};
+    ///
+    /// let strings = [ Red.paint("né"), Blue.bold().paint("e") ];
+    /// assert_eq!(ANSIStrings(&strings).char_at(1), Some(('é', Red.normal())));
+    /// assert_eq!(ANSIStrings(&strings).char_at(2), Some(('e', Blue.bold())));
+    /// assert_eq!(ANSIStrings(&strings).char_at(3), None);
+    /// ```
+    pub fn char_at(&self, index: usize) -> Option<(char, Style)> {
+        self.chars().nth(index)
+    }
+
+    /// Returns the fragments of these strings as a list of styles paired
+    /// with owned text, which doesn’t borrow from anything. This keeps every
+    /// fragment, even empty ones, so [`from_spans`] turns it back into the
+    /// same strings.
+    ///
+    /// [`from_spans`]: fn.from_spans.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::ANSIStrings;
+    /// use ansi_term::Colour::{Red, Blue};
+    ///
+    /// let strings = [ Red.paint("a"), Blue.paint("b") ];
+    /// assert_eq!(ANSIStrings(&strings).to_spans(),
+    ///            vec![ (Red.normal(), "a".to_string()), (Blue.normal(), "b".to_string()) ]);
+    /// ```
+    pub fn to_spans(&self) -> Vec<(Style, String)> {
+        self.0.iter().map(|i| (*i.style_ref(), String::from(i.deref()))).collect()
+    }
+
+    /// Returns an iterator over the visible characters of these strings,
+    /// each along with the style it gets displayed in, skipping over the
+    /// boundaries between fragments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::ANSIStrings;
+    /// use ansi_term::Colour::{Red, Blue};
+    ///
+    /// let strings = [ Red.paint("ab"), Blue.paint(""), Blue.bold().paint("c") ];
+    /// let chars: Vec<_> = ANSIStrings(&strings).chars().collect();
+    ///
+    /// assert_eq!(chars, vec![ ('a', Red.normal()), ('b', Red.normal()), ('c', Blue.bold()) ]);
+    /// ```
+    pub fn chars(&self) -> StyledChars<'a> {
+        StyledChars { fragments: self.0.iter(), current: "".chars(), style: Style::default() }
+    }
+
+    /// Returns the style of the character at the given visible position, as
+    /// with [`char_at`], or `None` if the position is past the end of the
+    /// text.
+    ///
+    /// [`char_at`]: #method.char_at
+    pub fn style_at(&self, index: usize) -> Option<Style> {
+        self.char_at(index).map(|(_, style)| style)
+    }
+}
+
+impl<'a, S: 'a + ToOwned + ?Sized + PartialEq + AsRef<[u8]>> ANSIGenericStrings<'a, S>
+where <S as ToOwned>::Owned: fmt::Debug {
+
+    /// Returns an iterator over the bytes of the text of these strings, each
+    /// along with the style it gets displayed in, leaving out the bytes of
+    /// the escape codes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::{ANSIByteStrings, Colour};
+    ///
+    /// let strings = [ Colour::Green.paint("a".as_bytes()), Colour::Red.paint("b".as_bytes()) ];
+    /// let bytes: Vec<_> = ANSIByteStrings(&strings).bytes().collect();
+    ///
+    /// assert_eq!(bytes, vec![ (b'a', Colour::Green.normal()), (b'b', Colour::Red.normal()) ]);
+    /// ```
+    pub fn bytes(&self) -> StyledBytes<'a, S> {
+        StyledBytes { fragments: self.0.iter(), current: [].iter(), style: Style::default() }
+    }
+}
+
+/// An iterator over the characters of some [`ANSIStrings`] along with their
+/// styles, returned by [`ANSIStrings::chars`].
+///
+/// [`ANSIStrings`]: type.ANSIStrings.html
+/// [`ANSIStrings::chars`]: type.ANSIStrings.html#method.chars
+#[derive(Clone, Debug)]
+pub struct StyledChars<'a> {
+    fragments: slice::Iter<'a, ANSIString<'a>>,
+    current: str::Chars<'a>,
+    style: Style,
+}
+
+impl<'a> Iterator for StyledChars<'a> {
+    type Item = (char, Style);
+
+    fn next(&mut self) -> Option<(char, Style)> {
+        loop {
+            if let Some(c) = self.current.next() {
+                return Some((c, self.style));
+            }
+
+            let fragment = self.fragments.next()?;
+            let text: &'a str = fragment.deref();
+            self.current = text.chars();
+            self.style = *fragment.style_ref();
+        }
+    }
+}
+
+/// An iterator over the bytes of some [`ANSIGenericStrings`] along with
+/// their styles, returned by their `bytes` method.
+///
+/// [`ANSIGenericStrings`]: struct.ANSIGenericStrings.html
+#[derive(Clone, Debug)]
+pub struct StyledBytes<'a, S: 'a + ToOwned + ?Sized>
+where <S as ToOwned>::Owned: fmt::Debug {
+    fragments: slice::Iter<'a, ANSIGenericString<'a, S>>,
+    current: slice::Iter<'a, u8>,
+    style: Style,
+}
+
+impl<'a, S: 'a + ToOwned + ?Sized + AsRef<[u8]>> Iterator for StyledBytes<'a, S>
+where <S as ToOwned>::Owned: fmt::Debug {
+    type Item = (u8, Style);
+
+    fn next(&mut self) -> Option<(u8, Style)> {
+        loop {
+            if let Some(&b) = self.current.next() {
+                return Some((b, self.style));
+            }
+
+            let fragment = self.fragments.next()?;
+            let text: &'a S = fragment;
+            self.current = text.as_ref().iter();
+            self.style = *fragment.style_ref();
+        }
+    }
+}
+
+fn trim_fragments<'a>(strs: &'a [ANSIString<'a>], start: bool, end: bool) -> Vec<ANSIString<'a>> {
+    let first = if !start { 0 } else {
+        strs.iter().position(|i| !i.deref().trim_start().is_empty()).unwrap_or(strs.len())
+    };
+    let last = if !end { strs.len() } else {
+        strs.iter().rposition(|i| !i.deref().trim_end().is_empty()).map_or(0, |p| p + 1)
+    };
+
+    if first >= last {
+        return Vec::new();
+    }
+
+    let mut vec = strs[first .. last].to_vec();
+
+    if start {
+        let text: &'a str = strs[first].deref();
+        vec[0] = strs[first].style_ref().paint(text.trim_start());
+    }
+
+    if end {
+        let text: &'a str = strs[last - 1].deref();
+        let text = if start && first + 1 == last { text.trim() } else { text.trim_end() };
+        vec[last - first - 1] = strs[last - 1].style_ref().paint(text);
+    }
+
+    vec
+}
+
 #[cfg(test)]
 mod test {
     use Colour::*;
+    use Style;
     use display::*;
     use super::*;
 
@@ -78,4 +632,95 @@ mod test {
         ];
         assert_eq!(sub_string(3, 11, &a).as_slice(), &l2);
     }
+
+    #[cfg(feature = "unicode-segmentation")]
+    #[test]
+    fn graphemes_across_fragments() {
+        let l = [ Red.paint("ae"), Blue.paint("\u{301}b") ];
+        assert_eq!(sub_string_graphemes(1, 1, &ANSIStrings(&l)), vec![ Red.paint("e"), Blue.paint("\u{301}") ]);
+    }
+
+    #[cfg(feature = "unicode-width")]
+    #[test]
+    fn columns_skip_half_a_wide_character() {
+        let l = [ Red.paint("a日"), Blue.paint("本b") ];
+        assert_eq!(sub_string_columns(2, 4, &ANSIStrings(&l)), vec![ Blue.paint("本b") ]);
+        assert_eq!(sub_string_columns(0, 2, &ANSIStrings(&l)), vec![ Red.paint("a") ]);
+    }
+
+    #[cfg(feature = "unicode-width")]
+    #[test]
+    fn columns_to_the_end() {
+        let l = [ Red.paint("a日"), Blue.paint("本b") ];
+        assert_eq!(sub_string_columns(1, usize::MAX, &ANSIStrings(&l)), vec![ Red.paint("日"), Blue.paint("本b") ]);
+    }
+
+    #[test]
+    fn trim_everything() {
+        let l = [ Red.paint(" "), Blue.paint("\t\n") ];
+        assert!(ANSIStrings(&l).trim().is_empty());
+    }
+
+    #[test]
+    fn trim_keeps_inner_fragments() {
+        let l = [ Red.paint(" a"), Blue.paint(""), Red.paint("b "), Blue.paint(" ") ];
+        assert_eq!(ANSIStrings(&l).trim_end(), vec![ Red.paint(" a"), Blue.paint(""), Red.paint("b") ]);
+        assert_eq!(ANSIStrings(&l).trim_start()[0], Red.paint("a"));
+    }
+
+    #[test]
+    fn repeat_nothing() {
+        assert_eq!(Red.paint("ab").repeat(0), Red.paint(""));
+    }
+
+    #[test]
+    fn cmp_prefix() {
+        let a = [ Red.paint("ab") ];
+        let b = [ Red.paint("a"), Blue.paint(""), Red.paint("bc") ];
+        assert_eq!(ANSIStrings(&a).cmp_unstyled(&ANSIStrings(&b)), Ordering::Less);
+        assert_eq!(Blue.paint("b").cmp_unstyled(&Red.paint("a")), Ordering::Greater);
+    }
+
+    #[test]
+    fn normalize_only_empties() {
+        let l = [ Red.paint(""), Style::default().paint("") ];
+        assert!(normalize(&ANSIStrings(&l)).is_empty());
+    }
+
+    #[test]
+    fn normalize_keeps_different_styles() {
+        let l = [ Red.paint("a"), Red.paint(""), Red.bold().paint("b"), Red.bold().paint("c") ];
+        assert_eq!(normalize(&ANSIStrings(&l)), vec![ Red.paint("a"), Red.bold().paint("bc") ]);
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-width")]
+    fn truncated_wide_characters() {
+        let strings = [ Red.paint("ab"), Blue.paint("日本"), Red.paint("c") ];
+        assert_eq!(Truncated(&ANSIStrings(&strings), 5).to_string(), "\x1B[31mab\x1B[34m日\x1B[0m");
+        assert_eq!(Truncated(&ANSIStrings(&strings), 0).to_string(), "");
+        assert_eq!(Truncated(&ANSIStrings(&strings), 9).to_string(), ANSIStrings(&strings).to_string());
+    }
+
+    #[test]
+    fn spans_round_trip() {
+        let strings = [ Red.paint("a"), Blue.bold().paint(""), Red.paint("b") ];
+        assert_eq!(from_spans(ANSIStrings(&strings).to_spans()), strings.to_vec());
+    }
+
+    #[test]
+    fn bytes_of_text_strings() {
+        let strings = [ Red.paint("é"), Blue.paint("") ];
+        let bytes: Vec<_> = ANSIStrings(&strings).bytes().collect();
+        assert_eq!(bytes, vec![ (0xC3, Red.normal()), (0xA9, Red.normal()) ]);
+    }
+
+    #[test]
+    fn style_at_skips_empty_fragments() {
+        let strings = [ Red.paint(""), Blue.paint("ab"), Style::default().paint("c") ];
+        let strings = ANSIStrings(&strings);
+        assert_eq!(strings.style_at(0), Some(Blue.normal()));
+        assert_eq!(strings.style_at(2), Some(Style::default()));
+        assert_eq!(strings.style_at(3), None);
+    }
 }
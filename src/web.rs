@@ -0,0 +1,481 @@
+use std::error::Error;
+use std::fmt::{self, Write};
+use std::ops::RangeInclusive;
+
+use display::ANSIStrings;
+use style::{Colour, Style};
+
+
+/// A message for a browser’s developer console, with the styles of the
+/// text given as CSS, as returned by [`ANSIStrings::to_console_message`].
+///
+/// Browser consoles don’t understand escape codes. Instead, each `%c` in
+/// the format string takes the CSS in the next argument, so the message
+/// gets shown by passing the format and then every one of the styles to
+/// `console.log`, such as with `wasm-bindgen`’s `console::log` functions.
+///
+/// [`ANSIStrings::to_console_message`]: type.ANSIStrings.html#method.to_console_message
+#[derive(PartialEq, Clone, Default, Debug)]
+pub struct ConsoleMessage {
+
+    /// The text, with `%c` before each part with a different style, and
+    /// any `%` in the text itself doubled.
+    pub format: String,
+
+    /// The CSS for each `%c` in the format, in order.
+    pub styles: Vec<String>,
+}
+
+impl<'a> ANSIStrings<'a> {
+
+    /// Returns the strings as a message for a browser’s console, with their
+    /// styles turned into CSS.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::ANSIStrings;
+    /// use ansi_term::Colour::Red;
+    /// use ansi_term::Style;
+    ///
+    /// let message = ANSIStrings(&[ Red.bold().paint("error"), Style::new().paint(": 100%") ])
+    ///                   .to_console_message();
+    ///
+    /// assert_eq!(message.format, "%cerror%c: 100%%");
+    /// assert_eq!(message.styles, vec![ "color: #cd0000; font-weight: bold", "" ]);
+    /// ```
+    pub fn to_console_message(&self) -> ConsoleMessage {
+        let mut message = ConsoleMessage::default();
+        let mut previous = None;
+
+        for fragment in self.0 {
+            let style = *fragment.style_ref();
+            if previous != Some(style) {
+                message.format.push_str("%c");
+                message.styles.push(style.to_css());
+                previous = Some(style);
+            }
+
+            message.format.push_str(&fragment.replace('%', "%%"));
+        }
+
+        message
+    }
+
+    /// Returns the strings with their escape codes, like `to_string`, but
+    /// with every line ending in `\r\n`.
+    ///
+    /// Terminal emulators that run in a browser, such as xterm.js, only
+    /// move the cursor down on `\n`, the way a real terminal does without a
+    /// TTY driver in front of it to add the carriage return.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::ANSIStrings;
+    /// use ansi_term::Colour::Green;
+    ///
+    /// let lines = [ Green.paint("ok\nok\r\n") ];
+    /// assert_eq!(ANSIStrings(&lines).to_crlf_string(), "\x1B[32mok\r\nok\r\n\x1B[0m");
+    /// ```
+    pub fn to_crlf_string(&self) -> String {
+        let rendered = self.to_string();
+        let mut crlf = String::with_capacity(rendered.len());
+
+        for (index, line) in rendered.split('\n').enumerate() {
+            if index > 0 { crlf.push_str("\r\n") }
+            crlf.push_str(line.strip_suffix('\r').unwrap_or(line));
+        }
+
+        crlf
+    }
+}
+
+impl Style {
+
+    /// Returns the CSS declarations that show text in this style, separated
+    /// by semicolons, for browser consoles and HTML.
+    ///
+    /// The basic colours use xterm’s default palette. Blinking text doesn’t
+    /// blink, as browsers have mostly stopped supporting it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::Colour::{Blue, White};
+    ///
+    /// assert_eq!(Blue.on(White).underline().to_css(),
+    ///            "color: #0000ee; background-color: #e5e5e5; text-decoration: underline");
+    /// ```
+    pub fn to_css(&self) -> String {
+        self.css_declarations().iter()
+            .map(|&(property, ref value)| format!("{}: {}", property, value))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+
+    /// Returns the CSS declarations that show text in this style in their
+    /// most compact form, each ending with a semicolon, to go in the
+    /// `style` attribute of an HTML element.
+    ///
+    /// The values never contain quotes or angle brackets, so the string can
+    /// go straight into a quoted attribute without being escaped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "rgb")] {
+    /// use ansi_term::Colour::RGB;
+    ///
+    /// let style = RGB(255, 0, 0).bold();
+    /// assert_eq!(style.to_inline_css(), "color:#ff0000;font-weight:bold;");
+    /// assert_eq!(format!("<span style=\"{}\">", style.to_inline_css()),
+    ///            "<span style=\"color:#ff0000;font-weight:bold;\">");
+    /// # }
+    /// ```
+    pub fn to_inline_css(&self) -> String {
+        let mut css = String::new();
+        for (property, value) in self.css_declarations() {
+            write!(css, "{}:{};", property, value).expect("writing to a String");
+        }
+        css
+    }
+
+    /// Returns the CSS properties and values for this style, in order.
+    fn css_declarations(&self) -> Vec<(&'static str, String)> {
+        let (mut foreground, mut background) = (self.foreground, self.background);
+        if self.is_reverse {
+            foreground = self.background.or(Some(Colour::Black));
+            background = self.foreground.or(Some(Colour::White));
+        }
+
+        let mut declarations = Vec::new();
+        if let Some(colour) = foreground  { declarations.push(("color", css_colour(colour))) }
+        if let Some(colour) = background  { declarations.push(("background-color", css_colour(colour))) }
+        if self.is_bold                   { declarations.push(("font-weight", String::from("bold"))) }
+        if self.is_dimmed                 { declarations.push(("opacity", String::from("0.5"))) }
+        if self.is_italic                 { declarations.push(("font-style", String::from("italic"))) }
+        if self.is_hidden                 { declarations.push(("visibility", String::from("hidden"))) }
+
+        match (self.is_underline, self.is_strikethrough) {
+            (true, true)   => declarations.push(("text-decoration", String::from("underline line-through"))),
+            (true, false)  => declarations.push(("text-decoration", String::from("underline"))),
+            (false, true)  => declarations.push(("text-decoration", String::from("line-through"))),
+            (false, false) => {},
+        }
+
+        declarations
+    }
+}
+
+/// A reason that a string couldn’t be parsed as a CSS colour, as returned
+/// by [`Colour::parse_css`].
+///
+/// [`Colour::parse_css`]: enum.Colour.html#method.parse_css
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ParseColourError {
+
+    /// The string isn’t in any of the forms that can be parsed.
+    UnknownFormat,
+
+    /// The string is in a known form, but one of the numbers in it is
+    /// missing, can’t be read, or is out of range.
+    InvalidComponent,
+}
+
+impl fmt::Display for ParseColourError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseColourError::UnknownFormat     => f.write_str("not a CSS hex, rgb(), or hsl() colour"),
+            ParseColourError::InvalidComponent  => f.write_str("invalid number in CSS colour"),
+        }
+    }
+}
+
+impl Error for ParseColourError {}
+
+#[cfg(feature = "rgb")]
+impl Colour {
+
+    /// Returns this colour as an `RGB` colour, with the values from xterm’s
+    /// default palette for the named and `Fixed` colours, and `CMY` and
+    /// `CMYK` colours converted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::Colour;
+    ///
+    /// assert_eq!(Colour::White.to_rgb(), Colour::RGB(229, 229, 229));
+    /// # #[cfg(feature = "fixed")]
+    /// assert_eq!(Colour::Fixed(196).to_rgb(), Colour::RGB(255, 0, 0));
+    /// assert_eq!(Colour::RGB(1, 2, 3).to_rgb(), Colour::RGB(1, 2, 3));
+    /// ```
+    ///
+    /// This method is only available with the `rgb` feature.
+    #[cfg(feature = "rgb")]
+    pub fn to_rgb(self) -> Colour {
+        let (r, g, b) = rgb(self);
+        Colour::RGB(r, g, b)
+    }
+
+    /// Parses a colour written the way CSS writes them, as an `RGB` colour:
+    /// in hex, such as `#4682b4` or `#48b`, or as `rgb(70, 130, 180)` or
+    /// `hsl(207, 44%, 49%)`.
+    ///
+    /// The numbers can be separated by commas or by spaces, and `rgb()`
+    /// also accepts percentages. An alpha value, as in `rgba()` or
+    /// `hsla()`, gets ignored, as terminals can’t show it.
+    ///
+    /// This method is only available with the `rgb` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ansi_term::Colour;
+    ///
+    /// assert_eq!(Colour::parse_css("#4682b4"), Ok(Colour::RGB(70, 130, 180)));
+    /// assert_eq!(Colour::parse_css("#fff"), Ok(Colour::RGB(255, 255, 255)));
+    /// assert_eq!(Colour::parse_css("rgb(70, 130, 180)"), Ok(Colour::RGB(70, 130, 180)));
+    /// assert_eq!(Colour::parse_css("hsl(120, 100%, 25%)"), Ok(Colour::RGB(0, 128, 0)));
+    /// assert!(Colour::parse_css("steelblue").is_err());
+    /// ```
+    pub fn parse_css(input: &str) -> Result<Colour, ParseColourError> {
+        let input = input.trim();
+
+        if let Some(hex) = input.strip_prefix('#') {
+            return parse_hex(hex);
+        }
+
+        let open = input.find('(').ok_or(ParseColourError::UnknownFormat)?;
+        let args = input[open + 1 ..].strip_suffix(')').ok_or(ParseColourError::UnknownFormat)?;
+        let args: Vec<&str> = args.split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+                                  .filter(|a| !a.is_empty())
+                                  .collect();
+
+        if args.len() != 3 && args.len() != 4 {
+            return Err(ParseColourError::InvalidComponent);
+        }
+
+        match input[.. open].trim_end().to_ascii_lowercase().as_str() {
+            "rgb" | "rgba"  => {
+                let channel = |arg: &str| match arg.strip_suffix('%') {
+                    Some(percent)  => parse_number(percent, 100.0).map(|p| p * 2.55),
+                    None           => parse_number(arg, 255.0),
+                };
+                Ok(Colour::RGB(round(channel(args[0])?), round(channel(args[1])?), round(channel(args[2])?)))
+            },
+            "hsl" | "hsla"  => {
+                let hue = parse_number(args[0].strip_suffix("deg").unwrap_or(args[0]), f32::INFINITY)?;
+                let percent = |arg: &str| arg.strip_suffix('%').ok_or(ParseColourError::InvalidComponent)
+                                             .and_then(|p| parse_number(p, 100.0))
+                                             .map(|p| p / 100.0);
+                Ok(hsl(hue % 360.0, percent(args[1])?, percent(args[2])?))
+            },
+            _  => Err(ParseColourError::UnknownFormat),
+        }
+    }
+}
+
+/// Parses the digits of a hex colour, in its long or short form.
+#[cfg(feature = "rgb")]
+fn parse_hex(hex: &str) -> Result<Colour, ParseColourError> {
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(ParseColourError::InvalidComponent);
+    }
+
+    let digit = |i: usize, len: usize| u8::from_str_radix(&hex[i * len .. (i + 1) * len], 16).unwrap();
+    match hex.len() {
+        3  => Ok(Colour::RGB(digit(0, 1) * 0x11, digit(1, 1) * 0x11, digit(2, 1) * 0x11)),
+        6  => Ok(Colour::RGB(digit(0, 2), digit(1, 2), digit(2, 2))),
+        _  => Err(ParseColourError::InvalidComponent),
+    }
+}
+
+/// Parses a non-negative number that’s no bigger than the given maximum.
+#[cfg(feature = "rgb")]
+fn parse_number(number: &str, max: f32) -> Result<f32, ParseColourError> {
+    match number.parse::<f32>() {
+        Ok(n) if (0.0 ..= max).contains(&n)  => Ok(n),
+        _                                   => Err(ParseColourError::InvalidComponent),
+    }
+}
+
+#[cfg(feature = "rgb")]
+fn round(channel: f32) -> u8 {
+    channel.round() as u8
+}
+
+/// Converts a hue in degrees, and a saturation and lightness from zero to
+/// one, into an RGB colour.
+#[cfg(feature = "rgb")]
+pub(crate) fn hsl(hue: f32, saturation: f32, lightness: f32) -> Colour {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let sector = hue / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+
+    let (r, g, b) = match sector as u32 {
+        0  => (chroma, x, 0.0),
+        1  => (x, chroma, 0.0),
+        2  => (0.0, chroma, x),
+        3  => (0.0, x, chroma),
+        4  => (x, 0.0, chroma),
+        _  => (chroma, 0.0, x),
+    };
+
+    let m = lightness - chroma / 2.0;
+    Colour::RGB(round((r + m) * 255.0), round((g + m) * 255.0), round((b + m) * 255.0))
+}
+
+/// Returns the hue in degrees, and the saturation and lightness from zero
+/// to one, of a colour.
+#[cfg(feature = "rgb")]
+pub(crate) fn to_hsl(colour: Colour) -> (f32, f32, f32) {
+    let (r, g, b) = rgb(colour);
+    let (r, g, b) = (f32::from(r) / 255.0, f32::from(g) / 255.0, f32::from(b) / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let chroma = max - min;
+    let lightness = (max + min) / 2.0;
+
+    if chroma == 0.0 {
+        return (0.0, 0.0, lightness);
+    }
+
+    let saturation = chroma / (1.0 - (2.0 * lightness - 1.0).abs());
+    let sector = if max == r      { (g - b) / chroma }
+            else if max == g      { (b - r) / chroma + 2.0 }
+            else                  { (r - g) / chroma + 4.0 };
+
+    ((sector * 60.0).rem_euclid(360.0), saturation, lightness)
+}
+
+/// Returns a colour as a CSS hex colour.
+fn css_colour(colour: Colour) -> String {
+    let (r, g, b) = rgb(colour);
+    let mut hex = String::with_capacity(7);
+    write!(hex, "#{:02x}{:02x}{:02x}", r, g, b).expect("writing to a String");
+    hex
+}
+
+/// Returns the RGB value of a colour, taking the colours that don’t have
+/// one from xterm’s palette.
+pub(crate) fn rgb(colour: Colour) -> (u8, u8, u8) {
+    match colour {
+        #[cfg(feature = "rgb")]
+        Colour::RGB(r, g, b)  => (r, g, b),
+        #[cfg(feature = "fixed")]
+        Colour::Fixed(n)      => palette(n),
+        Colour::Black         => palette(0),
+        Colour::Red           => palette(1),
+        Colour::Green         => palette(2),
+        Colour::Yellow        => palette(3),
+        Colour::Blue          => palette(4),
+        Colour::Purple        => palette(5),
+        Colour::Cyan          => palette(6),
+        Colour::White         => palette(7),
+        #[cfg(feature = "cmyk")]
+        Colour::CMY(c, m, y)  => (255 - c, 255 - m, 255 - y),
+        #[cfg(feature = "cmyk")]
+        Colour::CMYK(c, m, y, k) => {
+            let channel = |n: u8| ((255 - u16::from(n)) * (255 - u16::from(k)) / 255) as u8;
+            (channel(c), channel(m), channel(y))
+        },
+    }
+}
+
+/// Returns the RGB value of a colour in xterm’s 256-colour palette.
+pub(crate) fn palette(n: u8) -> (u8, u8, u8) {
+    const BASIC: [(u8, u8, u8); 16] = [
+        (0x00, 0x00, 0x00), (0xcd, 0x00, 0x00), (0x00, 0xcd, 0x00), (0xcd, 0xcd, 0x00),
+        (0x00, 0x00, 0xee), (0xcd, 0x00, 0xcd), (0x00, 0xcd, 0xcd), (0xe5, 0xe5, 0xe5),
+        (0x7f, 0x7f, 0x7f), (0xff, 0x00, 0x00), (0x00, 0xff, 0x00), (0xff, 0xff, 0x00),
+        (0x5c, 0x5c, 0xff), (0xff, 0x00, 0xff), (0x00, 0xff, 0xff), (0xff, 0xff, 0xff),
+    ];
+
+    match n {
+        0 ..= 15 => BASIC[n as usize],
+        16 ..= 231 => {
+            let level = |l: u8| if l == 0 { 0 } else { 55 + l * 40 };
+            let n = n - 16;
+            (level(n / 36), level(n / 6 % 6), level(n % 6))
+        },
+        _ => {
+            let grey = 8 + (n - 232) * 10;
+            (grey, grey, grey)
+        },
+    }
+}
+
+/// Returns the number of the colour in the given part of the 256-colour
+/// palette that’s closest to the given RGB value.
+pub(crate) fn nearest_palette(range: RangeInclusive<u8>, r: u8, g: u8, b: u8) -> u8 {
+    let distance = |n: u8| {
+        let (pr, pg, pb) = palette(n);
+        let d = |x: u8, y: u8| (i32::from(x) - i32::from(y)).pow(2);
+        d(r, pr) + d(g, pg) + d(b, pb)
+    };
+
+    range.min_by_key(|&n| distance(n)).expect("range is not empty")
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use style::Colour::*;
+
+    #[test]
+    fn palette_cube_and_greys() {
+        assert_eq!(palette(16), (0, 0, 0));
+        assert_eq!(palette(196), (255, 0, 0));
+        assert_eq!(palette(110), (135, 175, 215));
+        assert_eq!(palette(255), (238, 238, 238));
+    }
+
+    #[test]
+    fn nearest_in_palette() {
+        assert_eq!(nearest_palette(16 ..= 255, 255, 0, 0), 196);
+        assert_eq!(nearest_palette(16 ..= 255, 130, 170, 220), 110);
+        assert_eq!(nearest_palette(16 ..= 255, 9, 9, 9), 232);
+        assert_eq!(nearest_palette(0 ..= 7, 250, 10, 10), 1);
+    }
+
+    #[test]
+    fn reversed_without_colours() {
+        assert_eq!(Style::new().reverse().to_css(), "color: #000000; background-color: #e5e5e5");
+    }
+
+    #[test]
+    fn inline_css_for_plain_style() {
+        assert_eq!(Style::new().to_inline_css(), "");
+        assert_eq!(Style::new().italic().strikethrough().to_inline_css(),
+                   "font-style:italic;text-decoration:line-through;");
+    }
+
+    #[test]
+    fn runs_share_a_style() {
+        let message = ANSIStrings(&[ Red.paint("a"), Red.paint("b"), Blue.paint("c") ]).to_console_message();
+        assert_eq!(message.format, "%cab%cc");
+        assert_eq!(message.styles.len(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "rgb")]
+    fn css_colour_forms() {
+        assert_eq!(Colour::parse_css("rgb(100% 0% 50%)"), Ok(RGB(255, 0, 128)));
+        assert_eq!(Colour::parse_css("rgba(1, 2, 3, 0.5)"), Ok(RGB(1, 2, 3)));
+        assert_eq!(Colour::parse_css("hsl(240deg 100% 50%)"), Ok(RGB(0, 0, 255)));
+        assert_eq!(Colour::parse_css("hsl(0, 0%, 100%)"), Ok(RGB(255, 255, 255)));
+    }
+
+    #[test]
+    #[cfg(feature = "rgb")]
+    fn css_colour_errors() {
+        assert_eq!(Colour::parse_css("#12345"), Err(ParseColourError::InvalidComponent));
+        assert_eq!(Colour::parse_css("#ggg"), Err(ParseColourError::InvalidComponent));
+        assert_eq!(Colour::parse_css("rgb(256, 0, 0)"), Err(ParseColourError::InvalidComponent));
+        assert_eq!(Colour::parse_css("hsl(0, 50, 50)"), Err(ParseColourError::InvalidComponent));
+        assert_eq!(Colour::parse_css("lab(50 0 0)"), Err(ParseColourError::UnknownFormat));
+    }
+}
@@ -59,3 +59,94 @@ pub fn enable_ansi_support() -> Result<(), u32> {
 
     return Ok(());
 }
+
+
+/// The backend that sets the colours of text in a Windows console through
+/// the console API, for old versions of Windows that don’t understand
+/// escape codes.
+///
+/// The text gets flushed before each change of colour, so the writer should
+/// be the standard output that the console is showing. Only the sixteen
+/// console colours are available, so other colours get turned into the
+/// closest of the basic ones, and bold text gets shown in the bright version
+/// of its colour. Underlining and reversing work on consoles that support
+/// them; the other styles get left out.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io;
+/// use ansi_term::{ANSIStrings, WindowsConsole};
+/// use ansi_term::Colour::Red;
+///
+/// let mut console = WindowsConsole::stdout().unwrap();
+/// ANSIStrings(&[ Red.bold().paint("error") ]).write_with(&mut console, io::stdout()).unwrap();
+/// ```
+#[cfg(windows)]
+#[derive(Debug)]
+pub struct WindowsConsole {
+    handle: winapi::um::winnt::HANDLE,
+
+    /// The attributes the console had to begin with, which get used for the
+    /// plain style.
+    original: u16,
+}
+
+#[cfg(windows)]
+impl WindowsConsole {
+
+    /// Returns a backend for the console that the standard output is
+    /// showing, or the Windows error code if it isn’t a console.
+    pub fn stdout() -> Result<WindowsConsole, u32> {
+        use std::mem::zeroed;
+        use winapi::um::errhandlingapi::GetLastError;
+        use winapi::um::processenv::GetStdHandle;
+        use winapi::um::winbase::STD_OUTPUT_HANDLE;
+        use winapi::um::wincon::{GetConsoleScreenBufferInfo, CONSOLE_SCREEN_BUFFER_INFO};
+
+        unsafe {
+            let handle = GetStdHandle(STD_OUTPUT_HANDLE);
+            let mut info: CONSOLE_SCREEN_BUFFER_INFO = zeroed();
+            if 0 == GetConsoleScreenBufferInfo(handle, &mut info) {
+                return Err(GetLastError());
+            }
+
+            Ok(WindowsConsole { handle, original: info.wAttributes })
+        }
+    }
+
+    /// Returns the console attributes for the given style.
+    fn attributes(&self, style: ::Style) -> u16 {
+        use depth::ColourDepth;
+        use winapi::um::wincon::{COMMON_LVB_REVERSE_VIDEO, COMMON_LVB_UNDERSCORE, FOREGROUND_INTENSITY};
+
+        // The console’s colour bits are in blue, green, red order, the other
+        // way round from the ANSI ones.
+        let bits = |colour: Option<::Colour>| {
+            colour.and_then(|c| c.to_depth(ColourDepth::Ansi16)).and_then(::Colour::to_index)
+                  .map(|i| u16::from((i & 1) << 2 | (i & 2) | (i & 4) >> 2 | (i & 8)))
+        };
+
+        let mut attributes = self.original & !(COMMON_LVB_REVERSE_VIDEO | COMMON_LVB_UNDERSCORE);
+        if let Some(fg) = bits(style.foreground)  { attributes = (attributes & !0x0F) | fg }
+        if let Some(bg) = bits(style.background)  { attributes = (attributes & !0xF0) | bg << 4 }
+        if style.is_bold       { attributes |= FOREGROUND_INTENSITY }
+        if style.is_underline  { attributes |= COMMON_LVB_UNDERSCORE }
+        if style.is_reverse    { attributes |= COMMON_LVB_REVERSE_VIDEO }
+        attributes
+    }
+}
+
+#[cfg(windows)]
+impl ::Backend for WindowsConsole {
+    fn transition(&mut self, w: &mut dyn std::io::Write, _from: ::Style, to: ::Style) -> std::io::Result<()> {
+        use winapi::um::wincon::SetConsoleTextAttribute;
+
+        w.flush()?;
+        if 0 == unsafe { SetConsoleTextAttribute(self.handle, self.attributes(to)) } {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        Ok(())
+    }
+}
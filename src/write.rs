@@ -1,8 +1,9 @@
 use std::fmt;
-use std::io;
+use std::io::{self, IoSlice};
 
 
 pub trait AnyWrite {
+    #[allow(non_camel_case_types)]
     type wstr: ?Sized;
     type Error;
 
@@ -12,7 +13,7 @@ pub trait AnyWrite {
 }
 
 
-impl<'a> AnyWrite for fmt::Write + 'a {
+impl<'a> AnyWrite for dyn fmt::Write + 'a {
     type wstr = str;
     type Error = fmt::Error;
 
@@ -26,7 +27,7 @@ impl<'a> AnyWrite for fmt::Write + 'a {
 }
 
 
-impl<'a> AnyWrite for io::Write + 'a {
+impl<'a> AnyWrite for dyn io::Write + 'a {
     type wstr = [u8];
     type Error = io::Error;
 
@@ -38,3 +39,23 @@ impl<'a> AnyWrite for io::Write + 'a {
         io::Write::write_all(self, s)
     }
 }
+
+
+/// Writes every one of the slices to the writer, like `write_all` does for
+/// a single buffer, retrying whenever only some of them got written.
+pub fn write_all_vectored<W: io::Write + ?Sized>(w: &mut W, mut slices: &mut [IoSlice]) -> io::Result<()> {
+    IoSlice::advance_slices(&mut slices, 0);
+
+    while !slices.is_empty() {
+        match w.write_vectored(slices) {
+            Ok(0) => {
+                return Err(io::Error::new(io::ErrorKind::WriteZero, "failed to write whole buffer"));
+            },
+            Ok(n) => IoSlice::advance_slices(&mut slices, n),
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {},
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(())
+}